@@ -0,0 +1,83 @@
+//! Benchmarks for parsing large responses.
+//!
+//! Run with `cargo bench --bench parse`.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use crustacean_states::parsers::{nation::Nation, region::Region, world::World};
+use std::fmt::Write;
+
+/// A nation with most public shards, a full census, and a long endorsement list.
+fn full_nation() -> String {
+    let mut xml = String::from(
+        r#"<NATION id="aramos"><NAME>Aramos</NAME><TYPE>Republic</TYPE><FULLNAME>The Republic of Aramos</FULLNAME><MOTTO>Strength &amp; Unity</MOTTO><CATEGORY>Civil Rights Lovefest</CATEGORY><UNSTATUS>WA Delegate</UNSTATUS><ISSUES_ANSWERED>1200</ISSUES_ANSWERED><REGION>Anteria</REGION><POPULATION>25000</POPULATION><TAX>42.5</TAX><ANIMAL>dolphin</ANIMAL><CURRENCY>aramo</CURRENCY><DEMONYM>Aramosian</DEMONYM><DEMONYM2>Aramosian</DEMONYM2><DEMONYM2PLURAL>Aramosians</DEMONYM2PLURAL><FLAG>https://www.nationstates.net/images/flags/uploads/aramos.svg</FLAG><MAJORINDUSTRY>Information Technology</MAJORINDUSTRY><GOVTPRIORITY>Education</GOVTPRIORITY><FOUNDED>5 years 3 days ago</FOUNDED><FIRSTLOGIN>1500000000</FIRSTLOGIN><LASTLOGIN>1700000000</LASTLOGIN><LASTACTIVITY>2 hours ago</LASTACTIVITY><INFLUENCE>Eminence Grise</INFLUENCE><PUBLICSECTOR>30.5</PUBLICSECTOR><LEADER></LEADER><CAPITAL>Aramopolis</CAPITAL><RELIGION></RELIGION><FACTBOOKS>3</FACTBOOKS><DISPATCHES>5</DISPATCHES><DBID>123456</DBID><ENDORSEMENTS>"#,
+    );
+    let endorsements = (0..2000).map(|i| format!("nation_{i}")).collect::<Vec<_>>();
+    xml.push_str(&endorsements.join(","));
+    xml.push_str("</ENDORSEMENTS><CENSUS>");
+    for id in 0..=88 {
+        write!(
+            xml,
+            r#"<SCALE id="{id}"><SCORE>{}.25</SCORE><RANK>{}</RANK><RRANK>{}</RRANK><PRANK>12.5</PRANK><PRRANK>3.5</PRRANK></SCALE>"#,
+            id * 7,
+            id + 1,
+            id + 2,
+        )
+        .unwrap();
+    }
+    xml.push_str("</CENSUS><DISPATCHLIST>");
+    for id in 0..50 {
+        write!(
+            xml,
+            r#"<DISPATCH id="{id}"><TITLE>Dispatch {id}</TITLE><AUTHOR>aramos</AUTHOR><CATEGORY>Factbook</CATEGORY><SUBCATEGORY>Overview</SUBCATEGORY><CREATED>1500000000</CREATED><EDITED>0</EDITED><VIEWS>100</VIEWS><SCORE>10</SCORE></DISPATCH>"#,
+        )
+        .unwrap();
+    }
+    xml.push_str("</DISPATCHLIST></NATION>");
+    xml
+}
+
+/// A feeder region with thousands of nations.
+fn feeder_region() -> String {
+    let nations = (0..10_000)
+        .map(|i| format!("nation_{i}"))
+        .collect::<Vec<_>>();
+    format!(
+        r#"<REGION id="the_north_pacific"><NAME>The North Pacific</NAME><NUMNATIONS>10000</NUMNATIONS><NATIONS>{}</NATIONS><UNNATIONS>{}</UNNATIONS></REGION>"#,
+        nations.join(":"),
+        nations[..3000].join(","),
+    )
+}
+
+/// The list of every nation in the world.
+fn world_nations() -> String {
+    let nations = (0..250_000)
+        .map(|i| format!("nation_{i}"))
+        .collect::<Vec<_>>();
+    format!(
+        r#"<WORLD><NATIONS>{}</NATIONS><NUMNATIONS>250000</NUMNATIONS></WORLD>"#,
+        nations.join(","),
+    )
+}
+
+fn parse(c: &mut Criterion) {
+    let nation = full_nation();
+    c.bench_function("full nation from_xml", |b| {
+        b.iter(|| Nation::from_xml(black_box(&nation)).unwrap())
+    });
+    c.bench_function("full nation from_reader", |b| {
+        b.iter(|| Nation::from_reader(black_box(nation.as_bytes())).unwrap())
+    });
+
+    let region = feeder_region();
+    c.bench_function("feeder region from_xml", |b| {
+        b.iter(|| Region::from_xml(black_box(&region)).unwrap())
+    });
+
+    let world = world_nations();
+    c.bench_function("world nations from_xml", |b| {
+        b.iter(|| World::from_xml(black_box(&world)).unwrap())
+    });
+}
+
+criterion_group!(benches, parse);
+criterion_main!(benches);
@@ -0,0 +1,98 @@
+//! Derive macros for `crustacean-states`.
+//! Use them through the `derive` feature of `crustacean-states`, not directly.
+
+use proc_macro::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields};
+
+/// Implements `NationView` and `NSResponse` for a struct whose fields are named after
+/// fields of `Nation`.
+///
+/// Each field is requested with its shard, and must have a type
+/// that the field of `Nation` can be converted [`Into`].
+/// A field called `name` is filled in with the nation's name, which is always sent.
+/// Any other field that is not named after a field of `Nation` fails to compile.
+#[proc_macro_derive(NationView)]
+pub fn derive_nation_view(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    nation_view(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn nation_view(input: DeriveInput) -> Result<proc_macro2::TokenStream, Error> {
+    let ident = &input.ident;
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new_spanned(
+                    ident,
+                    "NationView can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new_spanned(
+                ident,
+                "NationView can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut markers = vec![];
+    let mut inits = vec![];
+    for field in fields {
+        let name = field.ident.as_ref().expect("fields are named");
+        if name == "name" {
+            inits.push(quote! {
+                #name: ::core::convert::Into::into(::core::clone::Clone::clone(&nation.name))
+            });
+            continue;
+        }
+        // `by_field` is generated from the same list as the markers themselves,
+        // so a field that is not in it fails to resolve, pointing at the field.
+        let marker = quote_spanned! {name.span()=>
+            ::crustacean_states::shards::typed::by_field::#name
+        };
+        inits.push(quote! {
+            #name: ::core::convert::Into::into(
+                <#marker as ::crustacean_states::shards::typed::NationField>::take(nation)?
+            )
+        });
+        markers.push(marker);
+    }
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::crustacean_states::shards::typed::NationView
+            for #ident #ty_generics #where_clause
+        {
+            fn shards() -> ::std::vec::Vec<
+                ::crustacean_states::shards::nation::PublicNationShard<'static>
+            > {
+                ::std::vec![
+                    #(<#markers as ::crustacean_states::shards::typed::NationField>::shard()),*
+                ]
+            }
+
+            fn from_nation(
+                nation: &mut ::crustacean_states::parsers::nation::Nation,
+            ) -> ::core::result::Result<Self, ::crustacean_states::parsers::nation::IntoNationError>
+            {
+                ::core::result::Result::Ok(Self { #(#inits),* })
+            }
+        }
+
+        impl #impl_generics ::crustacean_states::parsers::NSResponse
+            for #ident #ty_generics #where_clause
+        {
+            type Error = ::crustacean_states::parsers::nation::IntoNationError;
+
+            fn from_xml(xml: &str) -> ::core::result::Result<Self, Self::Error> {
+                let mut nation = ::crustacean_states::parsers::nation::Nation::from_xml(xml)?;
+                <Self as ::crustacean_states::shards::typed::NationView>::from_nation(&mut nation)
+            }
+        }
+    })
+}
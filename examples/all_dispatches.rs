@@ -1,8 +1,4 @@
-use crustacean_states::{
-    client::Client,
-    parsers::nation::Nation,
-    shards::nation::{PublicNationRequest, PublicNationShard::DispatchList},
-};
+use crustacean_states::{client::Client, shards::nation::PublicNationShard::DispatchList};
 use dotenvy::dotenv;
 use std::error::Error;
 use tokio::time::Instant;
@@ -16,19 +12,13 @@ async fn main() -> Result<(), Box<dyn Error>> {
     eprintln!("Made client!");
 
     let target_nation = "Testlandia";
-    let request = PublicNationRequest::new_with_shards(target_nation, [DispatchList]);
     let end1 = Instant::now();
-    // eprintln!("{request}");
-    let text = client.get(request).await?.text().await?;
-    let begin2 = Instant::now();
-    // eprintln!("{text}");
-    let response = Nation::from_xml(&text)?;
+    let response = client.get_nation(target_nation, [DispatchList]).await?;
     let end2 = Instant::now();
     println!("{:#?}", response.dispatch_list);
 
     eprintln!("Creation time: {:?}", end1 - begin1);
-    eprintln!("Request time: {:?}", begin2 - end1);
-    eprintln!("Parsing time: {:?}", end2 - begin2);
+    eprintln!("Request and parsing time: {:?}", end2 - end1);
     eprintln!("Total time: {:?}", end2 - begin1);
 
     Ok(())
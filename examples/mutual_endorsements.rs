@@ -1,11 +1,6 @@
-use crustacean_states::{
-    client::{Client, ClientError},
-    parsers::nation::Nation,
-    shards::nation::{PublicNationRequest, PublicNationShard::Endorsements},
-};
+use crustacean_states::{client::Client, shards::nation::PublicNationShard::Endorsements};
 use dotenvy::dotenv;
 use std::error::Error;
-use tokio::time::Instant;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn Error>> {
@@ -14,30 +9,15 @@ async fn main() -> Result<(), Box<dyn Error>> {
     let client = Client::new(user_agent);
     eprintln!("Made client!");
     let target = "Aramos";
-    let request = PublicNationRequest::new_with_shards(target, [Endorsements]);
-    eprintln!("{request:?}");
-    let response = client.get(request).await?;
-    let text = response.text().await?;
-    let target_nation = Nation::from_xml(&text)?;
+    let target_nation = client.get_nation(target, [Endorsements]).await?;
     let endorsements = target_nation.endorsements.unwrap();
     eprintln!("{endorsements:?}");
     let l = endorsements.len();
     let mut n = 0;
-    for endorsed_nation in endorsements {
-        let request = PublicNationRequest::new_with_shards(&endorsed_nation, vec![Endorsements]);
-        eprintln!("{request:?}");
-        let response = match client.get(request.clone()).await {
-            Ok(r) => Ok(r),
-            Err(ClientError::RateLimitedError(t)) => {
-                tokio::time::sleep_until(Instant::from(t)).await;
-                client.get(request).await
-            }
-            Err(e) => Err(e),
-        }?;
-
-        let text = response.text().await?;
-        let nation = Nation::from_xml(&text)?;
-        if nation.endorsements.unwrap().contains(&target.to_string()) {
+    for endorsed_nation in &endorsements {
+        eprintln!("Checking {endorsed_nation}");
+        let nation = client.get_nation(endorsed_nation, [Endorsements]).await?;
+        if nation.endorsements.unwrap().contains(target) {
             n += 1;
             continue;
         }
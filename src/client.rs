@@ -1,29 +1,68 @@
 //! Additional tools for making requests.
 
-use crate::shards::NSRequest;
+use crate::{
+    models::{endorsements::EndorsementGraph, name::NationName, telegram::TelegramKind},
+    parsers::{
+        nation::{Nation, StandardNation},
+        region::{Region, RegionCensusReport},
+        wa::{WaSnapshot, WorldAssembly},
+        world::World,
+        Entity, NSResponse, ParseError,
+    },
+    shards::{
+        api_url,
+        nation::{PublicNationRequest, PublicNationShard, StandardPublicNationRequest},
+        region::{RegionRequest, RegionShard},
+        telegram::TelegramRequest,
+        wa::{CouncilRequest, WACouncil, WARequest, WAShard},
+        world::{WorldRequest, WorldShard},
+        CensusRanksShard, MissingShardData, NSRequest, NSRequestParse, RequestBuildError,
+        VerifyShards,
+    },
+};
+use async_trait::async_trait;
+use futures::{stream, Stream, StreamExt};
 use reqwest::{
     header::{HeaderMap, HeaderValue},
     Response,
 };
+#[cfg(feature = "tower")]
+use std::task::{Context, Poll};
 use std::{
-    num::ParseIntError,
+    borrow::Cow,
+    collections::HashMap,
+    io::BufRead,
+    num::{NonZeroU32, ParseIntError},
     ops::Add,
     sync::{Arc, Mutex},
-    time::{Duration, Instant},
+    time::{Duration, Instant, SystemTime},
 };
 use thiserror::Error;
+use tokio_util::io::{StreamReader, SyncIoBridge};
+use url::Url;
+
+pub use crate::shards::API_VERSION;
+
+/// The shortest interval between requests
+/// that stays within the rate limit of 50 requests every 30 seconds.
+const REQUEST_INTERVAL: Duration = Duration::from_millis(600);
+/// How many requests [`Client::get_many`] keeps in flight at once.
+const MAX_CONCURRENT_REQUESTS: usize = 4;
+/// How many times [`Client::get_many`] retries a request that failed for a transient reason.
+const MAX_RETRIES: u8 = 3;
+/// How many nations the API sends in each page of census ranks.
+const CENSUS_RANKS_PAGE_SIZE: u32 = 20;
 
 /// A client helper. Uses [`reqwest`] under the surface.
+///
+/// Cloning a client is cheap, and clones share the same rate limiter.
+#[derive(Clone)]
 pub struct Client {
     client: reqwest::Client,
-    state: Arc<Mutex<ClientState>>,
-}
-
-#[derive(Clone, Debug, Default)]
-struct ClientState {
-    rate_limiter: Option<RateLimits>,
-    last_sent: Option<Instant>,
-    send_after: Option<Instant>,
+    limiter: Arc<dyn RateLimiter>,
+    api_version: Option<u8>,
+    /// The earliest moment the next telegram may be sent.
+    next_telegram: Arc<Mutex<Instant>>,
 }
 
 impl Client {
@@ -31,6 +70,12 @@ impl Client {
     /// `user_agent` needs to be [`TryInto`]<[`HeaderValue`]>,
     /// which, as of [`reqwest`] 0.11.18, is implemented for `&[u8]`, `&String`, `&str`,
     /// `String`, and `Vec<u8>`.
+    ///
+    /// The client keeps track of rate limits with an [`InMemoryRateLimiter`].
+    /// To use a different strategy, see [`Client::with_rate_limiter`].
+    ///
+    /// Every request asks for version [`API_VERSION`] of the API.
+    /// To ask for a different version, see [`Client::with_api_version`].
     pub fn new<V>(user_agent: V) -> Self
     where
         V: TryInto<HeaderValue>,
@@ -41,60 +86,755 @@ impl Client {
                 .user_agent(user_agent)
                 .build()
                 .unwrap(),
-            state: Arc::new(Mutex::new(ClientState::default())),
+            limiter: Arc::new(InMemoryRateLimiter::default()),
+            api_version: Some(API_VERSION),
+            next_telegram: Arc::new(Mutex::new(Instant::now())),
         }
     }
 
+    /// Replaces the rate limiter used by the client.
+    ///
+    /// This is useful when several processes send requests under the same User-Agent
+    /// and need to share one view of the rate limit.
+    pub fn with_rate_limiter<L>(mut self, limiter: L) -> Self
+    where
+        L: RateLimiter + 'static,
+    {
+        self.limiter = Arc::new(limiter);
+        self
+    }
+
+    /// Sets the version of the API that requests ask for.
+    /// If the version is `None`, requests get the latest version,
+    /// which may not be one that this crate can parse.
+    pub fn with_api_version(mut self, version: Option<u8>) -> Self {
+        self.api_version = version;
+        self
+    }
+
+    /// Asks the API for its current version.
+    ///
+    /// If it is not [`API_VERSION`], a warning is logged with [`tracing`],
+    /// as responses may have changed since this crate was written.
+    ///
+    /// Fails for the same reasons as [`Client::get_parsed`].
+    pub async fn check_api_version(&self) -> Result<u8, ClientError> {
+        let version = self.get_parsed(ApiVersionRequest).await?;
+        let version = version
+            .trim()
+            .parse::<u8>()
+            .map_err(|e| ParseError::new(Entity::Other, e))?;
+        if version != API_VERSION {
+            tracing::warn!(
+                live = version,
+                expected = API_VERSION,
+                "the NationStates API version is not the one crustacean-states was written for",
+            );
+        }
+        Ok(version)
+    }
+
+    /// The underlying HTTP client, for requests that do not go through the rate limiter.
+    #[cfg(feature = "sse")]
+    pub(crate) fn http(&self) -> &reqwest::Client {
+        &self.client
+    }
+
     /// Make a request of the API.
     ///
-    /// If the last request was too recent, early-return [`ClientError::RateLimitedError`].
+    /// A slot is reserved with [`RateLimiter::acquire`] first.
+    /// If none is free, early-return [`ClientError::RateLimitedError`].
+    ///
+    /// If the request could not be built, return [`ClientError::RequestBuildError`].
     ///
     /// If there was an error in the [`reqwest`] crate, return [`ClientError::ReqwestError`].
     // Note: this function cannot be tested because it is `async`.
     pub async fn get<U: NSRequest>(&self, request: U) -> Result<Response, ClientError> {
-        // If the client was told that it should not send until some time after now,
-        if let Some(t) = self
-            .state
-            .lock()
-            .unwrap()
-            .send_after
-            .filter(|t| *t > Instant::now())
-        {
-            // Raise an error detailing when the request should have been sent.
-            return Err(ClientError::RateLimitedError(t));
+        let url = request.with_api_version(self.api_version).as_url()?;
+
+        // Reserve a slot, so that clients sharing the limiter cannot all send at once.
+        if let Err(wait) = self.limiter.acquire().await {
+            // Raise an error detailing when the request should be sent.
+            return Err(ClientError::RateLimitedError(Instant::now() + wait));
         }
 
-        match self.client.get(request.as_url()).send().await {
+        match self.client.get(url).send().await {
             Ok(r) => {
-                let mut state = self.state.lock().unwrap();
-                state.rate_limiter = Some(RateLimits::new(r.headers())?);
-                state.last_sent = Some(Instant::now());
-                if let Some(ref r) = state.rate_limiter {
-                    state.send_after = if r.remaining == 0 {
-                        Some(r.reset)
-                    } else {
-                        r.retry_after
-                    }
-                    .map(|t| state.last_sent.unwrap().add(Duration::from_secs(t as u64)))
-                }
+                self.limiter
+                    .update(RateLimits::new(r.headers())?, SystemTime::now())
+                    .await;
                 Ok(r)
             }
             Err(e) => Err(ClientError::ReqwestError { source: e }),
         }
     }
 
+    /// Make a request of the API, then parse the response
+    /// into the request's [`Response`](NSRequestParse::Response) type.
+    ///
+    /// Fails for the same reasons as [`Client::get`].
+    /// In addition, if the API responds with an error status,
+    /// return [`ClientError::ReqwestError`],
+    /// and if the response could not be parsed, return [`ClientError::ParseError`].
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use crustacean_states::client::Client;
+    /// # use crustacean_states::shards::nation::{PublicNationRequest, PublicNationShard};
+    /// # use std::error::Error;
+    /// # async fn test() -> Result<(), Box<dyn Error>> {
+    /// # let client = Client::new("");
+    /// let request = PublicNationRequest::new_with_shards("Aramos", [PublicNationShard::Motto]);
+    /// let nation = client.get_parsed(request).await?;
+    /// println!("{:?}", nation.motto);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_parsed<U: NSRequestParse>(
+        &self,
+        request: U,
+    ) -> Result<U::Response, ClientError> {
+        let text = self.get(request).await?.error_for_status()?.text().await?;
+        U::Response::from_xml(&text).map_err(|e| ClientError::ParseError { source: e.into() })
+    }
+
+    /// Like [`Client::get_parsed`],
+    /// but also checks that every requested shard has data in the response.
+    ///
+    /// If any shards are missing, return [`ClientError::MissingShardData`] listing them.
+    /// This catches shards that NationStates has stopped sending,
+    /// which would otherwise be silently left as `None`.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use crustacean_states::client::Client;
+    /// # use crustacean_states::shards::nation::{PublicNationRequest, PublicNationShard};
+    /// # use std::error::Error;
+    /// # async fn test() -> Result<(), Box<dyn Error>> {
+    /// # let client = Client::new("");
+    /// let request = PublicNationRequest::new_with_shards("Aramos", [PublicNationShard::Flag]);
+    /// let flag = client.get_verified(request).await?.flag.unwrap();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_verified<U: VerifyShards>(
+        &self,
+        request: U,
+    ) -> Result<U::Response, ClientError> {
+        let response = self.get_parsed(&request).await?;
+        request.verify(&response)?;
+        Ok(response)
+    }
+
+    /// Fetch a nation with the given shards and parse it into a [`Nation`].
+    ///
+    /// Unlike [`Client::get`], this waits until the rate limit allows the request to be sent.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use crustacean_states::client::Client;
+    /// # use crustacean_states::shards::nation::PublicNationShard;
+    /// # use std::error::Error;
+    /// # async fn test() -> Result<(), Box<dyn Error>> {
+    /// # let client = Client::new("");
+    /// let nation = client
+    ///     .get_nation("Aramos", [PublicNationShard::Capital, PublicNationShard::Motto])
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_nation<'a, T>(
+        &self,
+        nation: impl Into<Cow<'a, str>>,
+        shards: T,
+    ) -> Result<Nation, ClientError>
+    where
+        T: AsRef<[PublicNationShard<'a>]>,
+    {
+        self.wait_until_ready().await;
+        Nation::from_response(
+            self.get(PublicNationRequest::new_with_shards(nation, shards))
+                .await?,
+        )
+        .await
+    }
+
+    /// Fetch a nation using the standard shard set and parse it into a [`StandardNation`].
+    /// See [`StandardPublicNationRequest`] for what "standard" means.
+    ///
+    /// Unlike [`Client::get`], this waits until the rate limit allows the request to be sent.
+    pub async fn get_standard_nation<'a>(
+        &self,
+        nation: impl Into<Cow<'a, str>>,
+    ) -> Result<StandardNation, ClientError> {
+        self.wait_until_ready().await;
+        StandardNation::from_response(self.get(StandardPublicNationRequest::new(nation)).await?)
+            .await
+    }
+
+    /// Fetch a region with the given shards and parse it into a [`Region`].
+    /// If `shards` is empty, the standard region shard set is requested.
+    ///
+    /// Unlike [`Client::get`], this waits until the rate limit allows the request to be sent.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use crustacean_states::client::Client;
+    /// # use crustacean_states::shards::region::RegionShard;
+    /// # use std::error::Error;
+    /// # async fn test() -> Result<(), Box<dyn Error>> {
+    /// # let client = Client::new("");
+    /// let region = client
+    ///     .get_region("Testregionia", [RegionShard::Delegate, RegionShard::NumNations])
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_region<'a, T>(
+        &self,
+        region: impl Into<Cow<'a, str>>,
+        shards: T,
+    ) -> Result<Region, ClientError>
+    where
+        T: AsRef<[RegionShard<'a>]>,
+    {
+        self.wait_until_ready().await;
+        Region::from_response(
+            self.get(RegionRequest::new_with_shards(region, shards))
+                .await?,
+        )
+        .await
+    }
+
+    /// Fetch information about the world with the given shards and parse it into a [`World`].
+    ///
+    /// Unlike [`Client::get`], this waits until the rate limit allows the request to be sent.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use crustacean_states::client::Client;
+    /// # use crustacean_states::shards::world::WorldShard;
+    /// # use std::error::Error;
+    /// # async fn test() -> Result<(), Box<dyn Error>> {
+    /// # let client = Client::new("");
+    /// let world = client
+    ///     .get_world([WorldShard::FeaturedRegion, WorldShard::NumNations])
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_world<'a, T>(&self, shards: T) -> Result<World, ClientError>
+    where
+        T: AsRef<[WorldShard<'a>]>,
+    {
+        let mut request = WorldRequest::new_empty();
+        request.add_shards(shards.as_ref().to_vec());
+        self.wait_until_ready().await;
+        World::from_response(self.get(request).await?).await
+    }
+
+    /// Fetch information about a World Assembly council with the given shards
+    /// and parse it into a [`WorldAssembly`].
+    ///
+    /// Unlike [`Client::get`], this waits until the rate limit allows the request to be sent.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use crustacean_states::client::Client;
+    /// # use crustacean_states::shards::wa::{ResolutionShard, WACouncil, WAShard};
+    /// # use std::error::Error;
+    /// # async fn test() -> Result<(), Box<dyn Error>> {
+    /// # let client = Client::new("");
+    /// let wa = client
+    ///     .get_wa(
+    ///         WACouncil::GeneralAssembly,
+    ///         &[WAShard::from([ResolutionShard::Voters].as_slice())],
+    ///     )
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_wa<'a>(
+        &self,
+        council: WACouncil,
+        shards: &'a [WAShard<'a>],
+    ) -> Result<WorldAssembly, ClientError> {
+        self.wait_until_ready().await;
+        WorldAssembly::from_response(
+            self.get(WARequest::Council(CouncilRequest::new(council, shards)))
+                .await?,
+        )
+        .await
+    }
+
+    /// Fetch a [`WaSnapshot`] of a World Assembly council:
+    /// the resolution at vote with its vote track and delegate votes,
+    /// and the result of the last resolution, in a single request.
+    ///
+    /// Unlike [`Client::get`], this waits until the rate limit allows the request to be sent.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use crustacean_states::client::Client;
+    /// # use crustacean_states::shards::wa::WACouncil;
+    /// # use std::error::Error;
+    /// # async fn test() -> Result<(), Box<dyn Error>> {
+    /// # let client = Client::new("");
+    /// let snapshot = client.get_wa_snapshot(WACouncil::SecurityCouncil).await?;
+    /// if let Some(resolution) = snapshot.resolution {
+    ///     println!("{}: {:?}", resolution.name, resolution.vote_breakdown());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_wa_snapshot(&self, council: WACouncil) -> Result<WaSnapshot, ClientError> {
+        self.wait_until_ready().await;
+        Ok(WaSnapshot::from(
+            WorldAssembly::from_response(self.get(WaSnapshot::request(council)).await?).await?,
+        ))
+    }
+
+    /// Fetch the World Assembly members of a region and their endorsements,
+    /// and build an [`EndorsementGraph`] from them.
+    ///
+    /// This sends one request for the region and one for every member,
+    /// paced and retried like [`Client::get_many`],
+    /// so it can take a while for large regions.
+    /// Members whose endorsements still could not be fetched are listed,
+    /// with the reason, in [`EndorsementGraph::unfetched`]
+    /// instead of failing the whole graph.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use crustacean_states::client::Client;
+    /// # use std::error::Error;
+    /// # async fn test() -> Result<(), Box<dyn Error>> {
+    /// # let client = Client::new("");
+    /// let graph = client.get_endorsement_graph("Testregionia").await?;
+    /// for nation in graph.missing_endorsements("Testlandia") {
+    ///     println!("Testlandia should endorse {}", nation.pretty_name());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_endorsement_graph<'a>(
+        &self,
+        region: impl Into<Cow<'a, str>>,
+    ) -> Result<EndorsementGraph, ClientError> {
+        let members: Vec<NationName> = self
+            .get_region(region, [RegionShard::WANations])
+            .await?
+            .wa_nations
+            .unwrap_or_default()
+            .iter()
+            .map(NationName::from)
+            .collect();
+        let requests = members.iter().map(|member| {
+            PublicNationRequest::new_with_shards(
+                member.safe_name(),
+                [PublicNationShard::Endorsements],
+            )
+        });
+        let results = self
+            .get_many(requests)
+            .map(|(request, result)| {
+                (
+                    NationName::from(request.nation_name().unwrap_or_default()),
+                    result,
+                )
+            })
+            .collect()
+            .await;
+        Ok(endorsement_graph(&members, results))
+    }
+
+    /// Fetch how every nation in a region ranks on a World Census scale,
+    /// following [`RegionShard::CensusRanks`] page by page until the last nation.
+    ///
+    /// Each page is a separate request of 20 nations,
+    /// waiting until the rate limit allows it to be sent,
+    /// so large regions take a while.
+    /// The first page also asks for [`RegionShard::NumNations`],
+    /// so that no request is sent for a page past the last nation.
+    /// If any page fails, so does the whole report.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use crustacean_states::client::Client;
+    /// # use std::error::Error;
+    /// # async fn test() -> Result<(), Box<dyn Error>> {
+    /// # let client = Client::new("");
+    /// // World Assembly Endorsements
+    /// let report = client.region_census_report("Testregionia", 66).await?;
+    /// println!("fetched {} pages in {:?}", report.pages, report.elapsed);
+    /// for nation in report.nations {
+    ///     println!("{:?}. {}: {:?}", nation.rank, nation.name, nation.score);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn region_census_report<'a>(
+        &self,
+        region: impl Into<Cow<'a, str>>,
+        scale: u8,
+    ) -> Result<RegionCensusReport, ClientError> {
+        let started = Instant::now();
+        let region = region.into();
+        let mut nations = Vec::new();
+        let mut pages = 0;
+        let mut num_nations = None;
+        let mut next = Some(NonZeroU32::MIN);
+        while let Some(start) = next {
+            let mut shards = vec![RegionShard::CensusRanks(CensusRanksShard::new(
+                scale, start,
+            ))];
+            if pages == 0 {
+                shards.push(RegionShard::NumNations);
+            }
+            let response = self.get_region(region.clone(), shards).await?;
+            pages += 1;
+            num_nations = num_nations.or(response.num_nations);
+            let page = response.census_ranks.map(|r| r.nations).unwrap_or_default();
+            next = next_census_page(start, page.len() as u32, num_nations);
+            nations.extend(page);
+        }
+        Ok(RegionCensusReport {
+            scale,
+            nations,
+            pages,
+            elapsed: started.elapsed(),
+        })
+    }
+
+    /// Check whether each of `nations` will accept a telegram of a given kind,
+    /// optionally from the region `from`.
+    ///
+    /// Each nation needs its own request,
+    /// so the checks are sent with [`Client::get_many`] to stay within the rate limit.
+    /// A failed check only affects its own nation,
+    /// which is mapped to the error instead of to whether it accepts the telegram.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use crustacean_states::client::Client;
+    /// # use crustacean_states::models::telegram::TelegramKind;
+    /// # async fn test() {
+    /// # let client = Client::new("");
+    /// let checks = client
+    ///     .check_telegrams(["Aramos", "Testlandia"], Some("Testregionia"), TelegramKind::Recruitment)
+    ///     .await;
+    /// for (nation, check) in checks {
+    ///     match check {
+    ///         Ok(true) => println!("{} can be recruited", nation.pretty_name()),
+    ///         Ok(false) => println!("{} cannot be recruited", nation.pretty_name()),
+    ///         Err(e) => println!("{} could not be checked: {e}", nation.pretty_name()),
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub async fn check_telegrams<I, N>(
+        &self,
+        nations: I,
+        from: Option<&str>,
+        kind: TelegramKind,
+    ) -> HashMap<NationName, Result<bool, ClientError>>
+    where
+        I: IntoIterator<Item = N>,
+        N: Into<NationName>,
+    {
+        let requests = nations
+            .into_iter()
+            .map(|nation| {
+                PublicNationRequest::new_with_shards(nation.into().safe_name(), [kind.shard(from)])
+            })
+            .collect::<Vec<_>>();
+        self.get_many(requests)
+            .map(|(request, result)| {
+                let nation = NationName::from(request.nation_name().unwrap_or_default());
+                let accepts = result.map(|n| match kind {
+                    TelegramKind::Recruitment => n.tg_can_recruit,
+                    TelegramKind::Campaign => n.tg_can_campaign,
+                });
+                (nation, accepts.map(Option::unwrap_or_default))
+            })
+            .collect()
+            .await
+    }
+
+    /// Send a telegram, waiting until both the telegram rate limit and the API rate limit
+    /// allow it to be sent.
+    ///
+    /// The telegram rate limit depends on the kind of telegram that was sent last:
+    /// see [`TelegramKind::interval`].
+    /// Clones of the client share it, so telegrams sent from several tasks are spaced out.
+    ///
+    /// If the API does not queue the telegram, return [`ClientError::TelegramError`].
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use crustacean_states::client::Client;
+    /// # use crustacean_states::models::telegram::TelegramKind;
+    /// # use crustacean_states::shards::telegram::TelegramRequest;
+    /// # use std::error::Error;
+    /// # async fn test() -> Result<(), Box<dyn Error>> {
+    /// # let client = Client::new("");
+    /// let request = TelegramRequest::new("client_key", "12345", "secret_key", "Testlandia");
+    /// client.send_telegram(request, TelegramKind::Campaign).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn send_telegram(
+        &self,
+        request: TelegramRequest<'_>,
+        kind: TelegramKind,
+    ) -> Result<(), ClientError> {
+        let slot = {
+            let mut next = self.next_telegram.lock().unwrap();
+            let slot = (*next).max(Instant::now());
+            *next = slot + kind.interval();
+            slot
+        };
+        tokio::time::sleep_until(slot.into()).await;
+        self.wait_until_ready().await;
+        let response = self.get_parsed(request).await?;
+        if response.trim() == "queued" {
+            Ok(())
+        } else {
+            Err(ClientError::TelegramError(response.trim().to_string()))
+        }
+    }
+
+    /// Fetch and parse any number of requests,
+    /// yielding each request alongside its result as soon as it is available.
+    ///
+    /// Requests are spread evenly so that they never exceed the rate limit,
+    /// a few are kept in flight at once,
+    /// and requests that fail for a transient reason (see [`ClientError::is_transient`])
+    /// are retried a few times before their error is yielded.
+    /// Results are not necessarily yielded in the order that the requests were given.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use crustacean_states::client::Client;
+    /// # use crustacean_states::shards::nation::{PublicNationRequest, PublicNationShard};
+    /// # use futures::StreamExt;
+    /// # async fn test() {
+    /// # let client = Client::new("");
+    /// let requests = ["Aramos", "Testlandia"]
+    ///     .map(|n| PublicNationRequest::new_with_shards(n, [PublicNationShard::Motto]));
+    /// let mut results = std::pin::pin!(client.get_many(requests));
+    /// while let Some((request, result)) = results.next().await {
+    ///     println!("{request:?}: {:?}", result.map(|n| n.motto));
+    /// }
+    /// # }
+    /// ```
+    pub fn get_many<'c, U, I>(
+        &'c self,
+        requests: I,
+    ) -> impl Stream<Item = (U, Result<U::Response, ClientError>)> + 'c
+    where
+        U: NSRequestParse + Clone + 'c,
+        I: IntoIterator<Item = U>,
+        I::IntoIter: 'c,
+    {
+        let next_slot = Arc::new(Mutex::new(Instant::now()));
+        stream::iter(requests)
+            .map(move |request| {
+                let next_slot = Arc::clone(&next_slot);
+                async move {
+                    let mut retries = 0;
+                    let result = loop {
+                        let slot = {
+                            let mut next = next_slot.lock().unwrap();
+                            let slot = (*next).max(Instant::now());
+                            *next = slot + REQUEST_INTERVAL;
+                            slot
+                        };
+                        tokio::time::sleep_until(slot.into()).await;
+                        self.wait_until_ready().await;
+                        match self.get_parsed(request.clone()).await {
+                            Err(e) if e.is_transient() && retries < MAX_RETRIES => retries += 1,
+                            result => break result,
+                        }
+                    };
+                    (request, result)
+                }
+            })
+            .buffer_unordered(MAX_CONCURRENT_REQUESTS)
+    }
+
+    /// Waits until the rate limiter allows another request to be sent.
+    ///
+    /// Unlike [`Client::get`], which returns [`ClientError::RateLimitedError`]
+    /// if a request is sent too early,
+    /// this lets you pace requests cooperatively:
+    /// ```rust
+    /// # use crustacean_states::client::Client;
+    /// # use crustacean_states::shards::region::{RegionRequest, RegionShard};
+    /// # use std::error::Error;
+    /// # async fn test() -> Result<(), Box<dyn Error>> {
+    /// # let client = Client::new("");
+    /// for region in ["Anteria", "Europeia"] {
+    ///     client.wait_until_ready().await;
+    ///     let response = client
+    ///         .get(RegionRequest::new_with_shards(region, &[RegionShard::NumNations]))
+    ///         .await?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn wait_until_ready(&self) {
+        loop {
+            let wait = self.ready_in().await;
+            if wait.is_zero() {
+                break;
+            }
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// The number of requests that can still be sent before the rate limit resets.
+    /// If no request has been made yet, returns `None`.
+    pub async fn remaining_requests(&self) -> Option<u8> {
+        self.limiter.limits().await.map(|r| r.remaining)
+    }
+
+    /// How long until the rate limit resets.
+    /// If no request has been made yet, returns `None`.
+    pub async fn reset_in(&self) -> Option<Duration> {
+        self.limiter.limits().await.map(|r| r.reset_in())
+    }
+
+    /// How long until the next request may be sent.
+    /// If a request may be sent right away, returns [`Duration::ZERO`].
+    pub async fn ready_in(&self) -> Duration {
+        self.limiter
+            .send_after()
+            .await
+            .and_then(|t| t.duration_since(SystemTime::now()).ok())
+            .unwrap_or_default()
+    }
+
     /// Estimates the length of time to wait between each request to avoid a
     /// 429 Too Many Requests error.
-    pub fn wait_duration(&self) -> Option<Duration> {
-        self.state
-            .lock()
-            .unwrap()
-            .rate_limiter
-            .as_ref()
+    pub async fn wait_duration(&self) -> Option<Duration> {
+        self.limiter
+            .limits()
+            .await
             .map(|r| Duration::from_secs_f64(r.remaining as f64 / r.reset as f64))
     }
 }
 
+/// With the `tower` feature, a client is a [`Service`](tower_service::Service)
+/// for every request that owns its data,
+/// so it can be wrapped in `tower`'s retry, timeout and load-shedding layers.
+///
+/// The client is always ready.
+/// Each call waits until the rate limiter allows a request, like [`Client::wait_until_ready`],
+/// then sends the request and parses the response, like [`Client::get_parsed`].
+/// Retry policies can use [`ClientError::is_transient`] to decide what to retry.
+#[cfg(feature = "tower")]
+impl<U> tower_service::Service<U> for Client
+where
+    U: NSRequestParse + Send + 'static,
+    U::Response: Send,
+{
+    type Response = U::Response;
+    type Error = ClientError;
+    type Future = futures::future::BoxFuture<'static, Result<U::Response, ClientError>>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: U) -> Self::Future {
+        let client = self.clone();
+        Box::pin(async move {
+            client.wait_until_ready().await;
+            client.get_parsed(request).await
+        })
+    }
+}
+
+/// Decides when a [`Client`] may send its next request.
+///
+/// The default implementation, [`InMemoryRateLimiter`], only knows about requests
+/// sent by the process it lives in.
+/// If multiple processes send requests under one User-Agent,
+/// implement this trait on top of a shared store (Redis, a database, etc.)
+/// so that every process respects the same limit.
+/// Times are wall-clock times, so that they mean the same thing in every process,
+/// and [`RateLimiter::acquire`] must check and reserve a slot in one atomic step
+/// (in Redis, for example, with a Lua script).
+#[async_trait]
+pub trait RateLimiter: Send + Sync {
+    /// Reserves a slot for one request, which the client then sends right away.
+    ///
+    /// Fails with how long to wait before trying again if no slot is free.
+    async fn acquire(&self) -> Result<(), Duration>;
+
+    /// The earliest time the next request may be sent, without reserving it.
+    /// `None` means that a request may be sent right away.
+    async fn send_after(&self) -> Option<SystemTime>;
+
+    /// Records the rate limits that the API sent back
+    /// in response to a request that completed at `sent_at`.
+    async fn update(&self, limits: RateLimits, sent_at: SystemTime);
+
+    /// The most recent rate limits sent by the API, if a request has been made.
+    async fn limits(&self) -> Option<RateLimits>;
+}
+
+/// The default [`RateLimiter`], which keeps its state in memory.
+#[derive(Debug, Default)]
+pub struct InMemoryRateLimiter {
+    state: Mutex<LimiterState>,
+}
+
+#[derive(Clone, Debug, Default)]
+struct LimiterState {
+    limits: Option<RateLimits>,
+    /// The requests left until `reset_at`, counting the slots reserved since the last response.
+    remaining: Option<u8>,
+    reset_at: Option<SystemTime>,
+    send_after: Option<SystemTime>,
+}
+
+#[async_trait]
+impl RateLimiter for InMemoryRateLimiter {
+    async fn acquire(&self) -> Result<(), Duration> {
+        let now = SystemTime::now();
+        let wait_until = |t: SystemTime| t.duration_since(now).ok().filter(|d| !d.is_zero());
+        let mut state = self.state.lock().unwrap();
+        if let Some(wait) = state.send_after.and_then(wait_until) {
+            return Err(wait);
+        }
+        match (state.remaining, state.reset_at.and_then(wait_until)) {
+            (Some(0), Some(wait)) => return Err(wait),
+            (Some(remaining), Some(_)) => state.remaining = Some(remaining - 1),
+            // The timeframe is over, so the limits are not known again until the next response.
+            _ => state.remaining = None,
+        }
+        Ok(())
+    }
+
+    async fn send_after(&self) -> Option<SystemTime> {
+        self.state.lock().unwrap().send_after
+    }
+
+    async fn update(&self, limits: RateLimits, sent_at: SystemTime) {
+        let mut state = self.state.lock().unwrap();
+        state.send_after = limits.send_after(sent_at);
+        state.remaining = Some(limits.remaining);
+        state.reset_at = Some(sent_at + Duration::from_secs(limits.reset as u64));
+        state.limits = Some(limits);
+    }
+
+    async fn limits(&self) -> Option<RateLimits> {
+        self.state.lock().unwrap().limits.clone()
+    }
+}
+
 /// Describes the various errors that may come about from using [`Client`].
 #[derive(Debug, Error)]
 #[non_exhaustive]
@@ -142,10 +882,155 @@ pub enum ClientError {
         /// The parent error.
         source: ParseIntError,
     },
+    /// The response could not be parsed into the type that the request asked for.
+    #[error("could not parse response")]
+    ParseError {
+        /// The parent error.
+        #[from]
+        source: ParseError,
+    },
+    /// The response did not contain data for some of the requested shards.
+    /// See [`Client::get_verified`].
+    #[error("response was incomplete")]
+    MissingShardData {
+        /// The parent error.
+        #[from]
+        source: MissingShardData,
+    },
+    /// The request could not be turned into a URL.
+    #[error("could not build request")]
+    RequestBuildError {
+        /// The parent error.
+        #[from]
+        source: RequestBuildError,
+    },
     /// If you shouldn't send a request until later, this error will rate-limit you.
     /// Your request is perfectly fine, wait until your timeout is over.
     #[error("rate limited until {0:?}")]
     RateLimitedError(Instant),
+    /// The API did not queue a telegram, and answered with this instead.
+    #[error("telegram was not queued: {0}")]
+    TelegramError(String),
+}
+
+impl ClientError {
+    /// Whether sending the same request again later might succeed.
+    ///
+    /// This is true if the client was rate-limited,
+    /// if the request timed out or could not connect,
+    /// or if the server responded with 429 Too Many Requests or a 5xx status.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            ClientError::RateLimitedError(_) => true,
+            ClientError::ReqwestError { source } => {
+                source.is_timeout()
+                    || source.is_connect()
+                    || source.status().is_some_and(|s| {
+                        s == reqwest::StatusCode::TOO_MANY_REQUESTS || s.is_server_error()
+                    })
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Parses the body of a response as it arrives, for the `from_response` methods of the parsers.
+///
+/// The body is read chunk by chunk on a blocking thread and handed to `parse` as a [`BufRead`],
+/// so it is never held in memory all at once.
+/// If reading the body fails partway through, that is reported as [`ClientError::ReqwestError`],
+/// not as the parse error it causes.
+pub(crate) async fn parse_response<T, E>(
+    response: Response,
+    parse: impl FnOnce(Box<dyn BufRead + Send>) -> Result<T, E> + Send + 'static,
+) -> Result<T, ClientError>
+where
+    T: Send + 'static,
+    E: Into<ParseError> + Send + 'static,
+{
+    let read_error = Arc::new(Mutex::new(None));
+    let body = StreamReader::new(response.error_for_status()?.bytes_stream().map({
+        let read_error = Arc::clone(&read_error);
+        move |chunk| {
+            chunk.map_err(|e| {
+                let io_error = std::io::Error::other(e.to_string());
+                *read_error.lock().unwrap() = Some(e);
+                io_error
+            })
+        }
+    }));
+    let parsed = tokio::task::spawn_blocking(move || parse(Box::new(SyncIoBridge::new(body))))
+        .await
+        .unwrap_or_else(|e| std::panic::resume_unwind(e.into_panic()));
+    let read_error = read_error.lock().unwrap().take();
+    match read_error {
+        Some(source) => Err(ClientError::ReqwestError { source }),
+        None => parsed.map_err(|e| ClientError::ParseError { source: e.into() }),
+    }
+}
+
+/// Where the page of census ranks after the one starting at `start` starts,
+/// or `None` if the page of `len` nations was the last.
+///
+/// A short page is always the last.
+/// A full one is the last if it reached `num_nations`, when that is known.
+fn next_census_page(start: NonZeroU32, len: u32, num_nations: Option<u32>) -> Option<NonZeroU32> {
+    if len < CENSUS_RANKS_PAGE_SIZE {
+        return None;
+    }
+    let next = start.saturating_add(len);
+    match num_nations {
+        Some(num_nations) if next.get() > num_nations => None,
+        _ => Some(next),
+    }
+}
+
+/// Builds an [`EndorsementGraph`] from the results of fetching each member's endorsements,
+/// keeping the order of `members`.
+fn endorsement_graph(
+    members: &[NationName],
+    mut results: HashMap<NationName, Result<Nation, ClientError>>,
+) -> EndorsementGraph {
+    let mut graph = EndorsementGraph::new();
+    for member in members {
+        match results.remove(member) {
+            Some(Ok(nation)) => {
+                graph.add_endorsements(member.clone(), &nation.endorsements.unwrap_or_default())
+            }
+            Some(Err(e)) => graph.add_unfetched(member.clone(), describe_error(&e)),
+            None => graph.add_unfetched(member.clone(), "no response"),
+        }
+    }
+    graph
+}
+
+/// Describes an error with its sources, which are often more useful than the error itself.
+/// Sources that the message already includes are not repeated.
+pub(crate) fn describe_error(error: &(dyn std::error::Error + 'static)) -> String {
+    let mut message = error.to_string();
+    let mut source = error.source();
+    while let Some(e) = source {
+        let text = e.to_string();
+        if !message.contains(&text) {
+            message.push_str(": ");
+            message.push_str(&text);
+        }
+        source = e.source();
+    }
+    message
+}
+
+/// Asks for the current version of the API.
+struct ApiVersionRequest;
+
+impl NSRequest for ApiVersionRequest {
+    fn as_url(&self) -> Result<Url, RequestBuildError> {
+        Ok(api_url([("a", "version")])?)
+    }
+}
+
+impl NSRequestParse for ApiVersionRequest {
+    type Response = String;
 }
 
 /// A simple tool to help with NationStates rate limits.
@@ -164,6 +1049,7 @@ pub struct RateLimits {
     remaining: u8,
     reset: u8,
     retry_after: Option<u8>,
+    received: SystemTime,
 }
 
 impl RateLimits {
@@ -211,6 +1097,7 @@ impl RateLimits {
             remaining,
             reset,
             retry_after,
+            received: SystemTime::now(),
         })
     }
 
@@ -229,10 +1116,55 @@ impl RateLimits {
     pub fn retry_after(&self) -> Option<u8> {
         self.retry_after
     }
+
+    /// The time left until the timeframe resets,
+    /// measured from when the headers were received.
+    pub fn reset_in(&self) -> Duration {
+        self.received
+            .add(Duration::from_secs(self.reset as u64))
+            .duration_since(SystemTime::now())
+            .unwrap_or_default()
+    }
+
+    /// Given the time a request was sent,
+    /// calculates the earliest time the next request may be sent.
+    /// If the next request may be sent right away, returns `None`.
+    pub fn send_after(&self, sent_at: SystemTime) -> Option<SystemTime> {
+        if self.remaining == 0 {
+            Some(self.reset)
+        } else {
+            self.retry_after
+        }
+        .map(|t| sent_at.add(Duration::from_secs(t as u64)))
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    #[cfg(feature = "tower")]
+    #[tokio::test]
+    async fn tower_service() {
+        use crate::{
+            client::{Client, ClientError},
+            shards::{nation::PublicNationRequest, RequestBuildError},
+        };
+        use std::future::poll_fn;
+        use tower_service::Service;
+
+        let mut client = Client::new("crustacean-states tests");
+        poll_fn(|cx| Service::<PublicNationRequest>::poll_ready(&mut client, cx))
+            .await
+            .unwrap();
+        // The request fails to build before anything is sent.
+        let result = client.call(PublicNationRequest::default()).await;
+        assert!(matches!(
+            result,
+            Err(ClientError::RequestBuildError {
+                source: RequestBuildError::MissingParam("nation")
+            })
+        ));
+    }
+
     #[test]
     fn new_rate_limits() {
         use crate::client::RateLimits;
@@ -263,4 +1195,226 @@ mod tests {
         assert_eq!(limits.reset(), 25);
         assert_eq!(limits.retry_after(), Some(7));
     }
+
+    #[test]
+    fn reset_in_counts_down() {
+        use crate::client::RateLimits;
+        use reqwest::header::{HeaderMap, HeaderValue};
+        use std::time::Duration;
+
+        let mut headers = HeaderMap::new();
+        headers.insert("RateLimit-Remaining", HeaderValue::from(11));
+        headers.insert("RateLimit-Reset", HeaderValue::from(25));
+
+        let limits = RateLimits::new(&headers).unwrap();
+        assert!(limits.reset_in() <= Duration::from_secs(25));
+        assert!(limits.reset_in() > Duration::from_secs(24));
+    }
+
+    #[test]
+    fn send_after_retry() {
+        use crate::client::RateLimits;
+        use reqwest::header::{HeaderMap, HeaderValue};
+        use std::time::{Duration, SystemTime};
+
+        let mut headers = HeaderMap::new();
+        headers.insert("RateLimit-Remaining", HeaderValue::from(11));
+        headers.insert("RateLimit-Reset", HeaderValue::from(25));
+        headers.insert("Retry-After", HeaderValue::from(7));
+
+        let now = SystemTime::now();
+        let limits = RateLimits::new(&headers).unwrap();
+        assert_eq!(limits.send_after(now), Some(now + Duration::from_secs(7)));
+    }
+
+    #[test]
+    fn send_after_none_remaining() {
+        use crate::client::RateLimits;
+        use reqwest::header::{HeaderMap, HeaderValue};
+        use std::time::{Duration, SystemTime};
+
+        let mut headers = HeaderMap::new();
+        headers.insert("RateLimit-Remaining", HeaderValue::from(0));
+        headers.insert("RateLimit-Reset", HeaderValue::from(25));
+
+        let now = SystemTime::now();
+        let limits = RateLimits::new(&headers).unwrap();
+        assert_eq!(limits.send_after(now), Some(now + Duration::from_secs(25)));
+    }
+
+    #[tokio::test]
+    async fn shared_rate_limiter() {
+        use crate::{
+            client::{Client, ClientError, RateLimiter, RateLimits},
+            shards::world::{WorldRequest, WorldShard},
+        };
+        use async_trait::async_trait;
+        use reqwest::header::{HeaderMap, HeaderValue};
+        use std::{
+            sync::{Arc, Mutex},
+            time::{Duration, SystemTime, UNIX_EPOCH},
+        };
+
+        /// Stands in for a store shared between processes,
+        /// which only holds plain numbers and Unix timestamps.
+        #[derive(Clone, Default)]
+        struct SharedLimiter(Arc<Mutex<(u8, u64)>>);
+
+        fn unix(time: SystemTime) -> u64 {
+            time.duration_since(UNIX_EPOCH).unwrap().as_secs()
+        }
+
+        #[async_trait]
+        impl RateLimiter for SharedLimiter {
+            async fn acquire(&self) -> Result<(), Duration> {
+                let mut state = self.0.lock().unwrap();
+                let (remaining, reset_at) = *state;
+                let now = unix(SystemTime::now());
+                match remaining {
+                    0 if reset_at > now => Err(Duration::from_secs(reset_at - now)),
+                    0 => Ok(()),
+                    _ => {
+                        state.0 -= 1;
+                        Ok(())
+                    }
+                }
+            }
+
+            async fn send_after(&self) -> Option<SystemTime> {
+                let (remaining, reset_at) = *self.0.lock().unwrap();
+                (remaining == 0).then(|| UNIX_EPOCH + Duration::from_secs(reset_at))
+            }
+
+            async fn update(&self, limits: RateLimits, sent_at: SystemTime) {
+                *self.0.lock().unwrap() =
+                    (limits.remaining(), unix(sent_at) + limits.reset() as u64);
+            }
+
+            async fn limits(&self) -> Option<RateLimits> {
+                None
+            }
+        }
+
+        let shared = SharedLimiter::default();
+        let mut headers = HeaderMap::new();
+        headers.insert("RateLimit-Remaining", HeaderValue::from(2));
+        headers.insert("RateLimit-Reset", HeaderValue::from(30));
+        shared
+            .update(RateLimits::new(&headers).unwrap(), SystemTime::now())
+            .await;
+
+        let one = Client::new("crustacean-states tests").with_rate_limiter(shared.clone());
+        let two = Client::new("crustacean-states tests").with_rate_limiter(shared.clone());
+        // The two slots left are shared between the clients.
+        assert!(one.limiter.acquire().await.is_ok());
+        assert!(two.limiter.acquire().await.is_ok());
+        assert!(one.limiter.acquire().await.is_err());
+        assert!(two.ready_in().await > Duration::ZERO);
+        // Neither client sends anything until the timeframe resets.
+        for client in [one, two] {
+            let request = WorldRequest::new(&[WorldShard::NumNations]);
+            assert!(matches!(
+                client.get(request).await,
+                Err(ClientError::RateLimitedError(_))
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn in_memory_rate_limiter() {
+        use crate::client::{InMemoryRateLimiter, RateLimiter, RateLimits};
+        use reqwest::header::{HeaderMap, HeaderValue};
+        use std::time::SystemTime;
+
+        let limiter = InMemoryRateLimiter::default();
+        assert!(limiter.acquire().await.is_ok());
+        let mut headers = HeaderMap::new();
+        headers.insert("RateLimit-Remaining", HeaderValue::from(1));
+        headers.insert("RateLimit-Reset", HeaderValue::from(30));
+        limiter
+            .update(RateLimits::new(&headers).unwrap(), SystemTime::now())
+            .await;
+        assert!(limiter.acquire().await.is_ok());
+        assert!(limiter.acquire().await.is_err());
+    }
+
+    #[test]
+    fn endorsement_graph_errors() {
+        use crate::client::{endorsement_graph, ClientError};
+        use crate::models::name::NationName;
+        use crate::parsers::nation::Nation;
+        use std::collections::HashMap;
+
+        let members = ["Testlandia", "Maxtopia", "Aramos"].map(NationName::from);
+        let mut results = HashMap::new();
+        results.insert(
+            NationName::from("testlandia"),
+            Ok(Nation {
+                endorsements: Some(["maxtopia"].into_iter().collect()),
+                ..Default::default()
+            }),
+        );
+        results.insert(
+            NationName::from("maxtopia"),
+            Err(ClientError::RateLimitPolicyError),
+        );
+        let graph = endorsement_graph(&members, results);
+
+        assert_eq!(graph.members(), members);
+        assert_eq!(graph.unfetched().len(), 2);
+        assert_eq!(graph.unfetched()[0].0, NationName::from("maxtopia"));
+        assert_eq!(
+            graph.unfetched()[0].1,
+            ClientError::RateLimitPolicyError.to_string()
+        );
+        assert_eq!(graph.unfetched()[1].0, NationName::from("aramos"));
+        assert_eq!(
+            graph.endorsed("maxtopia").collect::<Vec<_>>(),
+            [&NationName::from("testlandia")]
+        );
+    }
+
+    #[test]
+    fn describe_error_sources() {
+        use crate::client::describe_error;
+
+        #[derive(Debug, thiserror::Error)]
+        #[error("timed out")]
+        struct Inner;
+        #[derive(Debug, thiserror::Error)]
+        #[error("connection failed: {0}")]
+        struct Outer(#[source] Inner);
+        #[derive(Debug, thiserror::Error)]
+        #[error("request failed")]
+        struct Top(#[source] Outer);
+
+        assert_eq!(
+            describe_error(&Top(Outer(Inner))),
+            "request failed: connection failed: timed out"
+        );
+    }
+
+    #[test]
+    fn census_pages() {
+        use crate::client::next_census_page;
+        use std::num::NonZeroU32;
+
+        let start = |n| NonZeroU32::new(n).unwrap();
+        assert_eq!(next_census_page(start(1), 20, Some(45)), Some(start(21)));
+        assert_eq!(next_census_page(start(41), 5, Some(45)), None);
+        // A region with an exact multiple of the page size has no empty last page.
+        assert_eq!(next_census_page(start(21), 20, Some(40)), None);
+        // Without the number of nations, only a short page ends the report.
+        assert_eq!(next_census_page(start(21), 20, None), Some(start(41)));
+        assert_eq!(next_census_page(start(41), 0, None), None);
+    }
+
+    #[test]
+    fn transient_errors() {
+        use crate::client::ClientError;
+        use std::time::Instant;
+
+        assert!(ClientError::RateLimitedError(Instant::now()).is_transient());
+        assert!(!ClientError::RateLimitPolicyError.is_transient());
+    }
 }
@@ -5,33 +5,68 @@
 //! Using the library usually takes three steps:
 //!
 //! 1. Creating a request
-//! (e.g. [`PublicNationRequest`])
-//! with the relevant shards.
+//!    (e.g. [`PublicNationRequest`])
+//!    with the relevant shards.
 //! 2. Sending the request as a URL through a [`Client`][crate::client::Client].
 //! 3. Parsing the response using a parser in [`parsers`].
 //!
+//! Steps 2 and 3 can be combined with
+//! [`Client::get_parsed`][crate::client::Client::get_parsed].
+//!
 //! Currently, the following requests can be formed and sent:
 //! - Nation (public shards only):
-//! [`PublicNationRequest::new`](shards::nation::PublicNationRequest::new),
-//! from [`PublicNationShards`](shards::nation::PublicNationShard);
-//! also, [`StandardPublicNationRequest`](shards::nation::StandardPublicNationRequest)
+//!   [`PublicNationRequest::new`](shards::nation::PublicNationRequest::new),
+//!   from [`PublicNationShards`](shards::nation::PublicNationShard);
+//!   also, [`StandardPublicNationRequest`](shards::nation::StandardPublicNationRequest)
 //! - Region: [`RegionRequest::new`](shards::region::RegionRequest::new),
-//! from [`RegionShards`](shards::region::RegionShard);
-//! also, [`StandardRegionRequest`](shards::region::StandardRegionRequest)
+//!   from [`RegionShards`](shards::region::RegionShard);
+//!   also, [`StandardRegionRequest`](shards::region::StandardRegionRequest)
 //! - World (except for `regionsfromtag`):
-//! [`WorldRequest::new`](shards::world::WorldRequest::new),
-//! from [`WorldShards`](shards::world::WorldShard)
+//!   [`WorldRequest::new`](shards::world::WorldRequest::new),
+//!   from [`WorldShards`](shards::world::WorldShard)
 //! - WA (World Assembly): [`WAShard`](shards::wa::WARequest),
-//! from [`WAShards`](shards::wa::WAShard`)
+//!   from [`WAShards`](shards::wa::WAShard`)
 //!
 //! The following requests can be parsed:
 //! - [`Nation`](parsers::nation::Nation) (some fields still being finalized)
+//! - [`Region`](parsers::region::Region)
+//! - [`World`](parsers::world::World)
+//! - [`WorldAssembly`](parsers::wa::WorldAssembly) (some shards still being finalized)
 //!
 //! The following functionality is planned, but is not implemented:
-//! - parsers for Region, World, and WA request responses
 //! - private shards
 //! - lighter-weight client using `hyper`
-//! - breaking crate into features
+//!
+//! ## Features
+//! - `client` (on by default): send requests with `client::Client`,
+//!   and watch the API with the streams in `streams`. Enables `parsers`.
+//! - `native-tls` (on by default) or `rustls`: the TLS library that `client::Client` uses
+//!   to reach the API, which is only served over HTTPS.
+//!   To use `rustls`, turn off default features and enable `client` and `rustls`.
+//! - `gzip`: let `client::Client` accept gzip-compressed responses.
+//! - `parsers`: parse responses with the parsers in `parsers`.
+//!   Without `parsers` or `client`, requests can still be built and turned into URLs
+//!   with [`shards`], which only needs `itertools`, `strum`, `thiserror` and `url`,
+//!   so it also builds for embedded and WebAssembly targets.
+//! - `dumps`: reserved for parsing the daily data dumps, which are not supported yet;
+//!   it does nothing for now.
+//! - `sse`: subscribe to live happenings through the server-sent events feed
+//!   (see `streams::sse`).
+//! - `derive`: `#[derive(NationView)]` on a struct to request exactly its fields
+//!   (see [`shards::typed`]).
+//! - `intern`: share the text of names that appear many times in bulk workloads
+//!   (see `models::name::NameInterner`).
+//! - `serde`: `serde::Serialize` and `serde::Deserialize` for every parsed type,
+//!   to cache, persist or re-serve responses,
+//!   and flat JSON views of nations and regions (see `models::json`).
+//! - `schemars`: JSON Schemas for every type that `serde` can serialize.
+//! - `time`: convert timestamps to `time::OffsetDateTime`
+//!   (see `models::timestamp::Timestamp`).
+//! - `tower`: use `client::Client` as a `tower` service.
+//! - `csv`: export census data and census rankings as CSV
+//!   (see `CensusData::to_csv` and `CensusRanks::to_csv`).
+//! - `proptest`: generate names, shards and census configurations for property tests
+//!   (see `strategies`).
 //!
 //! ## Examples
 //! For a list of examples,
@@ -41,13 +76,23 @@
 
 // #![deny(missing_docs)]
 
+// Lets the derive macros refer to `::crustacean_states` from inside this crate.
+#[cfg(feature = "derive")]
+extern crate self as crustacean_states;
+
 #[doc(hidden)]
 mod macros;
 
+#[cfg(feature = "client")]
 pub mod client;
 pub mod models;
+#[cfg(feature = "parsers")]
 pub mod parsers;
 pub mod shards;
+#[cfg(feature = "proptest")]
+pub mod strategies;
+#[cfg(feature = "client")]
+pub mod streams;
 
 /// Takes a nation name with capital letters and spaces
 /// and turns it into a safe-to-send, lowercase name.
@@ -65,20 +110,22 @@ pub fn safe_name(unsafe_name: impl ToString) -> String {
 /// Note: this will not always result in a name
 /// that is capitalized the same way as it is on NationStates.
 pub fn pretty_name<T: ToString>(safe_name: T) -> String {
+    // The first letter of every word is capitalized.
+    let mut word_start = true;
     safe_name
         .to_string()
-        .replace('_', " ")
         .chars()
-        .fold(String::new(), |s, c| {
-            format!(
-                "{s}{}",
-                if s.ends_with(' ') || s.is_empty() {
-                    c.to_ascii_uppercase()
-                } else {
-                    c
-                }
-            )
+        .map(|c| {
+            let c = if c == '_' { ' ' } else { c };
+            let pretty = if word_start {
+                c.to_ascii_uppercase()
+            } else {
+                c
+            };
+            word_start = c == ' ';
+            pretty
         })
+        .collect()
 }
 
 #[cfg(test)]
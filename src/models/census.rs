@@ -0,0 +1,172 @@
+//! Names, units and titles of the World Census scales.
+//!
+//! The same text is sent by the API for
+//! [`WorldShard::CensusName`](crate::shards::world::WorldShard::CensusName),
+//! [`WorldShard::CensusScale`](crate::shards::world::WorldShard::CensusScale) and
+//! [`WorldShard::CensusTitle`](crate::shards::world::WorldShard::CensusTitle),
+//! but looking it up here does not need a request for every scale:
+//! ```rust
+//! # use crustacean_states::models::census::CensusScaleInfo;
+//! let scale = CensusScaleInfo::get(66).unwrap();
+//! assert_eq!(scale.name, "World Assembly Endorsements");
+//! assert_eq!(scale.unit, "Endorsements");
+//! assert_eq!(CensusScaleInfo::find("world assembly endorsements"), Some(scale));
+//! ```
+//! The table was written by hand from the World Census,
+//! and new scales are added to the game from time to time,
+//! so the API should be preferred where the text has to be exact.
+
+/// The text the World Census uses to describe one of its scales.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct CensusScaleInfo {
+    /// The ID of the scale.
+    pub id: u8,
+    /// The name of the scale, as sent for `CensusName`.
+    pub name: &'static str,
+    /// The unit that scores are measured in, as sent for `CensusScale`.
+    pub unit: &'static str,
+    /// The title of the ranking of nations on the scale, as sent for `CensusTitle`.
+    pub title: &'static str,
+}
+
+impl CensusScaleInfo {
+    /// Every scale that this crate knows about, in order of ID.
+    pub const ALL: &'static [CensusScaleInfo] = CENSUS_SCALES;
+
+    /// Finds a scale by its ID.
+    pub fn get(id: u8) -> Option<&'static CensusScaleInfo> {
+        CENSUS_SCALES.get(usize::from(id))
+    }
+
+    /// Finds a scale by its name, ignoring case.
+    pub fn find(name: &str) -> Option<&'static CensusScaleInfo> {
+        CENSUS_SCALES
+            .iter()
+            .find(|scale| scale.name.eq_ignore_ascii_case(name))
+    }
+}
+
+const fn scale(
+    id: u8,
+    name: &'static str,
+    unit: &'static str,
+    title: &'static str,
+) -> CensusScaleInfo {
+    CensusScaleInfo {
+        id,
+        name,
+        unit,
+        title,
+    }
+}
+
+//noinspection SpellCheckingInspection
+#[rustfmt::skip]
+const CENSUS_SCALES: &[CensusScaleInfo] = &[
+    scale(0, "Civil Rights", "Points", "Most Extensive Civil Rights"),
+    scale(1, "Economy", "Points", "Most Efficient Economies"),
+    scale(2, "Political Freedom", "Points", "Most Politically Free"),
+    scale(3, "Population", "Millions of people", "Largest Populations"),
+    scale(4, "Wealth Gaps", "Rich-Poor Income Ratio", "Largest Wealth Gaps"),
+    scale(5, "Death Rate", "Deaths Per 1000 People", "Highest Death Rates"),
+    scale(6, "Compassion", "Kind Hearts", "Most Compassionate Citizens"),
+    scale(7, "Eco-Friendliness", "Dolphin Recycling Awareness Index", "Most Eco-Friendly Governments"),
+    scale(8, "Social Conservatism", "Bananas Ingested Per Capita", "Most Conservative"),
+    scale(9, "Nudity", "Kilograms Per Square Mile", "Nudest"),
+    scale(10, "Industry: Automobile Manufacturing", "Standard Monetary Units", "Largest Automobile Manufacturing Sector"),
+    scale(11, "Industry: Cheese Exports", "Standard Monetary Units", "Largest Cheese Export Sector"),
+    scale(12, "Industry: Basket Weaving", "Standard Monetary Units", "Largest Basket Weaving Sector"),
+    scale(13, "Industry: Information Technology", "Standard Monetary Units", "Largest Information Technology Sector"),
+    scale(14, "Industry: Pizza Delivery", "Standard Monetary Units", "Largest Pizza Delivery Sector"),
+    scale(15, "Industry: Trout Fishing", "Standard Monetary Units", "Largest Trout Fishing Sector"),
+    scale(16, "Industry: Arms Manufacturing", "Standard Monetary Units", "Largest Arms Manufacturing Sector"),
+    scale(17, "Sector: Agriculture", "Standard Monetary Units", "Largest Agricultural Sector"),
+    scale(18, "Industry: Beverage Sales", "Standard Monetary Units", "Largest Soda Pop Sector"),
+    scale(19, "Industry: Timber Woodchipping", "Standard Monetary Units", "Largest Timber Woodchipping Industry"),
+    scale(20, "Industry: Mining", "Standard Monetary Units", "Largest Mining Sector"),
+    scale(21, "Industry: Insurance", "Standard Monetary Units", "Largest Insurance Industry"),
+    scale(22, "Industry: Furniture Restoration", "Standard Monetary Units", "Largest Furniture Restoration Industry"),
+    scale(23, "Industry: Retail", "Standard Monetary Units", "Largest Retail Industry"),
+    scale(24, "Industry: Book Publishing", "Standard Monetary Units", "Largest Publishing Industry"),
+    scale(25, "Industry: Gambling", "Standard Monetary Units", "Largest Gambling Industry"),
+    scale(26, "Sector: Manufacturing", "Standard Monetary Units", "Largest Manufacturing Sector"),
+    scale(27, "Government Size", "Percentage of GDP", "Largest Governments"),
+    scale(28, "Welfare", "Government Spending Per Capita", "Largest Welfare Programs"),
+    scale(29, "Public Healthcare", "Government Spending Per Capita", "Most Extensive Public Healthcare"),
+    scale(30, "Law Enforcement", "Government Spending Per Capita", "Most Advanced Law Enforcement"),
+    scale(31, "Business Subsidization", "Government Spending Per Capita", "Most Subsidized Industry"),
+    scale(32, "Religiousness", "Prayers Per Hour", "Most Devout"),
+    scale(33, "Income Equality", "Income Ratio", "Most Income Equality"),
+    scale(34, "Niceness", "Cuddles Per Hug", "Nicest Citizens"),
+    scale(35, "Rudeness", "Insults Per Minute", "Rudest Citizens"),
+    scale(36, "Intelligence", "Quips Per Hour", "Smartest Citizens"),
+    scale(37, "Ignorance", "Bits of Lint Per Capita", "Most Ignorant Citizens"),
+    scale(38, "Political Apathy", "Whatevers", "Most Politically Apathetic Citizens"),
+    scale(39, "Health", "Bananas Ingested Per Day", "Healthiest Citizens"),
+    scale(40, "Cheerfulness", "Percentage of Smiling Citizens", "Most Cheerful Citizens"),
+    scale(41, "Weather", "Meters of Sunshine", "Best Weather"),
+    scale(42, "Compliance", "Law-Abiding Citizens Per Capita", "Lowest Crime Rates"),
+    scale(43, "Safety", "Bubble-Wrapped Pillows", "Safest"),
+    scale(44, "Lifespan", "Years", "Longest Average Lifespans"),
+    scale(45, "Ideological Radicality", "Bombs Per Square Mile", "Most Extreme"),
+    scale(46, "Defense Forces", "Total Defense Forces", "Largest Defense Forces"),
+    scale(47, "Pacifism", "Doves Per Hawk", "Most Pacifist"),
+    scale(48, "Economic Freedom", "Standardized Economic Freedom Units", "Most Pro-Market"),
+    scale(49, "Taxation", "Average Tax Rate", "Highly Taxed"),
+    scale(50, "Freedom From Taxation", "Hayek Index", "Lowest Overall Tax Burden"),
+    scale(51, "Corruption", "Kickbacks Per Hour", "Most Corrupt Governments"),
+    scale(52, "Integrity", "Kickbacks Rejected Per Hour", "Least Corrupt Governments"),
+    scale(53, "Authoritarianism", "Stern Warnings Per Hour", "Most Authoritarian"),
+    scale(54, "Youth Rebelliousness", "Anarchy Symbols Per Square Mile", "Most Rebellious Youth"),
+    scale(55, "Culture", "Snifters Per Capita", "Most Cultured"),
+    scale(56, "Employment", "Workforce Participation Rate", "Highest Workforce Participation"),
+    scale(57, "Public Transport", "Government Spending Per Capita", "Most Advanced Public Transport"),
+    scale(58, "Tourism", "Tourists Per Hour", "Most Popular Tourist Destinations"),
+    scale(59, "Weaponization", "Weapons Per Capita", "Most Armed"),
+    scale(60, "Recreational Drug Use", "Slippers Per Hour", "Highest Drug Use"),
+    scale(61, "Obesity", "Obesity Rate", "Fattest Citizens"),
+    scale(62, "Secularism", "Atheists Per Capita", "Most Secular"),
+    scale(63, "Environmental Beauty", "Pretty Planet Index", "Most Beautiful Environments"),
+    scale(64, "Charmlessness", "Kitsch Index", "Most Avoided"),
+    scale(65, "Influence", "Soft Power Disbursement Rating", "Most Influential"),
+    scale(66, "World Assembly Endorsements", "Endorsements", "Most World Assembly Endorsements"),
+    scale(67, "Averageness", "Averageness Index", "Most Average"),
+    scale(68, "Human Development Index", "HDI", "Most Developed"),
+    scale(69, "Primitiveness", "Scary Big Sticks", "Most Primitive"),
+    scale(70, "Scientific Advancement", "Cheese Graters", "Most Advanced in Science"),
+    scale(71, "Inclusiveness", "Rainbows", "Most Inclusive"),
+    scale(72, "Average Income", "Standard Monetary Units", "Highest Average Incomes"),
+    scale(73, "Average Income of Poor", "Standard Monetary Units", "Highest Poor Incomes"),
+    scale(74, "Average Income of Rich", "Standard Monetary Units", "Highest Wealthy Incomes"),
+    scale(75, "Public Education", "Government Spending Per Capita", "Most Advanced Public Education"),
+    scale(76, "Economic Output", "Standard Monetary Units", "Highest Economic Output"),
+    scale(77, "Crime", "Crimes Per Capita", "Most Crime"),
+    scale(78, "Foreign Aid", "Government Spending Per Capita", "Largest Foreign Aid Budgets"),
+    scale(79, "Black Market", "Standard Monetary Units", "Largest Black Markets"),
+    scale(80, "Residency", "Days", "Most Stationary"),
+    scale(81, "Survivors", "Millions of people", "Largest Survivor Populations"),
+    scale(82, "Zombies", "Millions of zombies", "Largest Zombie Hordes"),
+    scale(83, "Dead", "Millions of corpses", "Most Dead"),
+    scale(84, "Percentage Zombies", "Percent", "Highest Zombie Percentages"),
+    scale(85, "Average Disposable Income", "Standard Monetary Units", "Highest Disposable Incomes"),
+    scale(86, "International Artwork", "Artworks", "Most Internationally Acclaimed Artwork"),
+    scale(87, "Patriotism", "Flag Waves", "Most Patriotic"),
+    scale(88, "Food Quality", "Michelin Stars", "Tastiest Food"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn census_table() {
+        assert!(CensusScaleInfo::ALL
+            .iter()
+            .enumerate()
+            .all(|(i, scale)| usize::from(scale.id) == i));
+        assert_eq!(CensusScaleInfo::get(0).unwrap().name, "Civil Rights");
+        assert_eq!(CensusScaleInfo::find("ECONOMY").unwrap().id, 1);
+        assert_eq!(CensusScaleInfo::get(u8::MAX), None);
+        assert_eq!(CensusScaleInfo::find("Not A Scale"), None);
+    }
+}
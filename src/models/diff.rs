@@ -0,0 +1,449 @@
+//! Changes between two snapshots of the same nation or region,
+//! for "what changed since yesterday" reports and change-log bots.
+//!
+//! ```rust
+//! # use crustacean_states::models::diff::NationChange;
+//! # use crustacean_states::parsers::nation::Nation;
+//! let yesterday = Nation::from_xml(
+//!     r#"<NATION id="aramos"><POPULATION>40</POPULATION><ENDORSEMENTS>testlandia</ENDORSEMENTS></NATION>"#,
+//! )?;
+//! let today = Nation::from_xml(
+//!     r#"<NATION id="aramos"><POPULATION>42</POPULATION><ENDORSEMENTS>maxtopia</ENDORSEMENTS></NATION>"#,
+//! )?;
+//! let diff = Nation::diff(&yesterday, &today);
+//! assert_eq!(diff.changes[0], NationChange::Population { old: 40, new: 42 });
+//! assert_eq!(diff.changes[0].population_delta(), Some(2));
+//! assert_eq!(diff.changes.len(), 3);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//! Only fields that both snapshots have data for are compared.
+//! Regions are compared the same way, with [`Region::diff`].
+
+use crate::{
+    models::name::{NSName, NameList, NationName, RegionName},
+    parsers::{
+        nation::{Nation, WAStatus},
+        region::{EmbassyKind, Officer, Region},
+        CensusData,
+    },
+};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// One change between two snapshots of a nation.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub enum NationChange {
+    /// The population changed, in millions.
+    Population {
+        /// The old population.
+        old: u32,
+        /// The new population.
+        new: u32,
+    },
+    /// The nation's category changed.
+    Category {
+        /// The old category.
+        old: String,
+        /// The new category.
+        new: String,
+    },
+    /// The nation joined, left, or changed its status in the World Assembly.
+    WAStatus {
+        /// The old status.
+        old: WAStatus,
+        /// The new status.
+        new: WAStatus,
+    },
+    /// The nation moved to another region.
+    Region {
+        /// The region it was in.
+        old: String,
+        /// The region it is in now.
+        new: String,
+    },
+    /// Nations that started endorsing the nation, in the order they are listed.
+    EndorsementsGained(Vec<NationName>),
+    /// Nations that stopped endorsing the nation, in the order they were listed.
+    EndorsementsLost(Vec<NationName>),
+    /// The score on a World Census scale changed.
+    CensusScore {
+        /// The ID of the scale.
+        scale: u8,
+        /// The old score.
+        old: f64,
+        /// The new score.
+        new: f64,
+    },
+}
+
+impl NationChange {
+    /// How much the population grew, in millions, if this is a population change.
+    pub fn population_delta(&self) -> Option<i64> {
+        match self {
+            NationChange::Population { old, new } => Some(i64::from(*new) - i64::from(*old)),
+            _ => None,
+        }
+    }
+
+    /// How much the score grew, if this is a World Census score change.
+    pub fn census_delta(&self) -> Option<f64> {
+        match self {
+            NationChange::CensusScore { old, new, .. } => Some(new - old),
+            _ => None,
+        }
+    }
+}
+
+/// The changes between two snapshots of a nation, created by [`Nation::diff`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct NationDiff {
+    /// The nation, named as in the newer snapshot.
+    pub nation: NationName,
+    /// Every change, in the order of the variants of [`NationChange`].
+    /// World Census changes are sorted by scale.
+    pub changes: Vec<NationChange>,
+}
+
+impl NationDiff {
+    /// Whether nothing that both snapshots have data for changed.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl Nation {
+    /// Compares two snapshots of the same nation.
+    ///
+    /// A field is only compared if both `old` and `new` have data for it,
+    /// and World Census scores are only compared for current data.
+    pub fn diff(old: &Nation, new: &Nation) -> NationDiff {
+        let mut changes = Vec::new();
+        if let (Some(old), Some(new)) = (old.population, new.population) {
+            if old != new {
+                changes.push(NationChange::Population { old, new });
+            }
+        }
+        if let (Some(old), Some(new)) = (&old.category, &new.category) {
+            if old != new {
+                changes.push(NationChange::Category {
+                    old: old.clone(),
+                    new: new.clone(),
+                });
+            }
+        }
+        if let (Some(old), Some(new)) = (&old.wa_status, &new.wa_status) {
+            if old != new {
+                changes.push(NationChange::WAStatus {
+                    old: old.clone(),
+                    new: new.clone(),
+                });
+            }
+        }
+        if let (Some(old), Some(new)) = (&old.region, &new.region) {
+            if old != new {
+                changes.push(NationChange::Region {
+                    old: old.clone(),
+                    new: new.clone(),
+                });
+            }
+        }
+        if let (Some(old), Some(new)) = (&old.endorsements, &new.endorsements) {
+            let gained = missing_from(new, old);
+            if !gained.is_empty() {
+                changes.push(NationChange::EndorsementsGained(gained));
+            }
+            let lost = missing_from(old, new);
+            if !lost.is_empty() {
+                changes.push(NationChange::EndorsementsLost(lost));
+            }
+        }
+        if let (Some(CensusData::Current(old)), Some(CensusData::Current(new))) =
+            (&old.census, &new.census)
+        {
+            let old = old
+                .iter()
+                .filter_map(|d| Some((d.id, d.score?)))
+                .collect::<HashMap<_, _>>();
+            let mut scores = new
+                .iter()
+                .filter_map(|d| Some((d.id, old.get(&d.id).copied()?, d.score?)))
+                .filter(|(_, old, new)| old != new)
+                .collect::<Vec<_>>();
+            scores.sort_by_key(|&(scale, _, _)| scale);
+            changes.extend(
+                scores
+                    .into_iter()
+                    .map(|(scale, old, new)| NationChange::CensusScore { scale, old, new }),
+            );
+        }
+        NationDiff {
+            nation: NationName::from(new.name.as_str()),
+            changes,
+        }
+    }
+}
+
+/// One change between two snapshots of a region.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub enum RegionChange {
+    /// The delegate changed, or the region gained or lost its delegate.
+    Delegate {
+        /// The old delegate, if there was one.
+        old: Option<NationName>,
+        /// The new delegate, if there is one.
+        new: Option<NationName>,
+    },
+    /// A nation was made an officer.
+    OfficerAppointed(Officer),
+    /// A nation stopped being an officer.
+    OfficerDismissed(Officer),
+    /// An officer's office or authority changed.
+    OfficerChanged {
+        /// The officer as it was.
+        old: Officer,
+        /// The officer as it is now.
+        new: Officer,
+    },
+    /// Nations that joined the region, in the order they are listed.
+    NationsJoined(Vec<NationName>),
+    /// Nations that left the region, in the order they were listed.
+    NationsLeft(Vec<NationName>),
+    /// An embassy was opened, closed, or changed its state.
+    Embassy {
+        /// The other region of the embassy.
+        region: RegionName,
+        /// The old state, if there was an embassy.
+        old: Option<EmbassyKind>,
+        /// The new state, if there is an embassy.
+        new: Option<EmbassyKind>,
+    },
+    /// The World Factbook Entry was edited.
+    Factbook {
+        /// The old text.
+        old: String,
+        /// The new text.
+        new: String,
+    },
+}
+
+/// The changes between two snapshots of a region, created by [`Region::diff`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct RegionDiff {
+    /// The region, named as in the newer snapshot.
+    pub region: RegionName,
+    /// Every change, in the order of the variants of [`RegionChange`].
+    /// Officer changes are in the order of the newer officer list, then of the older one,
+    /// and embassy changes are sorted by region.
+    pub changes: Vec<RegionChange>,
+}
+
+impl RegionDiff {
+    /// Whether nothing that both snapshots have data for changed.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl Region {
+    /// Compares two snapshots of the same region.
+    ///
+    /// A field is only compared if both `old` and `new` have data for it.
+    /// Officers are matched by nation,
+    /// and only count as changed if their office or authority changed.
+    pub fn diff(old: &Region, new: &Region) -> RegionDiff {
+        let mut changes = Vec::new();
+        if let (Some(old), Some(new)) = (&old.delegate, &new.delegate) {
+            let old = old.as_deref().map(NationName::from);
+            let new = new.as_deref().map(NationName::from);
+            if old != new {
+                changes.push(RegionChange::Delegate { old, new });
+            }
+        }
+        if let (Some(old), Some(new)) = (&old.officers, &new.officers) {
+            let old_by_nation = old
+                .iter()
+                .map(|o| (NationName::from(o.nation.as_str()), o))
+                .collect::<HashMap<_, _>>();
+            let new_nations = new
+                .iter()
+                .map(|o| NationName::from(o.nation.as_str()))
+                .collect::<HashSet<_>>();
+            for officer in new {
+                match old_by_nation.get(&NationName::from(officer.nation.as_str())) {
+                    None => changes.push(RegionChange::OfficerAppointed(officer.clone())),
+                    Some(old)
+                        if old.office != officer.office || old.authority != officer.authority =>
+                    {
+                        changes.push(RegionChange::OfficerChanged {
+                            old: (*old).clone(),
+                            new: officer.clone(),
+                        })
+                    }
+                    Some(_) => {}
+                }
+            }
+            changes.extend(
+                old.iter()
+                    .filter(|o| !new_nations.contains(&NationName::from(o.nation.as_str())))
+                    .cloned()
+                    .map(RegionChange::OfficerDismissed),
+            );
+        }
+        if let (Some(old), Some(new)) = (&old.nations, &new.nations) {
+            let joined = missing_from(new, old);
+            if !joined.is_empty() {
+                changes.push(RegionChange::NationsJoined(joined));
+            }
+            let left = missing_from(old, new);
+            if !left.is_empty() {
+                changes.push(RegionChange::NationsLeft(left));
+            }
+        }
+        if let (Some(old), Some(new)) = (&old.embassies, &new.embassies) {
+            let mut embassies = BTreeMap::new();
+            for embassy in old {
+                embassies
+                    .entry(RegionName::from(embassy.region.as_str()).canonical())
+                    .or_insert((&embassy.region, None, None))
+                    .1 = Some(&embassy.kind);
+            }
+            for embassy in new {
+                embassies
+                    .entry(RegionName::from(embassy.region.as_str()).canonical())
+                    .or_insert((&embassy.region, None, None))
+                    .2 = Some(&embassy.kind);
+            }
+            changes.extend(
+                embassies
+                    .into_values()
+                    .filter(|(_, old, new)| old != new)
+                    .map(|(region, old, new)| RegionChange::Embassy {
+                        region: RegionName::from(region.as_str()),
+                        old: old.cloned(),
+                        new: new.cloned(),
+                    }),
+            );
+        }
+        if let (Some(old), Some(new)) = (&old.factbook, &new.factbook) {
+            if old != new {
+                changes.push(RegionChange::Factbook {
+                    old: old.clone(),
+                    new: new.clone(),
+                });
+            }
+        }
+        RegionDiff {
+            region: RegionName::from(new.name.as_str()),
+            changes,
+        }
+    }
+}
+
+/// The names in `list` that are not in `other`.
+fn missing_from(list: &NameList, other: &NameList) -> Vec<NationName> {
+    let other = other.iter().map(NationName::from).collect::<HashSet<_>>();
+    list.iter()
+        .map(NationName::from)
+        .filter(|name| !other.contains(name))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nation_diff() {
+        let nation = |category: &str, score: &str| {
+            Nation::from_xml(&format!(
+                r#"<NATION id="aramos"><CATEGORY>{category}</CATEGORY><UNSTATUS>Non-member</UNSTATUS><CENSUS><SCALE id="0"><SCORE>{score}</SCORE></SCALE><SCALE id="3"><SCORE>1</SCORE></SCALE></CENSUS></NATION>"#
+            ))
+            .unwrap()
+        };
+        let old = nation("Anarchy", "12.5");
+        assert!(Nation::diff(&old, &old).is_empty());
+
+        let new = nation("Democratic Socialists", "10");
+        let diff = Nation::diff(&old, &new);
+        assert_eq!(diff.nation, NationName::from("aramos"));
+        assert_eq!(
+            diff.changes,
+            [
+                NationChange::Category {
+                    old: "Anarchy".to_string(),
+                    new: "Democratic Socialists".to_string(),
+                },
+                NationChange::CensusScore {
+                    scale: 0,
+                    old: 12.5,
+                    new: 10.0,
+                },
+            ]
+        );
+        assert_eq!(diff.changes[1].census_delta(), Some(-2.5));
+        assert_eq!(diff.changes[1].population_delta(), None);
+    }
+
+    #[test]
+    fn region_diff() {
+        let old = Region::from_xml(
+            r#"<REGION id="testregionia">
+<NATIONS>testlandia:maxtopia</NATIONS>
+<DELEGATE>0</DELEGATE>
+<FACTBOOK>Hello</FACTBOOK>
+<EMBASSIES><EMBASSY type="pending">Osiris</EMBASSY><EMBASSY>Lazarus</EMBASSY></EMBASSIES>
+<OFFICERS><OFFICER><NATION>maxtopia</NATION><OFFICE>Minister</OFFICE><AUTHORITY>BP</AUTHORITY><TIME>5</TIME><BY>testlandia</BY><ORDER>1</ORDER></OFFICER></OFFICERS>
+</REGION>"#,
+        )
+        .unwrap();
+        assert!(Region::diff(&old, &old).is_empty());
+        let new = Region::from_xml(
+            r#"<REGION id="testregionia">
+<NATIONS>testlandia:aramos</NATIONS>
+<DELEGATE>testlandia</DELEGATE>
+<FACTBOOK>Hello</FACTBOOK>
+<EMBASSIES><EMBASSY>Osiris</EMBASSY></EMBASSIES>
+<OFFICERS><OFFICER><NATION>aramos</NATION><OFFICE>Minister</OFFICE><AUTHORITY>B</AUTHORITY><TIME>9</TIME><BY>testlandia</BY><ORDER>1</ORDER></OFFICER></OFFICERS>
+</REGION>"#,
+        )
+        .unwrap();
+        let changes = Region::diff(&old, &new).changes;
+        assert_eq!(
+            changes[0],
+            RegionChange::Delegate {
+                old: None,
+                new: Some(NationName::from("testlandia")),
+            }
+        );
+        assert!(matches!(&changes[1], RegionChange::OfficerAppointed(o) if o.nation == "aramos"));
+        assert!(matches!(&changes[2], RegionChange::OfficerDismissed(o) if o.nation == "maxtopia"));
+        assert_eq!(
+            changes[3..],
+            [
+                RegionChange::NationsJoined(vec![NationName::from("aramos")]),
+                RegionChange::NationsLeft(vec![NationName::from("maxtopia")]),
+                RegionChange::Embassy {
+                    region: RegionName::from("lazarus"),
+                    old: Some(EmbassyKind::Established),
+                    new: None,
+                },
+                RegionChange::Embassy {
+                    region: RegionName::from("osiris"),
+                    old: Some(EmbassyKind::Pending),
+                    new: Some(EmbassyKind::Established),
+                },
+            ]
+        );
+    }
+}
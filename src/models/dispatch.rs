@@ -1,9 +1,19 @@
 //! Contains information about the Dispatch
 
-use std::fmt::{Display, Formatter};
+#[cfg(feature = "parsers")]
+use crate::parsers::Dispatch;
+#[cfg(feature = "parsers")]
+use std::cmp::Reverse;
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+use strum::EnumString;
 
 /// The categories of dispatches.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum DispatchCategory {
     /// Factbooks officially describe a nation.
     Factbook(FactbookCategory),
@@ -15,7 +25,9 @@ pub enum DispatchCategory {
     Meta(MetaCategory),
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, EnumString, PartialEq, strum::Display)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[allow(missing_docs)]
 #[non_exhaustive]
 /// The subcategories of factbooks.
@@ -38,10 +50,13 @@ pub enum FactbookCategory {
     /// NOTE:
     /// This is only used in shard queries;
     /// no [`Dispatch`][crate::parsers::Dispatch] will ever be tagged [`FactbookCategory::Any`].
+    #[strum(serialize = "")]
     Any,
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, EnumString, PartialEq, strum::Display)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[allow(missing_docs)]
 #[non_exhaustive]
 /// The subcategories of bulletins.
@@ -56,10 +71,13 @@ pub enum BulletinCategory {
     /// NOTE:
     /// This is only used in shard queries;
     /// no [`Dispatch`][crate::parsers::Dispatch] will ever be tagged [`BulletinCategory::Any`].
+    #[strum(serialize = "")]
     Any,
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, EnumString, PartialEq, strum::Display)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[allow(missing_docs)]
 #[non_exhaustive]
 /// The subcategories of accounts.
@@ -78,10 +96,13 @@ pub enum AccountCategory {
     /// NOTE:
     /// This is only used in shard queries;
     /// no [`Dispatch`][crate::parsers::Dispatch] will ever be tagged [`AccountCategory::Any`].
+    #[strum(serialize = "")]
     Any,
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, EnumString, PartialEq, strum::Display)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[allow(missing_docs)]
 #[non_exhaustive]
 /// The subcategories of meta-category dispatches.
@@ -94,99 +115,171 @@ pub enum MetaCategory {
     /// NOTE:
     /// This is only used in shard queries;
     /// no [`Dispatch`][crate::parsers::Dispatch] will ever be tagged [`MetaCategory::Any`].
+    #[strum(serialize = "")]
     Any,
 }
 
-impl Display for FactbookCategory {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            match self {
-                FactbookCategory::Any => String::new(),
-                c => format!("{c:?}"),
-            }
-        )
+impl DispatchCategory {
+    /// Reads a category from its main category and subcategory, as NationStates names them.
+    /// An empty subcategory is read as `Any`.
+    pub(crate) fn from_parts(main: &str, sub: &str) -> Result<Self, strum::ParseError> {
+        Ok(match main {
+            "Factbook" => DispatchCategory::Factbook(sub.parse()?),
+            "Bulletin" => DispatchCategory::Bulletin(sub.parse()?),
+            "Account" => DispatchCategory::Account(sub.parse()?),
+            "Meta" => DispatchCategory::Meta(sub.parse()?),
+            _ => return Err(strum::ParseError::VariantNotFound),
+        })
+    }
+
+    /// Whether `other` is in this category.
+    ///
+    /// A category with the `Any` subcategory contains every subcategory of its main category,
+    /// and any other category only contains itself.
+    /// ```rust
+    /// # use crustacean_states::models::dispatch::{DispatchCategory, FactbookCategory};
+    /// let any = DispatchCategory::Factbook(FactbookCategory::Any);
+    /// let history = DispatchCategory::Factbook(FactbookCategory::History);
+    /// assert!(any.contains(&history));
+    /// assert!(!history.contains(&any));
+    /// ```
+    pub fn contains(&self, other: &DispatchCategory) -> bool {
+        match (self, other) {
+            (DispatchCategory::Factbook(FactbookCategory::Any), DispatchCategory::Factbook(_))
+            | (DispatchCategory::Bulletin(BulletinCategory::Any), DispatchCategory::Bulletin(_))
+            | (DispatchCategory::Account(AccountCategory::Any), DispatchCategory::Account(_))
+            | (DispatchCategory::Meta(MetaCategory::Any), DispatchCategory::Meta(_)) => true,
+            _ => self == other,
+        }
     }
 }
 
-impl Display for BulletinCategory {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            match self {
-                BulletinCategory::Any => String::new(),
-                c => format!("{c:?}"),
-            }
-        )
+/// Searching and sorting a list of dispatches,
+/// such as [`Nation::dispatch_list`](crate::parsers::nation::Nation::dispatch_list).
+///
+/// Sorts are stable, so dispatches that compare equal keep their order.
+/// ```rust
+/// # use crustacean_states::models::dispatch::{
+/// #     BulletinCategory, DispatchCategory, DispatchList, MetaCategory,
+/// # };
+/// # use crustacean_states::parsers::Dispatch;
+/// let dispatch = |id, category, score| Dispatch {
+///     id,
+///     title: format!("Dispatch {id}"),
+///     author: "Testlandia".to_string(),
+///     category,
+///     created: u64::from(id),
+///     edited: None,
+///     views: 0,
+///     score,
+///     text: None,
+/// };
+/// let mut dispatches = vec![
+///     dispatch(1, DispatchCategory::Meta(MetaCategory::Reference), 10),
+///     dispatch(2, DispatchCategory::Bulletin(BulletinCategory::News), 30),
+///     dispatch(3, DispatchCategory::Bulletin(BulletinCategory::Policy), 20),
+/// ];
+/// let bulletins = dispatches.in_category(&DispatchCategory::Bulletin(BulletinCategory::Any));
+/// assert_eq!(bulletins.len(), 2);
+///
+/// dispatches.sort_by_score();
+/// assert_eq!(dispatches[0].id, 2);
+/// assert_eq!(dispatches.find_id(1).map(|d| d.score), Some(10));
+/// ```
+#[cfg(feature = "parsers")]
+pub trait DispatchList {
+    /// The dispatch with the ID `id`, if there is one.
+    fn find_id(&self, id: u32) -> Option<&Dispatch>;
+
+    /// The dispatches in `category`, in their current order.
+    /// See [`DispatchCategory::contains`] for how subcategories are matched.
+    fn in_category(&self, category: &DispatchCategory) -> Vec<&Dispatch>;
+
+    /// Sorts the dispatches by score, highest first.
+    fn sort_by_score(&mut self);
+
+    /// Sorts the dispatches by views, most first.
+    fn sort_by_views(&mut self);
+
+    /// Sorts the dispatches by when they were created, newest first.
+    fn sort_by_created(&mut self);
+}
+
+#[cfg(feature = "parsers")]
+impl DispatchList for [Dispatch] {
+    fn find_id(&self, id: u32) -> Option<&Dispatch> {
+        self.iter().find(|d| d.id == id)
+    }
+
+    fn in_category(&self, category: &DispatchCategory) -> Vec<&Dispatch> {
+        self.iter()
+            .filter(|d| category.contains(&d.category))
+            .collect()
+    }
+
+    fn sort_by_score(&mut self) {
+        self.sort_by_key(|d| Reverse(d.score));
+    }
+
+    fn sort_by_views(&mut self) {
+        self.sort_by_key(|d| Reverse(d.views));
+    }
+
+    fn sort_by_created(&mut self) {
+        self.sort_by_key(|d| Reverse(d.created));
     }
 }
 
-impl Display for AccountCategory {
+impl Display for DispatchCategory {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            match self {
-                AccountCategory::Any => String::new(),
-                c => format!("{c:?}"),
-            }
-        )
+        let (main, sub) = match self {
+            DispatchCategory::Factbook(sub) => ("Factbook", sub.to_string()),
+            DispatchCategory::Bulletin(sub) => ("Bulletin", sub.to_string()),
+            DispatchCategory::Account(sub) => ("Account", sub.to_string()),
+            DispatchCategory::Meta(sub) => ("Meta", sub.to_string()),
+        };
+        if sub.is_empty() {
+            f.write_str(main)
+        } else {
+            write!(f, "{main}: {sub}")
+        }
     }
 }
 
-impl Display for MetaCategory {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            match self {
-                MetaCategory::Any => String::new(),
-                c => format!("{c:?}"),
-            }
-        )
+/// Reads a category as it is written by `Display`,
+/// such as `Factbook: History`, or `Factbook` for any factbook.
+impl FromStr for DispatchCategory {
+    type Err = strum::ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (main, sub) = s.split_once(": ").unwrap_or((s, ""));
+        Self::from_parts(main, sub)
     }
 }
 
-impl Display for DispatchCategory {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", {
-            let c: (&str, Option<String>) = match self {
-                DispatchCategory::Factbook(subcategory) => (
-                    "Factbook",
-                    match subcategory {
-                        FactbookCategory::Any => None,
-                        other => Some(other.to_string()),
-                    },
-                ),
-                DispatchCategory::Bulletin(subcategory) => (
-                    "Bulletin",
-                    match subcategory {
-                        BulletinCategory::Any => None,
-                        other => Some(other.to_string()),
-                    },
-                ),
-                DispatchCategory::Account(subcategory) => (
-                    "Account",
-                    match subcategory {
-                        AccountCategory::Any => None,
-                        other => Some(other.to_string()),
-                    },
-                ),
-                DispatchCategory::Meta(subcategory) => (
-                    "Meta",
-                    match subcategory {
-                        MetaCategory::Any => None,
-                        other => Some(other.to_string()),
-                    },
-                ),
-            };
-            format!(
-                "{}{}",
-                c.0,
-                c.1.map(|x| format!(": {x}")).unwrap_or_default(),
-            )
-        })
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn category_from_str() {
+        for category in [
+            DispatchCategory::Factbook(FactbookCategory::History),
+            DispatchCategory::Bulletin(BulletinCategory::Any),
+            DispatchCategory::Account(AccountCategory::Other),
+            DispatchCategory::Meta(MetaCategory::Reference),
+        ] {
+            assert_eq!(
+                category.to_string().parse::<DispatchCategory>(),
+                Ok(category)
+            );
+        }
+        assert_eq!(
+            "Bulletin".parse(),
+            Ok(DispatchCategory::Bulletin(BulletinCategory::Any))
+        );
+        assert!("Factbook: Any".parse::<DispatchCategory>().is_err());
+        assert!("Meta: History".parse::<DispatchCategory>().is_err());
+        assert!("Poetry".parse::<DispatchCategory>().is_err());
     }
 }
@@ -0,0 +1,221 @@
+//! Who endorses whom among the World Assembly members of a region.
+//!
+//! An [`EndorsementGraph`] is usually fetched with
+//! [`Client::get_endorsement_graph`](crate::client::Client::get_endorsement_graph),
+//! but it can also be built by hand:
+//! ```rust
+//! # use crustacean_states::models::endorsements::EndorsementGraph;
+//! # use crustacean_states::models::name::NationName;
+//! let mut graph = EndorsementGraph::new();
+//! graph.add_endorsements("testlandia", ["maxtopia", "aramos"]);
+//! graph.add_endorsements("maxtopia", ["testlandia"]);
+//! graph.add_endorsements("aramos", [] as [&str; 0]);
+//!
+//! assert_eq!(graph.endorsements_received("testlandia"), 2);
+//! assert_eq!(graph.endorsements_given("aramos"), 1);
+//! // Maxtopia has not endorsed Aramos yet.
+//! assert_eq!(graph.missing_endorsements("maxtopia"), [NationName::from("aramos")]);
+//! ```
+
+use crate::models::name::{NSName, NationName};
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt::Write,
+};
+
+/// A directed graph of endorsements between World Assembly members,
+/// with an edge from every nation to each nation that it endorses.
+#[derive(Clone, Debug, Default)]
+pub struct EndorsementGraph {
+    /// Every member, in the order they were added.
+    members: Vec<NationName>,
+    /// The nations that endorse each member.
+    endorsed_by: HashMap<NationName, HashSet<NationName>>,
+    /// The nations that each nation endorses.
+    endorses: HashMap<NationName, HashSet<NationName>>,
+    /// Members whose endorsements could not be fetched, and why.
+    unfetched: Vec<(NationName, String)>,
+}
+
+impl EndorsementGraph {
+    /// Creates an empty graph.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a member of the World Assembly and the nations that endorse it.
+    pub fn add_endorsements<I, N>(&mut self, nation: impl Into<NationName>, endorsers: I)
+    where
+        I: IntoIterator<Item = N>,
+        N: Into<NationName>,
+    {
+        let nation = nation.into();
+        if !self.endorsed_by.contains_key(&nation) {
+            self.members.push(nation.clone());
+        }
+        let received = self.endorsed_by.entry(nation.clone()).or_default();
+        for endorser in endorsers {
+            let endorser = endorser.into();
+            received.insert(endorser.clone());
+            self.endorses
+                .entry(endorser)
+                .or_default()
+                .insert(nation.clone());
+        }
+    }
+
+    /// Adds a member of the World Assembly whose endorsements could not be fetched,
+    /// and the reason they could not be.
+    ///
+    /// It is still a member of the graph,
+    /// but only the endorsements it gives to other members are known.
+    pub fn add_unfetched(&mut self, nation: impl Into<NationName>, reason: impl ToString) {
+        let nation = nation.into();
+        self.add_endorsements(nation.clone(), [] as [NationName; 0]);
+        self.unfetched.push((nation, reason.to_string()));
+    }
+
+    /// Every member of the graph, in the order they were added.
+    pub fn members(&self) -> &[NationName] {
+        &self.members
+    }
+
+    /// The members whose endorsements could not be fetched, and why.
+    pub fn unfetched(&self) -> &[(NationName, String)] {
+        &self.unfetched
+    }
+
+    /// The nations that endorse `nation`.
+    pub fn endorsers(&self, nation: impl Into<NationName>) -> impl Iterator<Item = &NationName> {
+        self.endorsed_by.get(&nation.into()).into_iter().flatten()
+    }
+
+    /// The nations that `nation` endorses.
+    pub fn endorsed(&self, nation: impl Into<NationName>) -> impl Iterator<Item = &NationName> {
+        self.endorses.get(&nation.into()).into_iter().flatten()
+    }
+
+    /// The number of endorsements `nation` has received.
+    pub fn endorsements_received(&self, nation: impl Into<NationName>) -> usize {
+        self.endorsed_by.get(&nation.into()).map_or(0, HashSet::len)
+    }
+
+    /// The number of endorsements `nation` has given.
+    pub fn endorsements_given(&self, nation: impl Into<NationName>) -> usize {
+        self.endorses.get(&nation.into()).map_or(0, HashSet::len)
+    }
+
+    /// The members that `nation` has not endorsed yet, in the order they were added.
+    pub fn missing_endorsements(&self, nation: impl Into<NationName>) -> Vec<NationName> {
+        let nation = nation.into();
+        let endorsed = self.endorses.get(&nation);
+        self.members
+            .iter()
+            .filter(|m| **m != nation && !endorsed.is_some_and(|e| e.contains(*m)))
+            .cloned()
+            .collect()
+    }
+
+    /// The nations that endorse `nation` but that `nation` does not endorse back.
+    pub fn unreturned_endorsements(&self, nation: impl Into<NationName>) -> Vec<NationName> {
+        let nation = nation.into();
+        let endorsed = self.endorses.get(&nation);
+        let mut unreturned = self
+            .endorsers(nation.clone())
+            .filter(|e| !endorsed.is_some_and(|d| d.contains(*e)))
+            .cloned()
+            .collect::<Vec<_>>();
+        unreturned.sort_by_key(NSName::canonical);
+        unreturned
+    }
+
+    /// The members, most endorsed first.
+    /// Members with the same number of endorsements are kept in the order they were added.
+    pub fn by_endorsements(&self) -> Vec<(&NationName, usize)> {
+        let mut members = self
+            .members
+            .iter()
+            .map(|m| (m, self.endorsed_by.get(m).map_or(0, HashSet::len)))
+            .collect::<Vec<_>>();
+        members.sort_by_key(|&(_, endorsements)| Reverse(endorsements));
+        members
+    }
+
+    /// The canonical name of every nation that endorses another,
+    /// mapped to the sorted canonical names of the nations it endorses.
+    pub fn adjacency_list(&self) -> BTreeMap<String, Vec<String>> {
+        self.endorses
+            .iter()
+            .map(|(endorser, endorsed)| {
+                let mut endorsed = endorsed.iter().map(NSName::canonical).collect::<Vec<_>>();
+                endorsed.sort();
+                (endorser.canonical(), endorsed)
+            })
+            .collect()
+    }
+
+    /// Writes the graph in the DOT language, for Graphviz and similar tools.
+    /// Nations are named by their canonical names, and edges are sorted.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph endorsements {\n");
+        let mut members = self
+            .members
+            .iter()
+            .map(NSName::canonical)
+            .collect::<Vec<_>>();
+        members.sort();
+        for member in members {
+            writeln!(dot, "  \"{member}\";").unwrap();
+        }
+        for (endorser, endorsed) in self.adjacency_list() {
+            for nation in endorsed {
+                writeln!(dot, "  \"{endorser}\" -> \"{nation}\";").unwrap();
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn endorsement_graph() {
+        let mut graph = EndorsementGraph::new();
+        graph.add_endorsements("Testlandia", ["maxtopia", "aramos"]);
+        graph.add_endorsements("maxtopia", ["testlandia"]);
+        graph.add_unfetched("aramos", "request timed out");
+
+        assert_eq!(graph.members().len(), 3);
+        assert_eq!(
+            graph.unfetched(),
+            [(
+                NationName::from("aramos"),
+                String::from("request timed out")
+            )]
+        );
+        assert_eq!(
+            graph.unreturned_endorsements("testlandia"),
+            [NationName::from("aramos")]
+        );
+        assert_eq!(
+            graph.by_endorsements()[0],
+            (&NationName::from("testlandia"), 2)
+        );
+        assert_eq!(
+            graph.to_dot(),
+            "digraph endorsements {
+  \"aramos\";
+  \"maxtopia\";
+  \"testlandia\";
+  \"aramos\" -> \"testlandia\";
+  \"maxtopia\" -> \"testlandia\";
+  \"testlandia\" -> \"maxtopia\";
+}
+"
+        );
+    }
+}
@@ -0,0 +1,180 @@
+//! Daily counts of nations founded in each region, for recruitment dashboards.
+//!
+//! A [`FoundingRate`] is fed foundings from the happenings, for example from
+//! [`HappeningsStream`](crate::streams::happenings::HappeningsStream)
+//! with [`HappeningsFilterType::Founding`](crate::shards::world::HappeningsFilterType::Founding),
+//! and counts them by region and by UTC day:
+//! ```rust
+//! # use crustacean_states::models::founding_rate::{DailyFoundings, FoundingRate};
+//! # use crustacean_states::models::refounds::{Founding, FoundingKind};
+//! # use crustacean_states::parsers::happenings::Event;
+//! # fn events() -> Vec<Event> { vec![] }
+//! let mut rate = FoundingRate::new();
+//! for event in events() {
+//!     if let Some(founding) = Founding::from_event(&event) {
+//!         rate.record(&founding);
+//!     }
+//! }
+//! // Counts from elsewhere, such as the difference between two daily dumps, can be added too.
+//! rate.add("The Pacific", 86_400, 120, 15);
+//! assert_eq!(
+//!     rate.series("the_pacific"),
+//!     [DailyFoundings { day: 86_400, founded: 120, refounded: 15 }]
+//! );
+//! ```
+
+use crate::models::{
+    name::RegionName,
+    refounds::{Founding, FoundingKind},
+    timestamp::DAY,
+};
+use std::collections::{BTreeMap, HashMap};
+
+/// The number of nations founded in a region on one UTC day.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct DailyFoundings {
+    /// The Unix timestamp of the start of the day.
+    pub day: u64,
+    /// The number of nations founded for the first time.
+    pub founded: u64,
+    /// The number of nations refounded.
+    pub refounded: u64,
+}
+
+impl DailyFoundings {
+    /// The number of nations founded and refounded.
+    pub fn total(&self) -> u64 {
+        self.founded + self.refounded
+    }
+}
+
+/// Counts the nations founded in each region on each UTC day.
+#[derive(Clone, Debug, Default)]
+pub struct FoundingRate {
+    regions: HashMap<RegionName, BTreeMap<u64, DailyFoundings>>,
+}
+
+impl FoundingRate {
+    /// Creates an empty count.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Counts a founding.
+    pub fn record(&mut self, founding: &Founding) {
+        let (founded, refounded) = match founding.kind {
+            FoundingKind::Founded => (1, 0),
+            FoundingKind::Refounded => (0, 1),
+        };
+        self.add(
+            founding.region.clone(),
+            founding.timestamp,
+            founded,
+            refounded,
+        );
+    }
+
+    /// Adds foundings counted elsewhere to the day that `timestamp` falls on.
+    pub fn add(
+        &mut self,
+        region: impl Into<RegionName>,
+        timestamp: u64,
+        founded: u64,
+        refounded: u64,
+    ) {
+        let day = timestamp - timestamp % DAY;
+        let count = self
+            .regions
+            .entry(region.into())
+            .or_default()
+            .entry(day)
+            .or_insert(DailyFoundings {
+                day,
+                ..Default::default()
+            });
+        count.founded += founded;
+        count.refounded += refounded;
+    }
+
+    /// The regions that have any foundings counted.
+    pub fn regions(&self) -> impl Iterator<Item = &RegionName> {
+        self.regions.keys()
+    }
+
+    /// The foundings in a region on every day from the first day with any to the last,
+    /// oldest first.
+    /// Days in between with no foundings are included with counts of zero.
+    pub fn series(&self, region: impl Into<RegionName>) -> Vec<DailyFoundings> {
+        let Some(days) = self.regions.get(&region.into()) else {
+            return Vec::new();
+        };
+        let (Some(&first), Some(&last)) = (days.keys().next(), days.keys().next_back()) else {
+            return Vec::new();
+        };
+        (first..=last)
+            .step_by(DAY as usize)
+            .map(|day| {
+                days.get(&day).copied().unwrap_or(DailyFoundings {
+                    day,
+                    ..Default::default()
+                })
+            })
+            .collect()
+    }
+
+    /// The average number of nations founded and refounded in a region per day,
+    /// over its whole series.
+    pub fn average_per_day(&self, region: impl Into<RegionName>) -> Option<f64> {
+        let series = self.series(region);
+        let total = series.iter().map(DailyFoundings::total).sum::<u64>();
+        (!series.is_empty()).then(|| total as f64 / series.len() as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::happenings::Event;
+
+    #[test]
+    fn founding_rate() {
+        let founding = |timestamp, text: &str| {
+            Founding::from_event(&Event::from_text(timestamp, text)).unwrap()
+        };
+        let mut rate = FoundingRate::new();
+        rate.record(&founding(10, "@@a@@ was founded in %%the_pacific%%."));
+        rate.record(&founding(20, "@@b@@ was refounded in %%the_pacific%%."));
+        rate.record(&founding(
+            2 * DAY + 5,
+            "@@c@@ was founded in %%the_pacific%%.",
+        ));
+        rate.record(&founding(30, "@@d@@ was founded in %%lazarus%%."));
+
+        assert_eq!(rate.regions().count(), 2);
+        assert_eq!(
+            rate.series("The Pacific"),
+            [
+                DailyFoundings {
+                    day: 0,
+                    founded: 1,
+                    refounded: 1
+                },
+                DailyFoundings {
+                    day: DAY,
+                    founded: 0,
+                    refounded: 0
+                },
+                DailyFoundings {
+                    day: 2 * DAY,
+                    founded: 1,
+                    refounded: 0
+                },
+            ]
+        );
+        assert_eq!(rate.average_per_day("the_pacific"), Some(1.0));
+        assert_eq!(rate.series("osiris"), []);
+        assert_eq!(rate.average_per_day("osiris"), None);
+    }
+}
@@ -0,0 +1,492 @@
+//! Flat views of parsed types, for serving to web frontends as JSON.
+//!
+//! The derived `Serialize` implementations on [`Nation`] and [`Region`]
+//! follow the shape of the Rust types, which is awkward to use from JavaScript:
+//! enums become objects keyed by their variant, and unrequested fields become `null`.
+//! The views here borrow from a parsed value and serialize it
+//! with camelCase keys, enums as camelCase strings, nested values flattened,
+//! and fields that were not sent left out.
+//! ```rust
+//! # use crustacean_states::{models::json::NationJson, parsers::nation::Nation};
+//! # use crustacean_states::parsers::NSResponse;
+//! let nation = Nation::from_xml(
+//!     r#"<NATION id="aramos"><UNSTATUS>WA Delegate</UNSTATUS><POPULATION>42</POPULATION></NATION>"#,
+//! )?;
+//! let json = serde_json::to_string(&NationJson::from(&nation))?;
+//! assert_eq!(json, r#"{"name":"Aramos","waStatus":"delegate","population":42}"#);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+use crate::{
+    models::name::NameList,
+    parsers::{
+        nation::{Nation, WAStatus, WAVoteState},
+        region::{EmbassyKind, Officer, OfficerAuthority, Region, RegionWAVote},
+        CensusData, DefaultOrCustom, MaybeRelativeTime, MaybeSystemTime,
+    },
+};
+use serde::Serialize;
+use std::num::NonZeroU64;
+
+/// A flat view of a [`Nation`].
+#[derive(Clone, Debug, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+#[allow(missing_docs)]
+pub struct NationJson<'a> {
+    pub name: &'a str,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub kind: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub full_name: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub motto: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub region: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flag: Option<&'a str>,
+    /// One of `delegate`, `member`, `nonMember`, or the status as it was sent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wa_status: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub endorsements: Option<&'a NameList>,
+    /// One of `for`, `against`, `undecided`, `nonMember` or `unknown`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ga_vote: Option<&'static str>,
+    /// One of `for`, `against`, `undecided`, `nonMember` or `unknown`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sc_vote: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub population: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issues_answered: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub civil_rights: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub economy: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub political_freedom: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub civil_rights_score: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub economy_score: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub political_freedom_score: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tax: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gdp: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub income: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub animal: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub currency: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub demonym_adjective: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub demonym_singular: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub demonym_plural: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub major_industry: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub government_priority: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub leader: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub capital: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub religion: Option<&'a str>,
+    /// Left out if the nation was founded before founding times were recorded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub founded: Option<&'a str>,
+    /// Left out if the nation was founded before founding times were recorded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub founded_time: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_login: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_login: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_activity: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub influence: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub census: Option<Vec<CensusJson>>,
+}
+
+impl<'a> From<&'a Nation> for NationJson<'a> {
+    fn from(nation: &'a Nation) -> Self {
+        Self {
+            name: &nation.name,
+            kind: nation.kind.as_deref(),
+            full_name: nation.full_name.as_deref(),
+            motto: nation.motto.as_deref(),
+            category: nation.category.as_deref(),
+            region: nation.region.as_deref(),
+            flag: nation.flag.as_deref(),
+            wa_status: nation.wa_status.as_ref().map(wa_status),
+            endorsements: nation.endorsements.as_ref(),
+            ga_vote: nation.ga_vote.map(vote),
+            sc_vote: nation.sc_vote.map(vote),
+            population: nation.population,
+            issues_answered: nation.issues_answered,
+            civil_rights: nation.freedom.as_ref().map(|f| f.civil_rights.as_str()),
+            economy: nation.freedom.as_ref().map(|f| f.economy.as_str()),
+            political_freedom: nation
+                .freedom
+                .as_ref()
+                .map(|f| f.political_freedom.as_str()),
+            civil_rights_score: nation
+                .freedom_scores
+                .as_ref()
+                .map(|f| f.civil_rights.as_u8()),
+            economy_score: nation.freedom_scores.as_ref().map(|f| f.economy.as_u8()),
+            political_freedom_score: nation
+                .freedom_scores
+                .as_ref()
+                .map(|f| f.political_freedom.as_u8()),
+            tax: nation.tax,
+            gdp: nation.gdp,
+            income: nation.income,
+            animal: nation.animal.as_deref(),
+            currency: nation.currency.as_deref(),
+            demonym_adjective: nation.demonym_adjective.as_deref(),
+            demonym_singular: nation.demonym_singular.as_deref(),
+            demonym_plural: nation.demonym_plural.as_deref(),
+            major_industry: nation.major_industry.as_deref(),
+            government_priority: nation.government_priority.as_deref(),
+            leader: nation.leader.as_ref().map(default_or_custom),
+            capital: nation.capital.as_ref().map(default_or_custom),
+            religion: nation.religion.as_ref().map(default_or_custom),
+            founded: nation.founded.as_ref().and_then(relative_time),
+            founded_time: nation.founded_time.as_ref().and_then(system_time),
+            first_login: nation.first_login,
+            last_login: nation.last_login,
+            last_activity: nation.last_activity.as_deref(),
+            influence: nation.influence.as_deref(),
+            census: nation.census.as_ref().map(census),
+        }
+    }
+}
+
+/// A flat view of a [`Region`].
+#[derive(Clone, Debug, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+#[allow(missing_docs)]
+pub struct RegionJson<'a> {
+    pub name: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flag: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub banner_url: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub factbook: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub power: Option<&'a str>,
+    /// Left out if the region has no delegate.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delegate: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delegate_votes: Option<u32>,
+    /// Left out if the region has no founder.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub founder: Option<&'a str>,
+    /// Left out if the region has no governor.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub governor: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub officers: Option<Vec<OfficerJson<'a>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub num_nations: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub num_wa_nations: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nations: Option<&'a NameList>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wa_nations: Option<&'a NameList>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frontier: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<&'a str>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub embassies: Option<Vec<EmbassyJson<'a>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ga_vote: Option<VoteJson>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sc_vote: Option<VoteJson>,
+    /// Left out if the region was founded before founding times were recorded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub founded: Option<&'a str>,
+    /// Left out if the region was founded before founding times were recorded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub founded_time: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_update: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_major_update: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_minor_update: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub census: Option<Vec<CensusJson>>,
+}
+
+impl<'a> From<&'a Region> for RegionJson<'a> {
+    fn from(region: &'a Region) -> Self {
+        Self {
+            name: &region.name,
+            flag: region.flag.as_deref(),
+            banner_url: region.banner_url.as_deref(),
+            factbook: region.factbook.as_deref(),
+            power: region.power.as_deref(),
+            delegate: region.delegate.as_ref().and_then(Option::as_deref),
+            delegate_votes: region.delegate_votes,
+            founder: region.founder.as_ref().and_then(Option::as_deref),
+            governor: region.governor.as_ref().and_then(Option::as_deref),
+            officers: region
+                .officers
+                .as_ref()
+                .map(|officers| officers.iter().map(OfficerJson::from).collect()),
+            num_nations: region.num_nations,
+            num_wa_nations: region.num_wa_nations,
+            nations: region.nations.as_ref(),
+            wa_nations: region.wa_nations.as_ref(),
+            frontier: region.frontier,
+            tags: region
+                .tags
+                .as_ref()
+                .map(|tags| tags.iter().map(|t| t.as_str()).collect()),
+            embassies: region.embassies.as_ref().map(|embassies| {
+                embassies
+                    .iter()
+                    .map(|e| EmbassyJson {
+                        region: &e.region,
+                        status: embassy_kind(&e.kind),
+                    })
+                    .collect()
+            }),
+            ga_vote: region.ga_vote.as_ref().map(VoteJson::from),
+            sc_vote: region.sc_vote.as_ref().map(VoteJson::from),
+            founded: region.founded.as_ref().and_then(relative_time),
+            founded_time: region.founded_time.as_ref().and_then(system_time),
+            last_update: region.last_update,
+            last_major_update: region.last_major_update,
+            last_minor_update: region.last_minor_update,
+            census: region.census.as_ref().map(census),
+        }
+    }
+}
+
+/// A flat view of an [`Officer`].
+#[derive(Clone, Debug, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+#[allow(missing_docs)]
+pub struct OfficerJson<'a> {
+    pub nation: &'a str,
+    pub office: &'a str,
+    /// The authorities as camelCase strings, such as `borderControl`.
+    pub authority: Vec<&'static str>,
+    pub appointed: u64,
+    pub appointed_by: &'a str,
+}
+
+impl<'a> From<&'a Officer> for OfficerJson<'a> {
+    fn from(officer: &'a Officer) -> Self {
+        Self {
+            nation: &officer.nation,
+            office: &officer.office,
+            authority: officer.authority.iter().map(authority).collect(),
+            appointed: officer.time,
+            appointed_by: &officer.by,
+        }
+    }
+}
+
+/// An embassy, with its state as a camelCase string, such as `established`.
+#[derive(Clone, Debug, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[allow(missing_docs)]
+pub struct EmbassyJson<'a> {
+    pub region: &'a str,
+    pub status: &'static str,
+}
+
+/// The votes of a region on a World Assembly resolution.
+#[derive(Clone, Debug, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[allow(missing_docs)]
+pub struct VoteJson {
+    #[serde(rename = "for")]
+    pub for_votes: u32,
+    #[serde(rename = "against")]
+    pub against_votes: u32,
+}
+
+impl From<&RegionWAVote> for VoteJson {
+    fn from(vote: &RegionWAVote) -> Self {
+        Self {
+            for_votes: vote.for_votes,
+            against_votes: vote.against_votes,
+        }
+    }
+}
+
+/// One World Census scale, whether the data was current or historical.
+#[derive(Clone, Debug, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+#[allow(missing_docs)]
+pub struct CensusJson {
+    pub scale: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub world_rank: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub region_rank: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub percent_world_rank: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub percent_region_rank: Option<f64>,
+    /// Only sent with historical data.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<u64>,
+}
+
+fn census(data: &CensusData) -> Vec<CensusJson> {
+    match data {
+        CensusData::Current(data) => data
+            .iter()
+            .map(|d| CensusJson {
+                scale: d.id,
+                score: d.score,
+                world_rank: d.world_rank.map(u32::from),
+                region_rank: d.region_rank.map(u32::from),
+                percent_world_rank: d.percent_world_rank,
+                percent_region_rank: d.percent_region_rank,
+                timestamp: None,
+            })
+            .collect(),
+        CensusData::Historical(data) => data
+            .iter()
+            .map(|d| CensusJson {
+                scale: d.id,
+                score: d.score,
+                world_rank: None,
+                region_rank: None,
+                percent_world_rank: None,
+                percent_region_rank: None,
+                timestamp: d.timestamp.map(u64::from),
+            })
+            .collect(),
+    }
+}
+
+fn wa_status(status: &WAStatus) -> &str {
+    match status {
+        WAStatus::Delegate => "delegate",
+        WAStatus::Member => "member",
+        WAStatus::NonMember => "nonMember",
+        WAStatus::Unknown(status) => status,
+    }
+}
+
+fn vote(vote: WAVoteState) -> &'static str {
+    match vote {
+        WAVoteState::NonMember => "nonMember",
+        WAVoteState::Undecided => "undecided",
+        WAVoteState::For => "for",
+        WAVoteState::Against => "against",
+        WAVoteState::Unknown => "unknown",
+    }
+}
+
+fn authority(authority: OfficerAuthority) -> &'static str {
+    match authority {
+        OfficerAuthority::Executive => "executive",
+        OfficerAuthority::WorldAssembly => "worldAssembly",
+        OfficerAuthority::Succession => "succession",
+        OfficerAuthority::Appearance => "appearance",
+        OfficerAuthority::BorderControl => "borderControl",
+        OfficerAuthority::Communications => "communications",
+        OfficerAuthority::Embassies => "embassies",
+        OfficerAuthority::Polls => "polls",
+    }
+}
+
+fn embassy_kind(kind: &EmbassyKind) -> &'static str {
+    match kind {
+        EmbassyKind::Established => "established",
+        EmbassyKind::Pending => "pending",
+        EmbassyKind::Invited => "invited",
+        EmbassyKind::Requested => "requested",
+        EmbassyKind::Denied => "denied",
+        EmbassyKind::Closing => "closing",
+    }
+}
+
+fn default_or_custom(value: &DefaultOrCustom) -> &str {
+    match value {
+        DefaultOrCustom::Default(value) | DefaultOrCustom::Custom(value) => value,
+    }
+}
+
+fn relative_time(time: &MaybeRelativeTime) -> Option<&str> {
+    match time {
+        MaybeRelativeTime::Recorded(time) => Some(time),
+        MaybeRelativeTime::Antiquity => None,
+    }
+}
+
+fn system_time(time: &MaybeSystemTime) -> Option<u64> {
+    match time {
+        MaybeSystemTime::Recorded(time) => Some(NonZeroU64::get(*time)),
+        MaybeSystemTime::Antiquity => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn region_json() {
+        let region = Region::from_xml(
+            r#"<REGION id="testregionia">
+<NATIONS>testlandia:maxtopia</NATIONS>
+<DELEGATE>0</DELEGATE>
+<FOUNDER>testlandia</FOUNDER>
+<EMBASSIES><EMBASSY type="pending">Osiris</EMBASSY></EMBASSIES>
+<TAGS><TAG>Anti-Capitalist</TAG></TAGS>
+<GAVOTE><FOR>2</FOR><AGAINST>1</AGAINST></GAVOTE>
+<CENSUS><SCALE id="0"><SCORE>12.5</SCORE><RANK>4</RANK></SCALE></CENSUS>
+<OFFICERS><OFFICER><NATION>maxtopia</NATION><OFFICE>Minister</OFFICE><AUTHORITY>BP</AUTHORITY><TIME>5</TIME><BY>testlandia</BY><ORDER>1</ORDER></OFFICER></OFFICERS>
+</REGION>"#,
+        )
+        .unwrap();
+        assert_eq!(
+            serde_json::to_value(RegionJson::from(&region)).unwrap(),
+            json!({
+                "name": "Testregionia",
+                "founder": "testlandia",
+                "officers": [{
+                    "nation": "maxtopia",
+                    "office": "Minister",
+                    "authority": ["borderControl", "polls"],
+                    "appointed": 5,
+                    "appointedBy": "testlandia",
+                }],
+                "nations": ["testlandia", "maxtopia"],
+                "tags": ["anti-capitalist"],
+                "embassies": [{ "region": "Osiris", "status": "pending" }],
+                "gaVote": { "for": 2, "against": 1 },
+                "census": [{ "scale": 0, "score": 12.5, "worldRank": 4 }],
+            })
+        );
+    }
+}
@@ -1,2 +1,18 @@
 //! Models that are useful for both sending and receiving information.
+pub mod banner;
+pub mod census;
+#[cfg(feature = "parsers")]
+pub mod diff;
 pub mod dispatch;
+pub mod endorsements;
+#[cfg(feature = "parsers")]
+pub mod founding_rate;
+#[cfg(all(feature = "serde", feature = "parsers"))]
+pub mod json;
+pub mod name;
+#[cfg(feature = "parsers")]
+pub mod refounds;
+#[cfg(feature = "client")]
+pub mod telegram;
+pub mod timestamp;
+pub mod update;
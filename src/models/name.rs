@@ -0,0 +1,655 @@
+//! Contains the names of things on NationStates.
+
+use crate::{pretty_name, safe_name};
+#[cfg(feature = "intern")]
+use std::collections::HashSet;
+use std::{
+    borrow::Cow,
+    fmt::{Debug, Display, Formatter},
+    hash::{Hash, Hasher},
+    ops::Index,
+    sync::Arc,
+};
+
+/// A name on NationStates.
+///
+/// NationStates does not tell apart names that differ only in capitalization,
+/// or in spaces and underscores, so names are compared by their canonical form:
+/// ```rust
+/// # use crustacean_states::models::name::{NationName, NSName};
+/// let name = NationName::from("The North Pacific Puppet");
+/// assert_eq!(name.canonical(), "the_north_pacific_puppet");
+/// assert_eq!(name, NationName::from("the_north_pacific_puppet"));
+/// ```
+pub trait NSName: AsRef<str> {
+    /// The lowercase, web-safe form of the name. See [`safe_name`](crate::safe_name).
+    fn canonical(&self) -> String {
+        safe_name(self.as_ref())
+    }
+}
+
+/// The bytes of the [canonical](NSName::canonical) form of a name, one at a time.
+///
+/// Names are compared and hashed very often, as the keys of maps and sets,
+/// so this avoids building the canonical form as a new `String` each time.
+fn canonical_bytes(name: &str) -> impl Iterator<Item = u8> + '_ {
+    name.bytes().map(|b| match b {
+        b' ' => b'_',
+        b => b.to_ascii_lowercase(),
+    })
+}
+
+/// Whether two names have the same canonical form.
+pub(crate) fn canonical_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len() && canonical_bytes(a).eq(canonical_bytes(b))
+}
+
+/// Hashes the canonical form of a name,
+/// ending it like a `str` so that no name hashes as a prefix of another.
+fn canonical_hash<H: Hasher>(name: &str, state: &mut H) {
+    for b in canonical_bytes(name) {
+        state.write_u8(b);
+    }
+    state.write_u8(0xff);
+}
+
+/// The name of a nation, as it was given.
+///
+/// Names are equal if their [canonical](NSName::canonical) forms are equal.
+/// The name is shared, so cloning it does not copy the text.
+#[derive(Clone, Debug)]
+pub struct NationName(Arc<str>);
+
+impl NationName {
+    /// Wraps a nation name.
+    pub fn new(name: impl ToString) -> Self {
+        Self(Arc::from(name.to_string()))
+    }
+
+    /// The name as it was given.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The lowercase, web-safe form of the name. See [`safe_name`](crate::safe_name).
+    pub fn safe_name(&self) -> String {
+        safe_name(&self.0)
+    }
+
+    /// The name with spaces and capital letters. See [`pretty_name`](crate::pretty_name).
+    pub fn pretty_name(&self) -> String {
+        pretty_name(&self.0)
+    }
+}
+
+impl NSName for NationName {}
+
+impl PartialEq for NationName {
+    fn eq(&self, other: &Self) -> bool {
+        canonical_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for NationName {}
+
+impl Hash for NationName {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        canonical_hash(&self.0, state)
+    }
+}
+
+impl Display for NationName {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<String> for NationName {
+    fn from(value: String) -> Self {
+        Self(Arc::from(value))
+    }
+}
+
+impl From<&str> for NationName {
+    fn from(value: &str) -> Self {
+        Self(Arc::from(value))
+    }
+}
+
+impl From<Arc<str>> for NationName {
+    fn from(value: Arc<str>) -> Self {
+        Self(value)
+    }
+}
+
+impl From<NationName> for String {
+    fn from(value: NationName) -> Self {
+        value.0.to_string()
+    }
+}
+
+impl AsRef<str> for NationName {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for NationName {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for NationName {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Self::from)
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for NationName {
+    fn inline_schema() -> bool {
+        true
+    }
+
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        String::schema_name()
+    }
+
+    fn json_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        String::json_schema(generator)
+    }
+}
+
+/// Requests are sent with the safe form of the name.
+impl From<NationName> for Cow<'_, str> {
+    fn from(value: NationName) -> Self {
+        Cow::Owned(value.safe_name())
+    }
+}
+
+/// Requests are sent with the safe form of the name.
+impl From<&NationName> for Cow<'_, str> {
+    fn from(value: &NationName) -> Self {
+        Cow::Owned(value.safe_name())
+    }
+}
+
+/// The name of a region, as it was given.
+///
+/// Names are equal if their [canonical](NSName::canonical) forms are equal.
+/// The name is shared, so cloning it does not copy the text.
+#[derive(Clone, Debug)]
+pub struct RegionName(Arc<str>);
+
+impl RegionName {
+    /// Wraps a region name.
+    pub fn new(name: impl ToString) -> Self {
+        Self(Arc::from(name.to_string()))
+    }
+
+    /// The name as it was given.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The lowercase, web-safe form of the name. See [`safe_name`](crate::safe_name).
+    pub fn safe_name(&self) -> String {
+        safe_name(&self.0)
+    }
+
+    /// The name with spaces and capital letters. See [`pretty_name`](crate::pretty_name).
+    pub fn pretty_name(&self) -> String {
+        pretty_name(&self.0)
+    }
+}
+
+impl NSName for RegionName {}
+
+impl PartialEq for RegionName {
+    fn eq(&self, other: &Self) -> bool {
+        canonical_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for RegionName {}
+
+impl Hash for RegionName {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        canonical_hash(&self.0, state)
+    }
+}
+
+impl Display for RegionName {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<String> for RegionName {
+    fn from(value: String) -> Self {
+        Self(Arc::from(value))
+    }
+}
+
+impl From<&str> for RegionName {
+    fn from(value: &str) -> Self {
+        Self(Arc::from(value))
+    }
+}
+
+impl From<Arc<str>> for RegionName {
+    fn from(value: Arc<str>) -> Self {
+        Self(value)
+    }
+}
+
+impl From<RegionName> for String {
+    fn from(value: RegionName) -> Self {
+        value.0.to_string()
+    }
+}
+
+impl AsRef<str> for RegionName {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for RegionName {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for RegionName {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Self::from)
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for RegionName {
+    fn inline_schema() -> bool {
+        true
+    }
+
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        String::schema_name()
+    }
+
+    fn json_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        String::json_schema(generator)
+    }
+}
+
+/// Requests are sent with the safe form of the name.
+impl From<RegionName> for Cow<'_, str> {
+    fn from(value: RegionName) -> Self {
+        Cow::Owned(value.safe_name())
+    }
+}
+
+/// Requests are sent with the safe form of the name.
+impl From<&RegionName> for Cow<'_, str> {
+    fn from(value: &RegionName) -> Self {
+        Cow::Owned(value.safe_name())
+    }
+}
+
+/// A list of names, stored as one string and the offsets where each name ends.
+///
+/// Lists like endorsements or the nations in a feeder region can have thousands of names,
+/// so they are kept in a single allocation rather than one `String` per name.
+/// The allocation is shared between clones, and only copied if a clone is changed:
+/// ```rust
+/// # use crustacean_states::models::name::NameList;
+/// let list = NameList::split("testlandia:aramos::maxtopia", ':');
+/// assert_eq!(list.len(), 3);
+/// assert_eq!(&list[1], "aramos");
+/// assert!(list.contains("maxtopia"));
+/// assert_eq!(list.iter().collect::<Vec<_>>(), ["testlandia", "aramos", "maxtopia"]);
+/// ```
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct NameList(Arc<NameListInner>);
+
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+struct NameListInner {
+    text: String,
+    ends: Vec<usize>,
+}
+
+impl NameList {
+    /// Creates an empty list.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Splits a list of names as NationStates sends it, skipping empty names.
+    pub fn split(list: &str, separator: char) -> Self {
+        list.split(separator).filter(|s| !s.is_empty()).collect()
+    }
+
+    /// Adds a name to the end of the list.
+    pub fn push(&mut self, name: &str) {
+        let inner = Arc::make_mut(&mut self.0);
+        inner.text.push_str(name);
+        inner.ends.push(inner.text.len());
+    }
+
+    /// The number of names.
+    pub fn len(&self) -> usize {
+        self.0.ends.len()
+    }
+
+    /// Whether there are no names.
+    pub fn is_empty(&self) -> bool {
+        self.0.ends.is_empty()
+    }
+
+    /// The name at `index`, if the list is long enough.
+    pub fn get(&self, index: usize) -> Option<&str> {
+        let end = *self.0.ends.get(index)?;
+        let start = index.checked_sub(1).map_or(0, |i| self.0.ends[i]);
+        Some(&self.0.text[start..end])
+    }
+
+    /// Whether the list has a name, exactly as it was given.
+    pub fn contains(&self, name: &str) -> bool {
+        self.iter().any(|n| n == name)
+    }
+
+    /// Iterates over the names, in order.
+    pub fn iter(&self) -> NameListIter<'_> {
+        NameListIter {
+            list: self,
+            index: 0,
+        }
+    }
+}
+
+impl Debug for NameList {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self).finish()
+    }
+}
+
+impl Index<usize> for NameList {
+    type Output = str;
+
+    fn index(&self, index: usize) -> &str {
+        self.get(index).expect("name list index out of bounds")
+    }
+}
+
+impl<S: AsRef<str>> FromIterator<S> for NameList {
+    fn from_iter<T: IntoIterator<Item = S>>(iter: T) -> Self {
+        let mut list = Self::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<S: AsRef<str>> Extend<S> for NameList {
+    fn extend<T: IntoIterator<Item = S>>(&mut self, iter: T) {
+        iter.into_iter().for_each(|name| self.push(name.as_ref()));
+    }
+}
+
+impl<'l> IntoIterator for &'l NameList {
+    type Item = &'l str;
+    type IntoIter = NameListIter<'l>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl From<NameList> for Vec<String> {
+    fn from(value: NameList) -> Self {
+        value.iter().map(String::from).collect()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for NameList {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for NameList {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::<String>::deserialize(deserializer).map(Self::from_iter)
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for NameList {
+    fn inline_schema() -> bool {
+        true
+    }
+
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        Vec::<String>::schema_name()
+    }
+
+    fn json_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        Vec::<String>::json_schema(generator)
+    }
+}
+
+impl<S: AsRef<str>> PartialEq<[S]> for NameList {
+    fn eq(&self, other: &[S]) -> bool {
+        self.len() == other.len() && self.iter().zip(other).all(|(a, b)| a == b.as_ref())
+    }
+}
+
+impl<S: AsRef<str>> PartialEq<Vec<S>> for NameList {
+    fn eq(&self, other: &Vec<S>) -> bool {
+        *self == other[..]
+    }
+}
+
+impl<S: AsRef<str>, const N: usize> PartialEq<[S; N]> for NameList {
+    fn eq(&self, other: &[S; N]) -> bool {
+        *self == other[..]
+    }
+}
+
+/// An iterator over the names in a [`NameList`], created by [`NameList::iter`].
+#[derive(Clone, Debug)]
+pub struct NameListIter<'l> {
+    list: &'l NameList,
+    index: usize,
+}
+
+impl<'l> Iterator for NameListIter<'l> {
+    type Item = &'l str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let name = self.list.get(self.index)?;
+        self.index += 1;
+        Some(name)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.list.len() - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for NameListIter<'_> {}
+
+/// Shares the text of names that appear many times,
+/// such as in endorsement lists or in the nations of a large region.
+///
+/// Every name with the same text, as it was given, is backed by the same allocation:
+/// ```rust
+/// # use crustacean_states::models::name::NameInterner;
+/// let mut interner = NameInterner::new();
+/// let names = interner.nations("testlandia,aramos,testlandia".split(','));
+/// assert_eq!(names.len(), 3);
+/// assert_eq!(interner.len(), 2);
+/// assert!(std::ptr::eq(names[0].as_str(), names[2].as_str()));
+/// ```
+#[cfg(feature = "intern")]
+#[derive(Clone, Debug, Default)]
+pub struct NameInterner {
+    names: HashSet<Arc<str>>,
+}
+
+#[cfg(feature = "intern")]
+impl NameInterner {
+    /// Creates an empty interner.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn intern(&mut self, name: &str) -> Arc<str> {
+        if let Some(shared) = self.names.get(name) {
+            return Arc::clone(shared);
+        }
+        let shared = Arc::<str>::from(name);
+        self.names.insert(Arc::clone(&shared));
+        shared
+    }
+
+    /// A nation name that shares its text with every other name interned with the same text.
+    pub fn nation(&mut self, name: &str) -> NationName {
+        NationName(self.intern(name))
+    }
+
+    /// A region name that shares its text with every other name interned with the same text.
+    pub fn region(&mut self, name: &str) -> RegionName {
+        RegionName(self.intern(name))
+    }
+
+    /// Interns each of the nation names.
+    pub fn nations<'n>(&mut self, names: impl IntoIterator<Item = &'n str>) -> Vec<NationName> {
+        names.into_iter().map(|n| self.nation(n)).collect()
+    }
+
+    /// Interns each of the region names.
+    pub fn regions<'n>(&mut self, names: impl IntoIterator<Item = &'n str>) -> Vec<RegionName> {
+        names.into_iter().map(|n| self.region(n)).collect()
+    }
+
+    /// The number of distinct names.
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    /// Whether no names have been interned.
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Forgets names that are no longer used anywhere else.
+    pub fn shrink(&mut self) {
+        self.names.retain(|n| Arc::strong_count(n) > 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{NSName, NameList, NationName, RegionName};
+    use crate::shards::{nation::PublicNationRequest, region::RegionRequest, NSRequest};
+    use std::collections::HashSet;
+
+    #[test]
+    fn nation_name_conversions() {
+        let name = NationName::from("the_north_pacific_puppet");
+        assert_eq!(name.safe_name(), "the_north_pacific_puppet");
+        assert_eq!(name.pretty_name(), "The North Pacific Puppet");
+        assert_eq!(name.to_string(), "the_north_pacific_puppet");
+    }
+
+    #[test]
+    fn region_name_conversions() {
+        let name = RegionName::from("the_east_pacific");
+        assert_eq!(name.safe_name(), "the_east_pacific");
+        assert_eq!(name.pretty_name(), "The East Pacific");
+        assert_eq!(name.to_string(), "the_east_pacific");
+    }
+
+    #[test]
+    fn canonical_names() {
+        let pretty = RegionName::from("The North Pacific");
+        let safe = RegionName::from("the_north_pacific");
+        assert_eq!(pretty.canonical(), safe.canonical());
+        assert_eq!(pretty, safe);
+        assert_ne!(pretty, RegionName::from("The South Pacific"));
+        assert_eq!(HashSet::from([pretty, safe]).len(), 1);
+
+        // Only ASCII letters and spaces are changed.
+        assert_eq!(NationName::from("Ærø Isle"), NationName::from("Ærø_isle"));
+        assert_ne!(NationName::from("Ærø Isle"), NationName::from("ærø_isle"));
+        assert_ne!(NationName::from("ab"), NationName::from("a"));
+    }
+
+    #[test]
+    fn names_in_requests() {
+        let nation = NationName::from("The North Pacific Puppet");
+        assert_eq!(
+            PublicNationRequest::new(&nation).as_url().unwrap().query(),
+            Some("nation=the_north_pacific_puppet&q=&v=12")
+        );
+        let region = RegionName::from("The North Pacific");
+        assert_eq!(
+            RegionRequest::new(region).as_url().unwrap().query(),
+            Some("region=the_north_pacific&q=&v=12")
+        );
+    }
+
+    #[test]
+    fn name_list() {
+        let list = NameList::split("", ',');
+        assert!(list.is_empty());
+        assert_eq!(list.get(0), None);
+
+        let mut list = NameList::split("testlandia,aramos", ',');
+        list.push("maxtopia");
+        assert_eq!(list, ["testlandia", "aramos", "maxtopia"]);
+        assert_eq!(list.get(2), Some("maxtopia"));
+        assert_eq!(list.get(3), None);
+        assert_eq!(list.iter().len(), 3);
+        assert_eq!(
+            format!("{list:?}"),
+            r#"["testlandia", "aramos", "maxtopia"]"#
+        );
+
+        // Clones share the names until one of them is changed.
+        let mut copy = list.clone();
+        assert!(std::sync::Arc::ptr_eq(&list.0, &copy.0));
+        copy.push("ns");
+        assert!(!std::sync::Arc::ptr_eq(&list.0, &copy.0));
+        assert_eq!((list.len(), copy.len()), (3, 4));
+        assert_eq!(
+            Vec::<String>::from(list),
+            vec!["testlandia", "aramos", "maxtopia"]
+        );
+    }
+
+    #[cfg(feature = "intern")]
+    #[test]
+    fn interned_names() {
+        let mut interner = super::NameInterner::new();
+        let nation = interner.nation("anteria");
+        let region = interner.region("anteria");
+        assert!(std::ptr::eq(nation.as_str(), region.as_str()));
+        assert_eq!(interner.len(), 1);
+        drop(interner.regions(["lazarus", "osiris"]));
+        assert_eq!(interner.len(), 3);
+        interner.shrink();
+        assert_eq!(interner.len(), 1);
+        drop((nation, region));
+        interner.shrink();
+        assert!(interner.is_empty());
+    }
+}
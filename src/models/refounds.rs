@@ -0,0 +1,194 @@
+//! Foundings, ceased nations, and the refounds that connect them.
+//!
+//! A nation that ceased to exist can be refounded by whoever has its password,
+//! and comes back in a feeder or frontier region with none of its history visible.
+//! A [`RefoundTracker`] watches founding and CTE happenings
+//! (from [`HappeningsFilterType::Founding`] and [`HappeningsFilterType::Cte`])
+//! and flags every refound, along with when and where the nation ceased to exist:
+//! ```rust
+//! # use crustacean_states::models::refounds::RefoundTracker;
+//! # use crustacean_states::parsers::happenings::Event;
+//! # fn events() -> Vec<Event> { vec![] }
+//! let mut tracker = RefoundTracker::new();
+//! for event in events() {
+//!     if let Some(refound) = tracker.observe(&event) {
+//!         match refound.ceased {
+//!             Some(cte) => println!(
+//!                 "{} was refounded after ceasing to exist in {}",
+//!                 refound.nation.pretty_name(),
+//!                 cte.region.pretty_name(),
+//!             ),
+//!             None => println!("{} was refounded", refound.nation.pretty_name()),
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! [`HappeningsFilterType::Founding`]: crate::shards::world::HappeningsFilterType::Founding
+//! [`HappeningsFilterType::Cte`]: crate::shards::world::HappeningsFilterType::Cte
+
+use crate::{
+    models::name::{NationName, RegionName},
+    parsers::happenings::Event,
+    regex,
+};
+use std::collections::HashMap;
+
+/// Whether a nation was founded for the first time or revived.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum FoundingKind {
+    /// The nation was founded for the first time.
+    Founded,
+    /// The nation was revived after ceasing to exist.
+    Refounded,
+}
+
+/// A nation that was founded.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct Founding {
+    /// The nation that was founded.
+    pub nation: NationName,
+    /// The region it was founded in.
+    pub region: RegionName,
+    /// Whether it was founded for the first time.
+    pub kind: FoundingKind,
+    /// The Unix timestamp of the founding.
+    pub timestamp: u64,
+}
+
+impl Founding {
+    /// Reads a founding from a happening, if it has the form "NATION was (re)founded in REGION."
+    pub fn from_event(event: &Event) -> Option<Self> {
+        let captures = regex!(r"^@@([a-z0-9_-]+)@@ was (re)?founded in %%([a-z0-9_-]+)%%")
+            .captures(&event.text)?;
+        Some(Self {
+            nation: NationName::from(&captures[1]),
+            region: RegionName::from(&captures[3]),
+            kind: match captures.get(2) {
+                Some(_) => FoundingKind::Refounded,
+                None => FoundingKind::Founded,
+            },
+            timestamp: event.timestamp,
+        })
+    }
+}
+
+/// A nation that ceased to exist.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct Cte {
+    /// The nation that ceased to exist.
+    pub nation: NationName,
+    /// The region it was in.
+    pub region: RegionName,
+    /// The Unix timestamp it ceased to exist at.
+    pub timestamp: u64,
+}
+
+impl Cte {
+    /// Reads a CTE from a happening, if it has the form "NATION ceased to exist in REGION."
+    pub fn from_event(event: &Event) -> Option<Self> {
+        let captures = regex!(r"^@@([a-z0-9_-]+)@@ ceased to exist in %%([a-z0-9_-]+)%%")
+            .captures(&event.text)?;
+        Some(Self {
+            nation: NationName::from(&captures[1]),
+            region: RegionName::from(&captures[2]),
+            timestamp: event.timestamp,
+        })
+    }
+}
+
+/// A nation that was refounded.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct Refound {
+    /// The nation that was refounded.
+    pub nation: NationName,
+    /// The region it was refounded in.
+    pub region: RegionName,
+    /// The Unix timestamp of the refound.
+    pub timestamp: u64,
+    /// When and where the nation last ceased to exist,
+    /// if the tracker saw it happen.
+    pub ceased: Option<Cte>,
+}
+
+/// Watches founding and CTE happenings, and flags nations that are refounded.
+#[derive(Clone, Debug, Default)]
+pub struct RefoundTracker {
+    ctes: HashMap<NationName, Cte>,
+}
+
+impl RefoundTracker {
+    /// Creates a tracker that has not seen any happenings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a happening.
+    /// Returns the refound if the happening was one,
+    /// with the CTE it followed if the tracker saw one for the same nation.
+    ///
+    /// Happenings should be given oldest first,
+    /// so that each CTE is seen before the refound that follows it.
+    pub fn observe(&mut self, event: &Event) -> Option<Refound> {
+        if let Some(cte) = Cte::from_event(event) {
+            self.ctes.insert(cte.nation.clone(), cte);
+            return None;
+        }
+        let founding = Founding::from_event(event)?;
+        let ceased = self.ctes.remove(&founding.nation);
+        (founding.kind == FoundingKind::Refounded).then_some(Refound {
+            nation: founding.nation,
+            region: founding.region,
+            timestamp: founding.timestamp,
+            ceased,
+        })
+    }
+
+    /// The last CTE seen for a nation that has not been refounded since.
+    pub fn cte(&self, nation: impl Into<NationName>) -> Option<&Cte> {
+        self.ctes.get(&nation.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn refounds() {
+        let event = Event::from_text;
+        let mut tracker = RefoundTracker::new();
+        assert_eq!(
+            tracker.observe(&event(
+                1,
+                "@@griefer@@ ceased to exist in %%testregionia%%."
+            )),
+            None
+        );
+        assert_eq!(tracker.cte("Griefer").unwrap().timestamp, 1);
+        assert_eq!(
+            tracker.observe(&event(2, "@@newbie@@ was founded in %%the_pacific%%.")),
+            None
+        );
+
+        let refound = tracker
+            .observe(&event(3, "@@griefer@@ was refounded in %%lazarus%%."))
+            .unwrap();
+        assert_eq!(refound.region, RegionName::from("lazarus"));
+        assert_eq!(
+            refound.ceased.unwrap().region,
+            RegionName::from("testregionia")
+        );
+        assert_eq!(tracker.cte("griefer"), None);
+
+        let refound = tracker
+            .observe(&event(4, "@@old_puppet@@ was refounded in %%osiris%%."))
+            .unwrap();
+        assert_eq!(refound.ceased, None);
+    }
+}
@@ -0,0 +1,283 @@
+//! Kinds of telegrams, checks for whether nations will accept them,
+//! and campaigns that send a telegram to many nations.
+//!
+//! Nations can block recruitment or campaign telegrams in their settings,
+//! and a nation that has just received a recruitment telegram from a region
+//! will not accept another from the same region for a while.
+//! [`Client::check_telegrams`](crate::client::Client::check_telegrams)
+//! asks the API about many nations at once.
+
+use crate::{
+    client::{describe_error, Client},
+    models::name::NationName,
+    shards::{nation::PublicNationShard, telegram::TelegramRequest},
+};
+use std::{
+    borrow::Cow,
+    fmt::{Display, Formatter},
+    time::Duration,
+};
+
+/// The kind of a telegram, which decides which telegram settings apply to it.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum TelegramKind {
+    /// A telegram inviting the nation to a region.
+    Recruitment,
+    /// A telegram campaigning for a World Assembly proposal or an election.
+    Campaign,
+}
+
+impl TelegramKind {
+    /// How long the API makes a client wait after sending this kind of telegram
+    /// before it sends another.
+    pub const fn interval(self) -> Duration {
+        match self {
+            TelegramKind::Recruitment => Duration::from_secs(180),
+            TelegramKind::Campaign => Duration::from_secs(30),
+        }
+    }
+
+    /// The shard that checks whether a nation will accept this kind of telegram,
+    /// optionally from the region `from`.
+    /// ```rust
+    /// # use crustacean_states::models::telegram::TelegramKind;
+    /// # use crustacean_states::shards::nation::PublicNationShard;
+    /// assert_eq!(
+    ///     TelegramKind::Recruitment.shard(Some("Testregionia")),
+    ///     PublicNationShard::TGCanRecruit { from: Some("Testregionia".into()) },
+    /// );
+    /// ```
+    pub fn shard<'a>(self, from: Option<impl Into<Cow<'a, str>>>) -> PublicNationShard<'a> {
+        let from = from.map(Into::into);
+        match self {
+            TelegramKind::Recruitment => PublicNationShard::TGCanRecruit { from },
+            TelegramKind::Campaign => PublicNationShard::TGCanCampaign { from },
+        }
+    }
+}
+
+impl Display for TelegramKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            TelegramKind::Recruitment => "recruitment",
+            TelegramKind::Campaign => "campaign",
+        })
+    }
+}
+
+/// What happened when a campaign got to a recipient.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum TelegramStatus {
+    /// The telegram was queued for delivery.
+    Sent,
+    /// The campaign is a dry run, so the telegram was not sent.
+    DryRun,
+    /// The telegram could not be sent, for the reason given.
+    Failed(String),
+    /// The campaign was stopped while sending the telegram,
+    /// so it may or may not have been sent.
+    /// It is not sent again, so that the recipient does not get it twice.
+    Unknown,
+}
+
+/// What happened when a campaign got to one of its recipients.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct TelegramResult {
+    /// The nation the telegram was for.
+    pub recipient: NationName,
+    /// Whether it was sent.
+    pub status: TelegramStatus,
+}
+
+/// Sends one telegram template to a list of recipients, one at a time,
+/// waiting for the telegram and API rate limits between them.
+///
+/// The campaign keeps a result for every recipient it has got to,
+/// so it can be stopped between telegrams and resumed later.
+/// With the `serde` feature, it can also be saved and loaded to resume after a restart;
+/// note that the saved campaign includes the client key and the template's secret key.
+///
+/// ## Example
+/// ```rust
+/// # use crustacean_states::client::Client;
+/// # use crustacean_states::models::telegram::{TelegramCampaign, TelegramKind, TelegramStatus};
+/// # async fn test() {
+/// # let client = Client::new("");
+/// let mut campaign = TelegramCampaign::new(
+///     "client_key",
+///     "12345",
+///     "secret_key",
+///     TelegramKind::Recruitment,
+///     ["Testlandia", "Maxtopia"],
+/// );
+/// campaign.dry_run(true);
+/// for result in campaign.run(&client).await {
+///     assert_eq!(result.status, TelegramStatus::DryRun);
+/// }
+/// assert!(campaign.is_finished());
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TelegramCampaign {
+    client_key: String,
+    tgid: String,
+    secret_key: String,
+    kind: TelegramKind,
+    recipients: Vec<NationName>,
+    results: Vec<TelegramResult>,
+    dry_run: bool,
+    /// Whether the telegram to the next recipient was being sent when the campaign stopped.
+    #[cfg_attr(feature = "serde", serde(default))]
+    in_flight: bool,
+}
+
+impl TelegramCampaign {
+    /// Creates a campaign that sends the template with the ID `tgid` and the key `secret_key`
+    /// to every nation in `recipients`, in order, using the client key `client_key`.
+    pub fn new<I, N>(
+        client_key: impl ToString,
+        tgid: impl ToString,
+        secret_key: impl ToString,
+        kind: TelegramKind,
+        recipients: I,
+    ) -> Self
+    where
+        I: IntoIterator<Item = N>,
+        N: Into<NationName>,
+    {
+        Self {
+            client_key: client_key.to_string(),
+            tgid: tgid.to_string(),
+            secret_key: secret_key.to_string(),
+            kind,
+            recipients: recipients.into_iter().map(Into::into).collect(),
+            results: Vec::new(),
+            dry_run: false,
+            in_flight: false,
+        }
+    }
+
+    /// Sets whether the campaign is a dry run.
+    /// A dry run goes through every recipient without sending anything or waiting.
+    pub fn dry_run(&mut self, dry_run: bool) -> &mut Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// The kind of telegram being sent.
+    pub fn kind(&self) -> TelegramKind {
+        self.kind
+    }
+
+    /// Every recipient of the campaign, in the order they are sent to.
+    pub fn recipients(&self) -> &[NationName] {
+        &self.recipients
+    }
+
+    /// The recipients that have not been got to yet.
+    pub fn remaining(&self) -> &[NationName] {
+        &self.recipients[self.results.len()..]
+    }
+
+    /// The results for the recipients that have been got to, in order.
+    pub fn results(&self) -> &[TelegramResult] {
+        &self.results
+    }
+
+    /// Whether every recipient has been got to.
+    pub fn is_finished(&self) -> bool {
+        self.results.len() == self.recipients.len()
+    }
+
+    /// Sends the telegram to the next recipient, waiting for the rate limits first,
+    /// and returns its result.
+    /// Returns `None` if the campaign is finished.
+    ///
+    /// If the future is dropped before it finishes, the telegram may already have been sent,
+    /// so the next call does not send it again,
+    /// and gives the recipient the status [`TelegramStatus::Unknown`] instead.
+    pub async fn send_next(&mut self, client: &Client) -> Option<&TelegramResult> {
+        let recipient = self.remaining().first()?.clone();
+        let status = if self.in_flight {
+            TelegramStatus::Unknown
+        } else if self.dry_run {
+            TelegramStatus::DryRun
+        } else {
+            self.in_flight = true;
+            let request = TelegramRequest::new(
+                self.client_key.as_str(),
+                self.tgid.as_str(),
+                self.secret_key.as_str(),
+                recipient.safe_name(),
+            );
+            match client.send_telegram(request, self.kind).await {
+                Ok(()) => TelegramStatus::Sent,
+                Err(e) => TelegramStatus::Failed(describe_error(&e)),
+            }
+        };
+        self.in_flight = false;
+        self.results.push(TelegramResult { recipient, status });
+        self.results.last()
+    }
+
+    /// Sends the telegram to every remaining recipient,
+    /// and returns the results for every recipient.
+    pub async fn run(&mut self, client: &Client) -> &[TelegramResult] {
+        while self.send_next(client).await.is_some() {}
+        &self.results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn dry_run_campaign() {
+        let client = Client::new("");
+        let mut campaign = TelegramCampaign::new(
+            "client",
+            "1",
+            "key",
+            TelegramKind::Campaign,
+            ["Testlandia", "maxtopia"],
+        );
+        campaign.dry_run(true);
+        let first = campaign.send_next(&client).await.unwrap();
+        assert_eq!(first.recipient, NationName::from("testlandia"));
+        assert_eq!(campaign.remaining(), [NationName::from("Maxtopia")]);
+
+        assert_eq!(campaign.run(&client).await.len(), 2);
+        assert!(campaign.is_finished());
+        assert!(campaign.send_next(&client).await.is_none());
+        assert!(campaign
+            .results()
+            .iter()
+            .all(|result| result.status == TelegramStatus::DryRun));
+    }
+
+    #[tokio::test]
+    async fn interrupted_send() {
+        let client = Client::new("");
+        let mut campaign = TelegramCampaign::new(
+            "client",
+            "1",
+            "key",
+            TelegramKind::Recruitment,
+            ["Testlandia"],
+        );
+        // As if a send had been cancelled after the request went out.
+        campaign.in_flight = true;
+        let result = campaign.send_next(&client).await.unwrap();
+        assert_eq!(result.status, TelegramStatus::Unknown);
+        assert!(campaign.is_finished());
+        assert!(!campaign.in_flight);
+    }
+}
@@ -0,0 +1,99 @@
+//! Conversions from the Unix timestamps in parsed responses.
+//!
+//! Timestamps are kept as the number of seconds the API sends,
+//! so no datetime library is forced on users of this crate.
+//! [`Timestamp`] converts them to [`SystemTime`],
+//! or to [`time::OffsetDateTime`] with the `time` feature:
+//! ```rust
+//! # use crustacean_states::models::timestamp::Timestamp;
+//! # use std::time::{Duration, UNIX_EPOCH};
+//! let last_login: u64 = 1_700_000_000;
+//! assert_eq!(last_login.system_time(), Some(UNIX_EPOCH + Duration::from_secs(1_700_000_000)));
+//! // The API sends 0 for times that were never recorded.
+//! assert_eq!(0u64.system_time(), None);
+//! ```
+#[cfg(feature = "parsers")]
+use crate::parsers::MaybeSystemTime;
+use std::{
+    num::NonZeroU64,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// The number of seconds in a day.
+#[cfg(feature = "parsers")]
+pub(crate) const DAY: u64 = 24 * 60 * 60;
+
+/// A Unix timestamp from the API that may not have been recorded.
+pub trait Timestamp {
+    /// The number of seconds since the Unix epoch, if the time was recorded.
+    fn unix(&self) -> Option<u64>;
+
+    /// The time as a [`SystemTime`], if it was recorded.
+    fn system_time(&self) -> Option<SystemTime> {
+        self.unix()
+            .map(|seconds| UNIX_EPOCH + Duration::from_secs(seconds))
+    }
+
+    /// The time as a UTC [`time::OffsetDateTime`], if it was recorded
+    /// and is within the range that `time` supports.
+    #[cfg(feature = "time")]
+    fn offset_date_time(&self) -> Option<time::OffsetDateTime> {
+        let seconds = i64::try_from(self.unix()?).ok()?;
+        time::OffsetDateTime::from_unix_timestamp(seconds).ok()
+    }
+}
+
+/// A timestamp of 0 was never recorded.
+impl Timestamp for u64 {
+    fn unix(&self) -> Option<u64> {
+        (*self != 0).then_some(*self)
+    }
+}
+
+impl Timestamp for NonZeroU64 {
+    fn unix(&self) -> Option<u64> {
+        Some(self.get())
+    }
+}
+
+impl Timestamp for Option<NonZeroU64> {
+    fn unix(&self) -> Option<u64> {
+        self.map(NonZeroU64::get)
+    }
+}
+
+#[cfg(feature = "parsers")]
+impl Timestamp for MaybeSystemTime {
+    fn unix(&self) -> Option<u64> {
+        match self {
+            MaybeSystemTime::Recorded(time) => Some(time.get()),
+            MaybeSystemTime::Antiquity => None,
+        }
+    }
+}
+
+#[cfg(all(test, feature = "parsers"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timestamps() {
+        let founded = MaybeSystemTime::from(1_500_000_000);
+        assert_eq!(founded.unix(), Some(1_500_000_000));
+        assert_eq!(MaybeSystemTime::Antiquity.system_time(), None);
+        assert_eq!(
+            NonZeroU64::new(60).system_time(),
+            Some(UNIX_EPOCH + Duration::from_secs(60))
+        );
+
+        #[cfg(feature = "time")]
+        {
+            let date = founded.offset_date_time().unwrap();
+            assert_eq!(
+                (date.year(), date.month(), date.day()),
+                (2017, time::Month::July, 14)
+            );
+            assert_eq!(u64::MAX.offset_date_time(), None);
+        }
+    }
+}
@@ -0,0 +1,179 @@
+//! Estimates of when regions update.
+//!
+//! Regions update one after another in a fixed order,
+//! which is the order they appear in the daily regions dump.
+//! An [`UpdateEstimator`] takes that order and the times that some regions updated
+//! in a past update (for example, from
+//! [`RegionShard::LastMajorUpdate`](crate::shards::region::RegionShard::LastMajorUpdate)),
+//! and estimates when every other region will update in a later one:
+//! ```rust
+//! # use crustacean_states::models::update::{UpdateEstimate, UpdateEstimator};
+//! let order = ["the_pacific", "testregionia", "lazarus", "the_north_pacific"];
+//! // Yesterday's major update started at 1000.
+//! let mut estimator = UpdateEstimator::new(order, 1000);
+//! estimator.observe("the_pacific", 1010);
+//! estimator.observe("lazarus", 1050);
+//!
+//! // Today's major update starts at 87400.
+//! assert_eq!(
+//!     estimator.estimate("testregionia", 87400),
+//!     Some(UpdateEstimate { earliest: 87410, latest: 87450 })
+//! );
+//! ```
+//! Update speed changes from day to day,
+//! so estimates are best made from observations of the most recent update.
+
+use crate::models::name::RegionName;
+use std::collections::{BTreeMap, HashMap};
+
+/// The window that a region is expected to update in.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct UpdateEstimate {
+    /// The earliest Unix timestamp the region is expected to update at.
+    pub earliest: u64,
+    /// The latest Unix timestamp the region is expected to update at.
+    pub latest: u64,
+}
+
+/// Estimates when regions update from their update order and past update times.
+#[derive(Clone, Debug)]
+pub struct UpdateEstimator {
+    positions: HashMap<RegionName, usize>,
+    len: usize,
+    start: u64,
+    /// Seconds after the start of the update, by position in the update order.
+    observations: BTreeMap<usize, u64>,
+}
+
+impl UpdateEstimator {
+    /// Creates an estimator from the update order of every region,
+    /// and the Unix timestamp of the start of the update that will be observed.
+    pub fn new<I, R>(order: I, start: u64) -> Self
+    where
+        I: IntoIterator<Item = R>,
+        R: Into<RegionName>,
+    {
+        let positions = order
+            .into_iter()
+            .enumerate()
+            .map(|(i, region)| (region.into(), i))
+            .collect::<HashMap<_, _>>();
+        Self {
+            len: positions.len(),
+            positions,
+            start,
+            observations: BTreeMap::new(),
+        }
+    }
+
+    /// Records the Unix timestamp that a region updated at.
+    /// Returns `false` if the region is not in the update order
+    /// or updated before the update started.
+    pub fn observe(&mut self, region: impl Into<RegionName>, timestamp: u64) -> bool {
+        let Some(&position) = self.positions.get(&region.into()) else {
+            return false;
+        };
+        let Some(offset) = timestamp.checked_sub(self.start) else {
+            return false;
+        };
+        self.observations.insert(position, offset);
+        true
+    }
+
+    /// The average number of seconds each region takes to update,
+    /// if any region after the first has been observed.
+    pub fn seconds_per_region(&self) -> Option<f64> {
+        let (&position, &offset) = self.observations.last_key_value()?;
+        (position > 0).then(|| offset as f64 / position as f64)
+    }
+
+    /// Estimates when a region will update in an update that starts at `start`.
+    ///
+    /// The region updates between the closest observed regions before and after it.
+    /// Where there is no observed region on one side,
+    /// that side of the window is extrapolated from the average update speed.
+    /// Returns `None` if the region is not in the update order,
+    /// or if too few regions have been observed to estimate it.
+    pub fn estimate(&self, region: impl Into<RegionName>, start: u64) -> Option<UpdateEstimate> {
+        let &position = self.positions.get(&region.into())?;
+        let before = self.observations.range(..=position).next_back();
+        let after = self.observations.range(position..).next();
+        let extrapolate = |position: usize| {
+            self.seconds_per_region()
+                .map(|rate| (rate * position as f64).round() as u64)
+        };
+        // An extrapolated side can land past the observed side when the update
+        // sped up or slowed down, so it is clamped to keep the window in order.
+        let earliest = match (before, after) {
+            (Some((_, &offset)), _) => offset,
+            (None, Some((_, &after))) => extrapolate(position)?.min(after),
+            (None, None) => extrapolate(position)?,
+        };
+        let latest = match after {
+            Some((_, &offset)) => offset,
+            None => extrapolate(position)?.max(earliest),
+        };
+        Some(UpdateEstimate {
+            earliest: start + earliest,
+            latest: start + latest,
+        })
+    }
+
+    /// The number of regions in the update order.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the update order is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn update_estimates() {
+        let order = (0..101).map(|i| format!("region_{i}"));
+        let mut estimator = UpdateEstimator::new(order, 0);
+        assert_eq!(estimator.estimate("region_50", 0), None);
+        assert!(estimator.observe("region_10", 20));
+        assert!(!estimator.observe("not_a_region", 20));
+
+        assert_eq!(
+            estimator.estimate("Region 10", 100),
+            Some(UpdateEstimate {
+                earliest: 120,
+                latest: 120
+            })
+        );
+        assert_eq!(
+            estimator.estimate("region_100", 100),
+            Some(UpdateEstimate {
+                earliest: 120,
+                latest: 300
+            })
+        );
+        assert_eq!(
+            estimator.estimate("region_5", 100),
+            Some(UpdateEstimate {
+                earliest: 110,
+                latest: 120
+            })
+        );
+
+        // The rest of the update was much slower than its start.
+        assert!(estimator.observe("region_100", 2000));
+        assert_eq!(
+            estimator.estimate("region_5", 0),
+            Some(UpdateEstimate {
+                earliest: 20,
+                latest: 20
+            })
+        );
+    }
+}
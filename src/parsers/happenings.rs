@@ -5,9 +5,16 @@ use once_cell::sync::Lazy;
 use regex::{Regex, RegexSet};
 
 /// A line of `happenings`.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[non_exhaustive]
 pub struct Event {
+    /// The ID of the event.
+    /// This is only sent for world happenings,
+    /// and can be used with the `since_id` and `before_id` fields of
+    /// [`WorldShard::Happenings`](crate::shards::world::WorldShard::Happenings).
+    pub id: Option<u32>,
     /// The Unix timestamp when the event happened.
     pub timestamp: u64,
     /// The exact contents of the event.
@@ -22,7 +29,9 @@ pub struct Event {
     pub kind: Option<EventKind>,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[non_exhaustive]
 /// The kind of event. Not currently implemented.
 pub enum EventKind {
@@ -46,6 +55,30 @@ static REGION_RE: Lazy<&Regex> = Lazy::new(|| regex!(r"%%[a-zA-Z0-9-]+%%"));
 static ALL_EXPRESSIONS: Lazy<RegexSet> =
     Lazy::new(|| RegexSet::new([NATION_RE.as_str(), REGION_RE.as_str()]).unwrap());
 
+impl Event {
+    /// Creates an event from its parts, finding the nations and regions mentioned in the text.
+    #[cfg(feature = "sse")]
+    pub(crate) fn new(id: Option<u32>, timestamp: u64, text: String) -> Self {
+        Self::from(RawEvent {
+            id,
+            timestamp,
+            text,
+        })
+    }
+}
+
+impl Event {
+    /// An event with the given text, as if it had been parsed from happenings.
+    #[cfg(test)]
+    pub(crate) fn from_text(timestamp: u64, text: &str) -> Self {
+        Self::from(RawEvent {
+            id: None,
+            timestamp,
+            text: text.to_string(),
+        })
+    }
+}
+
 impl From<RawEvent> for Event {
     fn from(value: RawEvent) -> Self {
         let which_matched = ALL_EXPRESSIONS.matches(&value.text);
@@ -68,6 +101,7 @@ impl From<RawEvent> for Event {
         };
 
         Self {
+            id: value.id,
             timestamp: value.timestamp,
             text: value.text,
             nations,
@@ -1,24 +1,735 @@
 //! Contains the modules that parse responses from the NationStates API.
-use crate::models::dispatch::DispatchCategory;
-use serde::Deserialize;
-use std::num::{NonZeroU32, NonZeroU64};
+//!
+//! Every parsed type can be cloned.
+//! Long lists of names are [`NameList`](crate::models::name::NameList)s,
+//! which share their text between clones,
+//! so handing a copy of a [`Nation`](nation::Nation) or [`Region`](region::Region)
+//! to another task does not copy its endorsements or nations.
+//! To share one value without cloning it at all, wrap it in an [`Arc`](std::sync::Arc).
+use crate::{
+    models::dispatch::DispatchCategory,
+    parsers::{
+        nation::IntoNationError, region::IntoRegionError, wa::IntoWAError, world::IntoWorldError,
+    },
+    pretty_name,
+    shards::wa::WACouncil,
+};
+use quick_xml::{
+    escape::resolve_predefined_entity, events::Event as XmlEvent, DeError, Reader, XmlVersion,
+};
+use serde::{de::Error as _, Deserialize, Deserializer};
+use serde_path_to_error::Segment;
+use std::{
+    borrow::Cow,
+    convert::Infallible,
+    error::Error,
+    fmt::Display,
+    io::BufRead,
+    num::{NonZeroU32, NonZeroU64},
+    ops::Deref,
+    str::FromStr,
+};
+use strum::Display;
+use thiserror::Error;
 
 pub mod happenings;
 pub mod nation;
 mod raw_nation;
+mod raw_region;
+mod raw_wa;
+mod raw_world;
+pub mod region;
+pub mod wa;
+pub mod world;
 
 pub(crate) const DEFAULT_LEADER: &str = "Leader";
 pub(crate) const DEFAULT_RELIGION: &str = "a major religion";
 
+/// A type that can be parsed from a response sent by the NationStates API.
+///
+/// Every [`NSRequestParse`](crate::shards::NSRequestParse) names the type
+/// that its response parses into with
+/// [`NSRequestParse::Response`](crate::shards::NSRequestParse::Response).
+pub trait NSResponse: Sized {
+    /// The error returned if the response could not be parsed.
+    ///
+    /// It can be converted into a [`ParseError`],
+    /// so that every parser's errors can be handled the same way.
+    type Error: Error + Send + Sync + Into<ParseError> + 'static;
+
+    /// Parses the XML response from NationStates.
+    fn from_xml(xml: &str) -> Result<Self, Self::Error>;
+}
+
+/// The raw text of the response, for requests that do not have a parser yet.
+impl NSResponse for String {
+    type Error = Infallible;
+
+    fn from_xml(xml: &str) -> Result<Self, Self::Error> {
+        Ok(xml.to_string())
+    }
+}
+
+/// A parsed response, together with the XML it was parsed from.
+///
+/// Useful for bug reports and for replaying responses later.
+/// Request one with [`NSRequestParse::keep_xml`](crate::shards::NSRequestParse::keep_xml).
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct WithXml<T> {
+    /// The response exactly as NationStates sent it.
+    pub xml: String,
+    /// The parsed response.
+    pub parsed: T,
+}
+
+impl<T> WithXml<T> {
+    /// Throws away the XML, keeping the parsed response.
+    pub fn into_inner(self) -> T {
+        self.parsed
+    }
+}
+
+impl<T: NSResponse> NSResponse for WithXml<T> {
+    type Error = T::Error;
+
+    fn from_xml(xml: &str) -> Result<Self, Self::Error> {
+        Ok(Self {
+            parsed: T::from_xml(xml)?,
+            xml: xml.to_string(),
+        })
+    }
+}
+
+/// The kind of response that could not be parsed.
+#[derive(Clone, Copy, Debug, Display, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum Entity {
+    /// A nation.
+    Nation,
+    /// A region.
+    Region,
+    /// The world.
+    World,
+    /// The World Assembly.
+    #[strum(serialize = "World Assembly")]
+    WorldAssembly,
+    /// A response with a parser from outside this crate,
+    /// or one that is not an XML document (such as the API version).
+    Other,
+}
+
+/// An error from parsing any response.
+///
+/// The errors of each parser (e.g. [`IntoNationError`]) convert into it,
+/// so code that only needs to know what failed to parse can handle them all the same way:
+/// ```rust
+/// # use crustacean_states::parsers::{nation::Nation, Entity, ParseError};
+/// let error = ParseError::from(
+///     Nation::from_xml(r#"<NATION id="aramos"><POPULATION>many</POPULATION></NATION>"#)
+///         .unwrap_err(),
+/// );
+/// assert_eq!(error.entity, Entity::Nation);
+/// assert_eq!(error.field.as_deref(), Some("NATION/POPULATION"));
+/// ```
+#[derive(Debug, Error)]
+#[error("could not parse {entity} response{}", .field.as_ref().map(|f| format!(" at {f}")).unwrap_or_default())]
+pub struct ParseError {
+    /// The kind of response.
+    pub entity: Entity,
+    /// Where in the response parsing failed, if it is known.
+    pub field: Option<String>,
+    /// The error from the parser. Look here for what went wrong.
+    #[source]
+    pub source: Box<dyn Error + Send + Sync>,
+}
+
+impl ParseError {
+    /// Wraps the error of a parser.
+    pub fn new(entity: Entity, source: impl Into<Box<dyn Error + Send + Sync>>) -> Self {
+        Self {
+            entity,
+            field: None,
+            source: source.into(),
+        }
+    }
+
+    /// Sets where in the response parsing failed.
+    pub fn at(mut self, field: impl ToString) -> Self {
+        self.field = Some(field.to_string());
+        self
+    }
+
+    /// The error from the parser, if it has the type `E`.
+    pub fn downcast_ref<E: Error + 'static>(&self) -> Option<&E> {
+        self.source.downcast_ref()
+    }
+}
+
+impl From<Infallible> for ParseError {
+    fn from(value: Infallible) -> Self {
+        match value {}
+    }
+}
+
+impl From<IntoNationError> for ParseError {
+    fn from(value: IntoNationError) -> Self {
+        let field = match &value {
+            IntoNationError::BadFieldError { field, .. } => Some(field.to_string()),
+            IntoNationError::BadWAVote { council, .. } => Some(String::from(match council {
+                WACouncil::GeneralAssembly => "NATION/GAVOTE",
+                WACouncil::SecurityCouncil => "NATION/SCVOTE",
+            })),
+            IntoNationError::DeserializationError { source } => Some(source.path.clone()),
+            IntoNationError::NoNameError => Some(String::from("NATION/NAME")),
+            IntoNationError::NoCensusDataError => Some(String::from("NATION/CENSUS")),
+            IntoNationError::MissingShardError(shard) => {
+                Some(format!("NATION/{}", shard.to_ascii_uppercase()))
+            }
+            IntoNationError::BadBannerId(_)
+            | IntoNationError::BadBooleanError(_)
+            | IntoNationError::BadDispatchCategory(_) => None,
+        };
+        Self {
+            entity: Entity::Nation,
+            field,
+            source: Box::new(value),
+        }
+    }
+}
+
+impl From<IntoRegionError> for ParseError {
+    fn from(value: IntoRegionError) -> Self {
+        let field = match &value {
+            IntoRegionError::BadEmbassyKind(_) => Some("REGION/EMBASSIES/EMBASSY"),
+            IntoRegionError::BadEmbassyRmb(_) => Some("REGION/EMBASSYRMB"),
+            IntoRegionError::BadMessageStatus(_) => Some("REGION/MESSAGES/POST/STATUS"),
+            IntoRegionError::BadWABadge(_) => Some("REGION/WABADGES/WABADGE"),
+            IntoRegionError::DeserializationError { source } => Some(source.path.as_str()),
+            IntoRegionError::NoSuppressorError(_) => Some("REGION/MESSAGES/POST/SUPPRESSOR"),
+            IntoRegionError::NoNameError => Some("REGION/NAME"),
+            IntoRegionError::NoCensusDataError => Some("REGION/CENSUS"),
+            IntoRegionError::BadAuthority(_) | IntoRegionError::BadBooleanError(_) => None,
+        }
+        .map(String::from);
+        Self {
+            entity: Entity::Region,
+            field,
+            source: Box::new(value),
+        }
+    }
+}
+
+impl From<IntoWorldError> for ParseError {
+    fn from(value: IntoWorldError) -> Self {
+        let field = match &value {
+            IntoWorldError::DeserializationError { source } => Some(source.path.clone()),
+            IntoWorldError::NoCensusDataError => Some(String::from("WORLD/CENSUS")),
+            IntoWorldError::BadBannerId(_) | IntoWorldError::BadDispatchCategory(_) => None,
+        };
+        Self {
+            entity: Entity::World,
+            field,
+            source: Box::new(value),
+        }
+    }
+}
+
+impl From<IntoWAError> for ParseError {
+    fn from(value: IntoWAError) -> Self {
+        let field = match &value {
+            IntoWAError::DeserializationError { source } => Some(source.path.clone()),
+            IntoWAError::BadCouncil(_) | IntoWAError::BadDelegateAction(_) => None,
+        };
+        Self {
+            entity: Entity::WorldAssembly,
+            field,
+            source: Box::new(value),
+        }
+    }
+}
+
+/// The longest snippet of a bad value that is kept in an [`XmlError`].
+const SNIPPET_LENGTH: usize = 64;
+
+/// An XML response that could not be deserialized, and where in the response it happened.
+#[derive(Debug, Error)]
+#[error("{source} at {path}{}", .snippet.as_ref().map(|s| format!(" (found {s:?})")).unwrap_or_default())]
+pub struct XmlError {
+    /// The path to the element that could not be deserialized,
+    /// e.g. `NATION/CENSUS/SCALE[3]/SCORE`.
+    pub path: String,
+    /// The start of the value that could not be deserialized, if it could be found.
+    pub snippet: Option<String>,
+    /// The error from the deserializer.
+    #[source]
+    pub source: DeError,
+}
+
+/// Deserializes a response, keeping track of where in the response deserialization fails.
+pub(crate) fn from_xml_str<'de, T: Deserialize<'de>>(xml: &'de str) -> Result<T, XmlError> {
+    serde_path_to_error::deserialize(&mut quick_xml::de::Deserializer::from_str(xml)).map_err(|e| {
+        let elements = error_elements(&e);
+        let (root, snippet) = find_value(xml, &elements).unwrap_or_default();
+        XmlError {
+            path: join_path(root, &elements),
+            snippet,
+            source: e.into_inner(),
+        }
+    })
+}
+
+/// Like [`from_xml_str`], but for a response that might not be UTF-8.
+pub(crate) fn from_xml_bytes<'de, T: Deserialize<'de>>(xml: &'de [u8]) -> Result<T, XmlError> {
+    from_xml_str(std::str::from_utf8(xml).map_err(|e| XmlError {
+        path: String::new(),
+        snippet: None,
+        source: DeError::from(quick_xml::Error::from(e)),
+    })?)
+}
+
+/// Like [`from_xml_str`], but reading the response as it is deserialized.
+///
+/// The response is not kept, so errors have no snippet,
+/// and the path starts with the expected `root` element.
+/// Nothing can be borrowed from the reader,
+/// so borrowed fields like [`Text`] are always owned.
+pub(crate) fn from_xml_reader<'de, T: Deserialize<'de>>(
+    reader: impl BufRead,
+    root: &str,
+) -> Result<T, XmlError> {
+    serde_path_to_error::deserialize(&mut quick_xml::de::Deserializer::from_reader(reader)).map_err(
+        |e| XmlError {
+            path: join_path(root.to_string(), &error_elements(&e)),
+            snippet: None,
+            source: e.into_inner(),
+        },
+    )
+}
+
+/// The elements leading to a deserialization error,
+/// each with its index among its siblings with the same name.
+fn error_elements(e: &serde_path_to_error::Error<DeError>) -> Vec<(String, usize)> {
+    // Each element is a map key, optionally followed by its index among its siblings.
+    let mut elements: Vec<(String, usize)> = vec![];
+    for segment in e.path().iter() {
+        match segment {
+            Segment::Map { key } if !key.starts_with('$') => elements.push((key.clone(), 0)),
+            Segment::Seq { index } => {
+                if let Some(last) = elements.last_mut() {
+                    last.1 = *index;
+                }
+            }
+            _ => {}
+        }
+    }
+    elements
+}
+
+/// Writes a path like `NATION/CENSUS/SCALE[3]/SCORE`.
+fn join_path(root: String, elements: &[(String, usize)]) -> String {
+    std::iter::once(root)
+        .chain(elements.iter().map(|(name, index)| match index {
+            0 => name.clone(),
+            i => format!("{name}[{i}]"),
+        }))
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Finds the name of the root element
+/// and the text of the element (or value of the attribute) at the end of `path`.
+fn find_value(xml: &str, path: &[(String, usize)]) -> Option<(String, Option<String>)> {
+    let truncate = |text: &str| {
+        let text = text.trim();
+        match text.char_indices().nth(SNIPPET_LENGTH) {
+            Some((end, _)) => format!("{}...", &text[..end]),
+            None => text.to_string(),
+        }
+    };
+    let mut reader = Reader::from_str(xml);
+    let mut root = None;
+    // How many elements of the path have been found, and how deep the reader is.
+    let (mut found, mut depth) = (0, 0);
+    // How many elements with the next name in the path have been passed.
+    let mut passed = 0;
+    // Text is split around entity references, so it is gathered until the element ends.
+    let mut text = String::new();
+    loop {
+        let (start, empty) = match reader.read_event().ok()? {
+            XmlEvent::Start(start) => (start, false),
+            XmlEvent::Empty(start) => (start, true),
+            XmlEvent::Text(t) if found == path.len() && depth == found + 1 => {
+                text.push_str(&t.xml10_content());
+                continue;
+            }
+            XmlEvent::GeneralRef(r) if found == path.len() && depth == found + 1 => {
+                let name = r.xml10_content();
+                match r.resolve_char_ref().ok()? {
+                    Some(c) => text.push(c),
+                    None => text.push_str(resolve_predefined_entity(&name)?),
+                }
+                continue;
+            }
+            XmlEvent::End(_) => {
+                if depth == found + 1 && found > 0 {
+                    // The element the path was in has ended.
+                    let value =
+                        (found == path.len() && !text.trim().is_empty()).then(|| truncate(&text));
+                    return Some((root?, value));
+                }
+                depth -= 1;
+                continue;
+            }
+            XmlEvent::Eof => return Some((root?, None)),
+            _ => continue,
+        };
+        let name = start.name().into_inner().to_string();
+        // The number of elements that this element is in.
+        let level = depth;
+        if !empty {
+            depth += 1;
+        }
+        if root.is_none() {
+            root = Some(name);
+        } else if found < path.len() && level == found + 1 && path[found].0 == name {
+            if passed < path[found].1 {
+                passed += 1;
+                continue;
+            }
+            found += 1;
+            passed = 0;
+        } else {
+            continue;
+        }
+        // An attribute is the last element of a path.
+        if let Some((attribute, _)) = path.get(found).filter(|(a, _)| a.starts_with('@')) {
+            let value = start
+                .try_get_attribute(&attribute[1..])
+                .ok()?
+                .and_then(|a| {
+                    a.normalized_value(XmlVersion::Implicit1_0)
+                        .ok()
+                        .map(|v| truncate(&v))
+                });
+            return Some((root?, value));
+        }
+        if empty && found == path.len() {
+            return Some((root?, Some(String::new())));
+        }
+    }
+}
+
+/// Deserializes an optional number straight from the text of the response,
+/// treating an empty element the same as a missing one.
+pub(super) fn empty_as_none<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: Display,
+{
+    match Option::<Text>::deserialize(deserializer)?
+        .as_deref()
+        .map(str::trim)
+    {
+        None | Some("") => Ok(None),
+        Some(text) => text.parse().map(Some).map_err(D::Error::custom),
+    }
+}
+
+/// Text in a response that is only needed while parsing,
+/// borrowed from the response unless it had to be unescaped.
+#[derive(Debug, Deserialize)]
+#[serde(transparent)]
+pub(super) struct Text<'x>(#[serde(borrow)] pub(super) Cow<'x, str>);
+
+impl Deref for Text<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "UPPERCASE")]
 pub(super) struct RawEvent {
+    #[serde(rename = "@id")]
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pub(super) id: Option<u32>,
     pub(super) timestamp: u64,
     pub(super) text: String,
 }
 
+#[derive(Debug, Deserialize)]
+pub(super) struct RawCensus {
+    #[serde(rename = "SCALE", default)]
+    pub(super) inner: Vec<RawCensusData>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub(super) struct RawHappenings {
+    #[serde(rename = "EVENT", default)]
+    pub(super) inner: Vec<RawEvent>,
+}
+
+//noinspection SpellCheckingInspection
+#[derive(Debug, Deserialize)]
+pub(super) struct RawCensusData {
+    #[serde(rename = "@id")]
+    pub(super) id: u8,
+    #[serde(rename = "SCORE")]
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pub(super) score: Option<f64>,
+    #[serde(rename = "RANK")]
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pub(super) world_rank: Option<NonZeroU32>,
+    #[serde(rename = "RRANK")]
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pub(super) region_rank: Option<NonZeroU32>,
+    #[serde(rename = "PRANK")]
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pub(super) percent_world_rank: Option<f64>,
+    #[serde(rename = "PRRANK")]
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pub(super) percent_region_rank: Option<f64>,
+    #[serde(rename = "TIMESTAMP")]
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pub(super) timestamp: Option<NonZeroU64>,
+}
+
+impl From<RawCensusData> for CensusCurrentData {
+    fn from(value: RawCensusData) -> Self {
+        let RawCensusData {
+            id,
+            score,
+            world_rank,
+            region_rank,
+            percent_world_rank,
+            percent_region_rank,
+            ..
+        } = value;
+        Self {
+            id,
+            score,
+            world_rank,
+            region_rank,
+            percent_world_rank,
+            percent_region_rank,
+        }
+    }
+}
+
+impl From<RawCensusData> for CensusHistoricalData {
+    fn from(value: RawCensusData) -> Self {
+        let RawCensusData {
+            id,
+            timestamp,
+            score,
+            ..
+        } = value;
+        Self {
+            id,
+            timestamp,
+            score,
+        }
+    }
+}
+
+impl RawCensus {
+    /// The census shard can return either current or historical data, but never both.
+    /// Returns `None` if there was no census data at all.
+    pub(super) fn into_census_data(self) -> Option<CensusData> {
+        match self.inner.first() {
+            Some(f) if f.timestamp.is_some() => Some(CensusData::Historical(
+                self.inner
+                    .into_iter()
+                    .map(CensusHistoricalData::from)
+                    .collect(),
+            )),
+            Some(_) => Some(CensusData::Current(
+                self.inner
+                    .into_iter()
+                    .map(CensusCurrentData::from)
+                    .collect(),
+            )),
+            None => None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub(super) struct RawCensusRanks {
+    #[serde(rename = "@id")]
+    pub(super) id: u8,
+    pub(super) nations: RawCensusRanksNations,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct RawCensusRanksNations {
+    #[serde(rename = "NATION", default)]
+    pub(super) inner: Vec<RawCensusRanksNation>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub(super) struct RawCensusRanksNation {
+    pub(super) name: String,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pub(super) rank: Option<u32>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pub(super) score: Option<f64>,
+}
+
+impl From<RawCensusRanks> for CensusRanks {
+    fn from(value: RawCensusRanks) -> Self {
+        Self {
+            id: value.id,
+            nations: value
+                .nations
+                .inner
+                .into_iter()
+                .map(|n| CensusRanksNation {
+                    name: n.name,
+                    rank: n.rank,
+                    score: n.score,
+                })
+                .collect(),
+        }
+    }
+}
+
+//noinspection SpellCheckingInspection
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub(super) struct RawPoll {
+    #[serde(rename = "@id")]
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pub(super) id: Option<u32>,
+    pub(super) title: Option<String>,
+    pub(super) text: Option<String>,
+    pub(super) region: Option<String>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pub(super) start: Option<u64>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pub(super) stop: Option<u64>,
+    pub(super) author: Option<String>,
+    pub(super) options: Option<RawPollOptions>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct RawPollOptions {
+    #[serde(rename = "OPTION", default)]
+    pub(super) inner: Vec<RawPollOption>,
+}
+
+//noinspection SpellCheckingInspection
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub(super) struct RawPollOption {
+    #[serde(rename = "@id")]
+    pub(super) id: u32,
+    pub(super) optiontext: String,
+    pub(super) votes: u32,
+    pub(super) voters: Option<String>,
+}
+
+impl RawPoll {
+    /// An empty `<POLL>` tag means that there is no poll.
+    pub(super) fn into_poll(self) -> Option<Poll> {
+        Some(Poll {
+            id: self.id?,
+            title: self.title.unwrap_or_default(),
+            text: self.text,
+            region: self.region.unwrap_or_default(),
+            start: self.start.unwrap_or_default(),
+            stop: self.stop.unwrap_or_default(),
+            author: self.author.unwrap_or_default(),
+            options: self
+                .options
+                .map(|o| {
+                    o.inner
+                        .into_iter()
+                        .map(|o| PollOption {
+                            id: o.id,
+                            text: o.optiontext,
+                            votes: o.votes,
+                            voters: o
+                                .voters
+                                .map(|v| v.split(':').map(String::from).collect())
+                                .unwrap_or_default(),
+                        })
+                        .collect()
+                })
+                .unwrap_or_default(),
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct RawDispatchList<'x> {
+    #[serde(rename = "DISPATCH", default, borrow)]
+    pub(super) inner: Vec<RawDispatch<'x>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub(super) struct RawDispatch<'x> {
+    #[serde(rename = "@id")]
+    pub(super) id: u32,
+    pub(super) title: String,
+    #[serde(borrow)]
+    pub(super) author: Text<'x>,
+    // Only used to find the category, so they are not copied out of the response.
+    #[serde(borrow)]
+    pub(super) category: Text<'x>,
+    #[serde(borrow)]
+    pub(super) subcategory: Text<'x>,
+    pub(super) created: u64,
+    pub(super) edited: u64,
+    pub(super) views: u32,
+    pub(super) score: u32,
+    pub(super) text: Option<String>,
+}
+
+impl RawDispatch<'_> {
+    /// If the category could not be parsed, the offending category is returned as the error.
+    pub(super) fn try_into_dispatch(self) -> Result<Dispatch, String> {
+        Ok(Dispatch {
+            id: self.id,
+            title: self.title,
+            author: pretty_name(&*self.author),
+            category: try_into_dispatch_category(&self.category, &self.subcategory)?,
+            created: self.created,
+            edited: NonZeroU64::try_from(self.edited).ok(), // field is 0 if never edited
+            views: self.views,
+            score: self.score,
+            text: self.text,
+        })
+    }
+}
+
+/// Reads the category of a dispatch.
+/// Every dispatch has a subcategory, so an empty one is an error.
+pub(crate) fn try_into_dispatch_category(
+    main_category: &str,
+    sub_category: &str,
+) -> Result<DispatchCategory, String> {
+    if sub_category.is_empty() {
+        return Err(main_category.to_string());
+    }
+    DispatchCategory::from_parts(main_category, sub_category)
+        .map_err(|_| format!("{main_category}:{sub_category}"))
+}
+
 /// A value that either comes from a default or was customized.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum DefaultOrCustom {
     /// The value is the default.
     Default(String),
@@ -51,7 +762,16 @@ impl DefaultOrCustom {
 }
 
 /// A relative timestamp that may or may not have been recorded.
-#[derive(Debug)]
+///
+/// With the `serde` feature, it is (de)serialized as the text of the time,
+/// or `"0"` if unrecorded, as in the API.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "String", into = "String")
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum MaybeRelativeTime {
     /// A known time.
     Recorded(String),
@@ -93,7 +813,16 @@ impl From<MaybeRelativeTime> for String {
 }
 
 /// An absolute Unix timestamp that may or may not have been recorded.
-#[derive(Debug)]
+///
+/// With the `serde` feature, it is (de)serialized as the number of seconds,
+/// or `0` if unrecorded, as in the API.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "u64", into = "u64")
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum MaybeSystemTime {
     /// A known time.
     Recorded(NonZeroU64),
@@ -136,7 +865,9 @@ impl From<MaybeSystemTime> for u64 {
 }
 
 /// World Census data about the nation. Either Current or Historical.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum CensusData {
     /// Current data.
     Current(Vec<CensusCurrentData>),
@@ -144,8 +875,62 @@ pub enum CensusData {
     Historical(Vec<CensusHistoricalData>),
 }
 
+#[cfg(feature = "csv")]
+impl CensusData {
+    /// Writes the data as CSV, with a header row, one row per scale,
+    /// and empty cells for values that were not sent.
+    ///
+    /// Current data has the columns
+    /// `scale,score,world_rank,region_rank,percent_world_rank,percent_region_rank`,
+    /// and historical data has the columns `scale,timestamp,score`.
+    pub fn to_csv<W: std::io::Write>(&self, writer: W) -> Result<(), csv::Error> {
+        let mut writer = csv::Writer::from_writer(writer);
+        match self {
+            CensusData::Current(data) => {
+                writer.write_record([
+                    "scale",
+                    "score",
+                    "world_rank",
+                    "region_rank",
+                    "percent_world_rank",
+                    "percent_region_rank",
+                ])?;
+                for d in data {
+                    writer.write_record([
+                        d.id.to_string(),
+                        csv_cell(d.score),
+                        csv_cell(d.world_rank),
+                        csv_cell(d.region_rank),
+                        csv_cell(d.percent_world_rank),
+                        csv_cell(d.percent_region_rank),
+                    ])?;
+                }
+            }
+            CensusData::Historical(data) => {
+                writer.write_record(["scale", "timestamp", "score"])?;
+                for d in data {
+                    writer.write_record([
+                        d.id.to_string(),
+                        csv_cell(d.timestamp),
+                        csv_cell(d.score),
+                    ])?;
+                }
+            }
+        }
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "csv")]
+fn csv_cell(value: Option<impl Display>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_default()
+}
+
 /// Current World Census data about the nation.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CensusCurrentData {
     /// The ID used for the data point. For example,
     pub id: u8,
@@ -167,7 +952,9 @@ pub struct CensusCurrentData {
 
 /// Historical data from the World Census.
 /// Note that only scores and not rankings are available this way.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CensusHistoricalData {
     /// The ID used for the data point. For example,
     pub id: u8,
@@ -180,7 +967,9 @@ pub struct CensusHistoricalData {
 }
 
 /// Metadata about a dispatch.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Dispatch {
     /// The numerical ID of the dispatch.
     /// This forms the URL: for example,
@@ -201,4 +990,91 @@ pub struct Dispatch {
     pub views: u32,
     /// The score of the dispatch
     pub score: u32,
+    /// The text of the dispatch, as BBCode.
+    /// This is only sent when a single dispatch is requested with
+    /// [`WorldShard::Dispatch`](crate::shards::world::WorldShard::Dispatch).
+    pub text: Option<String>,
+}
+
+/// How nations rank on a World Census scale.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct CensusRanks {
+    /// The ID of the World Census scale.
+    pub id: u8,
+    /// The nations, in order of their ranking.
+    pub nations: Vec<CensusRanksNation>,
+}
+
+#[cfg(feature = "csv")]
+impl CensusRanks {
+    /// Writes the ranks as CSV, with a header row and one row per nation,
+    /// in the columns `scale,nation,rank,score`.
+    pub fn to_csv<W: std::io::Write>(&self, writer: W) -> Result<(), csv::Error> {
+        let mut writer = csv::Writer::from_writer(writer);
+        writer.write_record(["scale", "nation", "rank", "score"])?;
+        let scale = self.id.to_string();
+        for nation in &self.nations {
+            writer.write_record([
+                scale.as_str(),
+                nation.name.as_str(),
+                &csv_cell(nation.rank),
+                &csv_cell(nation.score),
+            ])?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+/// A nation's placement on a World Census scale.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct CensusRanksNation {
+    /// The name of the nation.
+    pub name: String,
+    /// The placement of the nation, if the API gave one.
+    pub rank: Option<u32>,
+    /// The score of the nation on the Census scale, if the API gave one.
+    pub score: Option<f64>,
+}
+
+/// A regional poll.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Poll {
+    /// The numerical ID of the poll.
+    pub id: u32,
+    /// The title of the poll.
+    pub title: String,
+    /// The description of the poll, if it has one.
+    pub text: Option<String>,
+    /// The region the poll is in.
+    pub region: String,
+    /// The Unix timestamp of when the poll opened.
+    pub start: u64,
+    /// The Unix timestamp of when the poll closes.
+    pub stop: u64,
+    /// The nation that created the poll.
+    pub author: String,
+    /// The options that can be voted for.
+    pub options: Vec<PollOption>,
+}
+
+/// An option in a regional poll.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct PollOption {
+    /// The ID of the option.
+    pub id: u32,
+    /// The text of the option.
+    pub text: String,
+    /// The number of nations that voted for the option.
+    pub votes: u32,
+    /// The nations that voted for the option.
+    pub voters: Vec<String>,
 }
@@ -1,14 +1,17 @@
 //! The nation parser module.
 
+pub use crate::models::banner::BannerId;
+
 use crate::{
+    models::{banner::BadBannerId, name::NameList, timestamp::DAY},
     parsers::{
         happenings::Event, CensusData, DefaultOrCustom, Dispatch, MaybeRelativeTime,
-        MaybeSystemTime,
+        MaybeSystemTime, XmlError,
     },
-    shards::wa::WACouncil,
+    shards::{nation::PublicNationShard, wa::WACouncil, MissingShardData},
 };
-use quick_xml::DeError;
 use std::{
+    convert::Infallible,
     fmt::{Debug, Display, Formatter},
     num::{NonZeroU16, NonZeroU32},
     str::FromStr,
@@ -16,7 +19,9 @@ use std::{
 use thiserror::Error;
 
 /// The status of a nation in the World Assembly.
-#[derive(Debug)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum WAStatus {
     /// The nation is the delegate of a region.
     Delegate,
@@ -24,13 +29,76 @@ pub enum WAStatus {
     Member,
     /// The nation is not part of the World Assembly.
     NonMember,
+    /// A status that this crate does not know about yet, as it was sent.
+    Unknown(String),
+}
+
+impl FromStr for WAStatus {
+    type Err = Infallible;
+
+    /// Statuses that are not recognized become [`WAStatus::Unknown`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "WA Delegate" => WAStatus::Delegate,
+            "WA Member" => WAStatus::Member,
+            "Non-member" => WAStatus::NonMember,
+            other => WAStatus::Unknown(other.to_string()),
+        })
+    }
+}
+
+/// How many days a nation can go without logging in before it ceases to exist,
+/// unless it is in vacation mode.
+pub const CTE_DAYS: u64 = 28;
+
+/// How many days a nation in vacation mode can go without logging in
+/// before it ceases to exist.
+pub const VACATION_CTE_DAYS: u64 = 60;
+
+/// How recently a nation logged in.
+/// See [`activity_level`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum ActivityLevel {
+    /// The nation logged in within the last day.
+    ActiveToday,
+    /// The nation logged in within the last week.
+    ThisWeek,
+    /// The nation last logged in one to two weeks ago.
+    Inactive14d,
+    /// The nation last logged in two weeks ago or more,
+    /// and will cease to exist in this many days unless it logs in or is in vacation mode.
+    /// Nations that are overdue to cease to exist have 0 days left.
+    NearCTE(u64),
+}
+
+/// How recently a nation logged in,
+/// given the Unix timestamps of its last login and of the present.
+/// ```rust
+/// # use crustacean_states::parsers::nation::{activity_level, ActivityLevel};
+/// const DAY: u64 = 24 * 60 * 60;
+/// let now = 100 * DAY;
+/// assert_eq!(activity_level(now - 3600, now), ActivityLevel::ActiveToday);
+/// assert_eq!(activity_level(now - 10 * DAY, now), ActivityLevel::Inactive14d);
+/// assert_eq!(activity_level(now - 25 * DAY, now), ActivityLevel::NearCTE(3));
+/// ```
+pub fn activity_level(last_login: u64, now: u64) -> ActivityLevel {
+    match now.saturating_sub(last_login) {
+        away if away < DAY => ActivityLevel::ActiveToday,
+        away if away < 7 * DAY => ActivityLevel::ThisWeek,
+        away if away < 14 * DAY => ActivityLevel::Inactive14d,
+        away => ActivityLevel::NearCTE(CTE_DAYS.saturating_sub(away / DAY)),
+    }
 }
 
 /// Describes the nation's government spending as percentages.
 /// Each field represents a category.
 /// All fields *should* add up to 100.0,
 /// but expect it to not be exact due to floating-point arithmetic and on-site rounding error.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[allow(missing_docs)]
 pub struct Government {
     pub administration: f64,
@@ -52,7 +120,9 @@ pub struct Government {
 /// Note:
 /// in a future release,
 /// the fields in this struct will be converted from `String`s to enum variants.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[allow(missing_docs)]
 pub struct Freedoms {
     // TODO make enum
@@ -64,18 +134,69 @@ pub struct Freedoms {
 }
 
 /// Gives a score out of 100 for the three types of national freedom.
-// TODO restrict type from 0 to 100
-#[derive(Debug)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[allow(missing_docs)]
 pub struct FreedomScores {
-    pub civil_rights: u8,
-    pub economy: u8,
-    pub political_freedom: u8,
+    pub civil_rights: FreedomScore,
+    pub economy: FreedomScore,
+    pub political_freedom: FreedomScore,
+}
+
+/// A score from 0 to 100 for one type of national freedom.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "u8", into = "u8")
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct FreedomScore(u8);
+
+impl FreedomScore {
+    /// The highest possible score.
+    pub const MAX: FreedomScore = FreedomScore(100);
+
+    /// Creates a score, if it is no more than 100.
+    pub fn new(score: u8) -> Option<Self> {
+        (score <= Self::MAX.0).then_some(Self(score))
+    }
+
+    /// The score as a number.
+    pub fn as_u8(self) -> u8 {
+        self.0
+    }
+}
+
+impl Display for FreedomScore {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<FreedomScore> for u8 {
+    fn from(value: FreedomScore) -> Self {
+        value.0
+    }
+}
+
+impl TryFrom<u8> for FreedomScore {
+    type Error = IntoNationError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Self::new(value).ok_or_else(|| IntoNationError::BadFieldError {
+            field: "NATION/FREEDOMSCORES",
+            value: value.to_string(),
+        })
+    }
 }
 
 /// Causes of death in a nation.
 /// Note: at some point, the field `kind` in this struct will be converted to enum variants.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Cause {
     /// The way in which citizens die.
     // TODO make enum
@@ -85,7 +206,9 @@ pub struct Cause {
 }
 
 /// A breakdown of the nation's relative economic power in each economic sector.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[allow(missing_docs)] // TODO learn economics so I can explain this :P
 pub struct Sectors {
     pub black_market: f64,
@@ -101,7 +224,9 @@ pub struct Sectors {
 /// depending on the [`PublicNationShard`](crate::shards::nation::PublicNationShard)s used
 /// to make the request,
 /// only certain fields will be returned.
-#[derive(Debug)]
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[non_exhaustive]
 pub struct Nation {
     /// The name of the nation.
@@ -142,7 +267,7 @@ pub struct Nation {
     ///
     /// Requested by using
     /// [`PublicNationShard::Endorsements`](crate::shards::nation::PublicNationShard::Endorsements).
-    pub endorsements: Option<Vec<String>>,
+    pub endorsements: Option<NameList>,
     /// The number of issues answered by the nation.
     ///
     /// Requested by using
@@ -371,17 +496,16 @@ pub struct Nation {
     pub founded_time: Option<MaybeSystemTime>,
     /// The vote of the nation in the General Assembly.
     ///
-    /// Note:
-    /// if the nation is not in the World Assembly,
-    /// but the [`PublicNationShard::WA`] shard was not requested,
-    /// the field will erroneously be `Some(`[`WAVote::Undecided`]`)`.
+    /// If the [`PublicNationShard::WA`] shard was not requested,
+    /// it is not known whether an undecided nation is in the World Assembly,
+    /// and the field will be `Some(`[`WAVoteState::Unknown`]`)`.
     ///
     /// Requested by using
     /// [`PublicNationShard::GAVote`](crate::shards::nation::PublicNationShard::GAVote).
     /// Recommended to request with [`PublicNationShard::WA`].
     ///
     /// [`PublicNationShard::WA`]: crate::shards::nation::PublicNationShard::WA
-    pub ga_vote: Option<WAVote>,
+    pub ga_vote: Option<WAVoteState>,
     /// The GDP of the nation in its national currency.
     ///
     /// Requested by using [`PublicNationShard::Gdp`](crate::shards::nation::PublicNationShard::Gdp).
@@ -443,17 +567,16 @@ pub struct Nation {
     pub richest: Option<u32>,
     /// The vote of the nation in the Security Council.
     ///
-    /// Note:
-    /// if the nation is not in the World Assembly,
-    /// and the [`PublicNationShard::WA`] shard was not requested,
-    /// the field will erroneously be `Some(`[`WAVote::Undecided`]`)`.
+    /// If the [`PublicNationShard::WA`] shard was not requested,
+    /// it is not known whether an undecided nation is in the World Assembly,
+    /// and the field will be `Some(`[`WAVoteState::Unknown`]`)`.
     ///
     /// Requested by using
     /// [`PublicNationShard::SCVote`](crate::shards::nation::PublicNationShard::SCVote).
     /// Recommended to request with [`PublicNationShard::WA`].
     ///
     /// [`PublicNationShard::WA`]: crate::shards::nation::PublicNationShard::WA
-    pub sc_vote: Option<WAVote>,
+    pub sc_vote: Option<WAVoteState>,
     /// Describes the nation's economy as percentages controlled or funded by various sectors.
     ///
     /// Requested by using
@@ -481,6 +604,186 @@ pub struct Nation {
     pub world_census: Option<NonZeroU32>,
 }
 
+impl Nation {
+    /// How recently the nation logged in, as of the Unix timestamp `now`.
+    ///
+    /// This is `None` unless
+    /// [`PublicNationShard::LastLogin`](crate::shards::nation::PublicNationShard::LastLogin)
+    /// was requested.
+    pub fn activity_level(&self, now: u64) -> Option<ActivityLevel> {
+        self.last_login
+            .map(|last_login| activity_level(last_login, now))
+    }
+
+    /// Combines two responses for the same nation into one,
+    /// such as when its shards had to be requested in more than one request.
+    ///
+    /// Fields that only one of the nations has data for are kept,
+    /// and `policy` decides which value is kept when both have data.
+    /// The name of `self` is always kept.
+    /// ```rust
+    /// # use crustacean_states::parsers::nation::{MergePolicy, Nation};
+    /// let first = Nation::from_xml(
+    ///     r#"<NATION id="aramos"><ANIMAL>dolphin</ANIMAL><POPULATION>41</POPULATION></NATION>"#,
+    /// )?;
+    /// let second = Nation::from_xml(
+    ///     r#"<NATION id="aramos"><POPULATION>42</POPULATION><MOTTO>Hi</MOTTO></NATION>"#,
+    /// )?;
+    /// let nation = first.merge(second, MergePolicy::KeepOther);
+    /// assert_eq!(nation.animal.as_deref(), Some("dolphin"));
+    /// assert_eq!(nation.population, Some(42));
+    /// assert_eq!(nation.motto.as_deref(), Some("Hi"));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn merge(mut self, other: Nation, policy: MergePolicy) -> Self {
+        let Nation {
+            name: _,
+            kind,
+            full_name,
+            motto,
+            category,
+            wa_status,
+            endorsements,
+            issues_answered,
+            freedom,
+            region,
+            population,
+            tax,
+            animal,
+            currency,
+            demonym_adjective,
+            demonym_singular,
+            demonym_plural,
+            flag,
+            major_industry,
+            government_priority,
+            government,
+            founded,
+            first_login,
+            last_login,
+            last_activity,
+            influence,
+            freedom_scores,
+            public_sector,
+            deaths,
+            leader,
+            capital,
+            religion,
+            factbooks,
+            dispatches,
+            dbid,
+            admirable,
+            admirables,
+            animal_trait,
+            banner,
+            banners,
+            census,
+            crime,
+            dispatch_list,
+            factbook_list,
+            founded_time,
+            ga_vote,
+            gdp,
+            govt_desc,
+            happenings,
+            income,
+            industry_desc,
+            legislation,
+            notable,
+            notables,
+            policies,
+            poorest,
+            regional_census,
+            richest,
+            sc_vote,
+            sectors,
+            sensibilities,
+            tg_can_recruit,
+            tg_can_campaign,
+            world_census,
+        } = other;
+        merge_field(&mut self.kind, kind, policy);
+        merge_field(&mut self.full_name, full_name, policy);
+        merge_field(&mut self.motto, motto, policy);
+        merge_field(&mut self.category, category, policy);
+        merge_field(&mut self.wa_status, wa_status, policy);
+        merge_field(&mut self.endorsements, endorsements, policy);
+        merge_field(&mut self.issues_answered, issues_answered, policy);
+        merge_field(&mut self.freedom, freedom, policy);
+        merge_field(&mut self.region, region, policy);
+        merge_field(&mut self.population, population, policy);
+        merge_field(&mut self.tax, tax, policy);
+        merge_field(&mut self.animal, animal, policy);
+        merge_field(&mut self.currency, currency, policy);
+        merge_field(&mut self.demonym_adjective, demonym_adjective, policy);
+        merge_field(&mut self.demonym_singular, demonym_singular, policy);
+        merge_field(&mut self.demonym_plural, demonym_plural, policy);
+        merge_field(&mut self.flag, flag, policy);
+        merge_field(&mut self.major_industry, major_industry, policy);
+        merge_field(&mut self.government_priority, government_priority, policy);
+        merge_field(&mut self.government, government, policy);
+        merge_field(&mut self.founded, founded, policy);
+        merge_field(&mut self.first_login, first_login, policy);
+        merge_field(&mut self.last_login, last_login, policy);
+        merge_field(&mut self.last_activity, last_activity, policy);
+        merge_field(&mut self.influence, influence, policy);
+        merge_field(&mut self.freedom_scores, freedom_scores, policy);
+        merge_field(&mut self.public_sector, public_sector, policy);
+        merge_field(&mut self.deaths, deaths, policy);
+        merge_field(&mut self.leader, leader, policy);
+        merge_field(&mut self.capital, capital, policy);
+        merge_field(&mut self.religion, religion, policy);
+        merge_field(&mut self.factbooks, factbooks, policy);
+        merge_field(&mut self.dispatches, dispatches, policy);
+        merge_field(&mut self.dbid, dbid, policy);
+        merge_field(&mut self.admirable, admirable, policy);
+        merge_field(&mut self.admirables, admirables, policy);
+        merge_field(&mut self.animal_trait, animal_trait, policy);
+        merge_field(&mut self.banner, banner, policy);
+        merge_field(&mut self.banners, banners, policy);
+        merge_field(&mut self.census, census, policy);
+        merge_field(&mut self.crime, crime, policy);
+        merge_field(&mut self.dispatch_list, dispatch_list, policy);
+        merge_field(&mut self.factbook_list, factbook_list, policy);
+        merge_field(&mut self.founded_time, founded_time, policy);
+        merge_field(&mut self.ga_vote, ga_vote, policy);
+        merge_field(&mut self.gdp, gdp, policy);
+        merge_field(&mut self.govt_desc, govt_desc, policy);
+        merge_field(&mut self.happenings, happenings, policy);
+        merge_field(&mut self.income, income, policy);
+        merge_field(&mut self.industry_desc, industry_desc, policy);
+        merge_field(&mut self.legislation, legislation, policy);
+        merge_field(&mut self.notable, notable, policy);
+        merge_field(&mut self.notables, notables, policy);
+        merge_field(&mut self.policies, policies, policy);
+        merge_field(&mut self.poorest, poorest, policy);
+        merge_field(&mut self.regional_census, regional_census, policy);
+        merge_field(&mut self.richest, richest, policy);
+        merge_field(&mut self.sc_vote, sc_vote, policy);
+        merge_field(&mut self.sectors, sectors, policy);
+        merge_field(&mut self.sensibilities, sensibilities, policy);
+        merge_field(&mut self.tg_can_recruit, tg_can_recruit, policy);
+        merge_field(&mut self.tg_can_campaign, tg_can_campaign, policy);
+        merge_field(&mut self.world_census, world_census, policy);
+        self
+    }
+}
+
+/// Which value [`Nation::merge`] keeps when both nations have data for the same field.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum MergePolicy {
+    /// Keep the value of the nation being merged into.
+    KeepSelf,
+    /// Keep the value of the nation being merged in.
+    KeepOther,
+}
+
+fn merge_field<T>(field: &mut Option<T>, other: Option<T>, policy: MergePolicy) {
+    if other.is_some() && (field.is_none() || policy == MergePolicy::KeepOther) {
+        *field = other;
+    }
+}
+
 /// A nation given by the standard version of the public nation API.
 ///
 /// This struct aims to have parity with [`Nation`],
@@ -488,7 +791,9 @@ pub struct Nation {
 /// fields are not wrapped in the [`Option`] type,
 /// and only the fields required for the struct are provided.
 /// This should speed up parsing and create ease of use.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[non_exhaustive]
 pub struct StandardNation {
     /// The name of the nation.
@@ -507,7 +812,7 @@ pub struct StandardNation {
     /// The WA status of the nation.
     pub wa_status: WAStatus,
     /// A list of nations that endorse the nation.
-    pub endorsements: Vec<String>,
+    pub endorsements: NameList,
     /// The number of issues answered by the nation.
     pub issues_answered: u32,
     /// The freedom statistics of the nation.
@@ -590,8 +895,190 @@ pub struct StandardNation {
     pub dbid: u32,
 }
 
+impl StandardNation {
+    /// How recently the nation logged in, as of the Unix timestamp `now`.
+    pub fn activity_level(&self, now: u64) -> ActivityLevel {
+        activity_level(self.last_login, now)
+    }
+}
+
+/// Every field of a [`StandardNation`] is kept, and the fields it does not have are `None`.
+impl From<StandardNation> for Nation {
+    fn from(value: StandardNation) -> Self {
+        Self {
+            name: value.name,
+            kind: Some(value.kind),
+            full_name: Some(value.full_name),
+            motto: Some(value.motto),
+            category: Some(value.category),
+            wa_status: Some(value.wa_status),
+            endorsements: Some(value.endorsements),
+            issues_answered: Some(value.issues_answered),
+            freedom: Some(value.freedom),
+            region: Some(value.region),
+            population: Some(value.population),
+            tax: Some(value.tax),
+            animal: Some(value.animal),
+            currency: Some(value.currency),
+            demonym_adjective: Some(value.demonym_adjective),
+            demonym_singular: Some(value.demonym_singular),
+            demonym_plural: Some(value.demonym_plural),
+            flag: Some(value.flag),
+            major_industry: Some(value.major_industry),
+            government_priority: Some(value.government_priority),
+            government: Some(value.government),
+            founded: Some(value.founded),
+            first_login: Some(value.first_login),
+            last_login: Some(value.last_login),
+            last_activity: Some(value.last_activity),
+            influence: Some(value.influence),
+            freedom_scores: Some(value.freedom_scores),
+            public_sector: Some(value.public_sector),
+            deaths: Some(value.deaths),
+            leader: Some(value.leader),
+            capital: Some(value.capital),
+            religion: Some(value.religion),
+            factbooks: Some(value.factbooks),
+            dispatches: Some(value.dispatches),
+            dbid: Some(value.dbid),
+            ..Default::default()
+        }
+    }
+}
+
+/// Fails with [`MissingShardData`], listing the shards of the standard nation response
+/// that have no data in the [`Nation`].
+impl TryFrom<Nation> for StandardNation {
+    type Error = MissingShardData;
+
+    fn try_from(value: Nation) -> Result<Self, Self::Error> {
+        let mut missing = Vec::new();
+        macro_rules! take {
+            ($field:ident, $shard:ident) => {
+                value.$field.or_else(|| {
+                    missing.push(PublicNationShard::$shard.as_ref().to_ascii_lowercase());
+                    None
+                })
+            };
+        }
+        let (
+            Some(kind),
+            Some(full_name),
+            Some(motto),
+            Some(category),
+            Some(wa_status),
+            Some(endorsements),
+            Some(issues_answered),
+            Some(freedom),
+            Some(region),
+            Some(population),
+            Some(tax),
+            Some(animal),
+            Some(currency),
+            Some(demonym_adjective),
+            Some(demonym_singular),
+            Some(demonym_plural),
+            Some(flag),
+            Some(major_industry),
+            Some(government_priority),
+            Some(government),
+            Some(founded),
+            Some(first_login),
+            Some(last_login),
+            Some(last_activity),
+            Some(influence),
+            Some(freedom_scores),
+            Some(public_sector),
+            Some(deaths),
+            Some(leader),
+            Some(capital),
+            Some(religion),
+            Some(factbooks),
+            Some(dispatches),
+            Some(dbid),
+        ) = (
+            take!(kind, Type),
+            take!(full_name, FullName),
+            take!(motto, Motto),
+            take!(category, Category),
+            take!(wa_status, WA),
+            take!(endorsements, Endorsements),
+            take!(issues_answered, Answered),
+            take!(freedom, Freedom),
+            take!(region, Region),
+            take!(population, Population),
+            take!(tax, Tax),
+            take!(animal, Animal),
+            take!(currency, Currency),
+            take!(demonym_adjective, Demonym),
+            take!(demonym_singular, Demonym2),
+            take!(demonym_plural, Demonym2Plural),
+            take!(flag, Flag),
+            take!(major_industry, MajorIndustry),
+            take!(government_priority, GovtPriority),
+            take!(government, Govt),
+            take!(founded, Founded),
+            take!(first_login, FirstLogin),
+            take!(last_login, LastLogin),
+            take!(last_activity, LastActivity),
+            take!(influence, Influence),
+            take!(freedom_scores, FreedomScores),
+            take!(public_sector, PublicSector),
+            take!(deaths, Deaths),
+            take!(leader, Leader),
+            take!(capital, Capital),
+            take!(religion, Religion),
+            take!(factbooks, Factbooks),
+            take!(dispatches, Dispatches),
+            take!(dbid, DbId),
+        )
+        else {
+            return Err(MissingShardData { shards: missing });
+        };
+        Ok(Self {
+            name: value.name,
+            kind,
+            full_name,
+            motto,
+            category,
+            wa_status,
+            endorsements,
+            issues_answered,
+            freedom,
+            region,
+            population,
+            tax,
+            animal,
+            currency,
+            demonym_adjective,
+            demonym_singular,
+            demonym_plural,
+            flag,
+            major_industry,
+            government_priority,
+            government,
+            founded,
+            first_login,
+            last_login,
+            last_activity,
+            influence,
+            freedom_scores,
+            public_sector,
+            deaths,
+            leader,
+            capital,
+            religion,
+            factbooks,
+            dispatches,
+            dbid,
+        })
+    }
+}
+
 /// Describes a national policy.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Policy {
     /// The name of the policy.
     pub name: String,
@@ -619,10 +1106,16 @@ pub enum IntoNationError {
     /// could not be parsed as a [`DispatchCategory`](crate::models::dispatch::DispatchCategory).
     #[error("malformed dispatch category: {0}")]
     BadDispatchCategory(String),
-    /// A `String` could not be parsed as a [`WAStatus`].
-    #[error("malformed WA status response: {0}")]
-    BadWAStatusError(String),
-    /// A `String` could not be parsed as a [`WAVote`].
+    /// A field had a value outside of the values it can have.
+    #[error("malformed {field}: {value}")]
+    BadFieldError {
+        /// The field that the value is for,
+        /// as a path in the response (e.g. `NATION/FREEDOMSCORES/ECONOMY`) where it is known.
+        field: &'static str,
+        /// The problematic value.
+        value: String,
+    },
+    /// A `String` could not be parsed as a [`WAVoteState`].
     #[error("malformed WA vote: {bad_vote} in {council:?}")]
     BadWAVote {
         /// The problematic content.
@@ -635,7 +1128,7 @@ pub enum IntoNationError {
     DeserializationError {
         /// The error source. Look here for what went wrong.
         #[from]
-        source: DeError,
+        source: XmlError,
     },
     /// There was neither an `id` attribute in the `<NATION>` root tag nor a `<NAME>` tag.
     #[error("could not find a nation name in response")]
@@ -643,74 +1136,77 @@ pub enum IntoNationError {
     /// No census data was created for this nation.
     #[error("could not find any census data in response")]
     NoCensusDataError,
+    /// A shard that was requested is not in the response.
+    /// The shard is named as in [`PublicNationShard`](crate::shards::nation::PublicNationShard),
+    /// e.g. `Motto`.
+    #[error("could not find the {0} shard in response")]
+    MissingShardError(&'static str),
 }
 
-/// Describes a nation's vote in the World Assembly.
+/// A field that [`Nation::from_xml_lenient`] left out because its value was not understood.
 #[derive(Debug)]
-pub enum WAVote {
+pub struct ParseWarning {
+    /// The name of the field in [`Nation`].
+    pub field: &'static str,
+    /// Why the value was not understood.
+    pub error: IntoNationError,
+}
+
+impl Display for ParseWarning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} left out: {}", self.field, self.error)
+    }
+}
+
+/// Describes a nation's vote in a World Assembly council.
+///
+/// The API says a nation is undecided even if it is not in the World Assembly,
+/// so the vote can only be trusted alongside the nation's [`WAStatus`].
+/// When [`PublicNationShard::WA`](crate::shards::nation::PublicNationShard::WA)
+/// is requested with the vote, both are used to find the state of the vote;
+/// otherwise, an undecided vote is [`WAVoteState::Unknown`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum WAVoteState {
+    /// The nation is not in the World Assembly, so it cannot vote.
+    NonMember,
+    /// The nation is in the World Assembly and has not voted on the proposed resolution.
+    Undecided,
     /// The nation votes for the proposed resolution.
     For,
     /// The nation votes against the proposed resolution.
     Against,
-    /// The nation has not voted on the proposed resolution.
-    ///
-    /// This is the default response that the game provides,
-    /// even if the nation is not in the World Assembly.
-    /// See the documentation for
-    /// [`PublicNationShard::GAVote`](crate::shards::nation::PublicNationShard::GAVote)
-    /// or [`PublicNationShard::SCVote`](crate::shards::nation::PublicNationShard::SCVote)
-    /// for more details.
-    Undecided,
+    /// The nation has not voted,
+    /// but it is not known whether it is in the World Assembly,
+    /// because [`PublicNationShard::WA`](crate::shards::nation::PublicNationShard::WA)
+    /// was not requested.
+    Unknown,
 }
 
-impl TryFrom<String> for WAVote {
-    type Error = IntoNationError;
-
-    fn try_from(value: String) -> Result<Self, Self::Error> {
-        match value.as_str() {
-            "FOR" => Ok(WAVote::For),
-            "AGAINST" => Ok(WAVote::Against),
-            "UNDECIDED" => Ok(WAVote::Undecided),
-            other => Err(IntoNationError::BadWAVote {
+impl WAVoteState {
+    /// Combines the vote from the API with the nation's WA status, if it is known.
+    pub(crate) fn parse(
+        vote: &str,
+        status: Option<&WAStatus>,
+        council: WACouncil,
+    ) -> Result<Self, IntoNationError> {
+        match (vote, status) {
+            (_, Some(WAStatus::NonMember)) => Ok(WAVoteState::NonMember),
+            ("FOR", _) => Ok(WAVoteState::For),
+            ("AGAINST", _) => Ok(WAVoteState::Against),
+            ("UNDECIDED", None | Some(WAStatus::Unknown(_))) => Ok(WAVoteState::Unknown),
+            ("UNDECIDED", Some(_)) => Ok(WAVoteState::Undecided),
+            (other, _) => Err(IntoNationError::BadWAVote {
                 bad_vote: other.to_string(),
-                council: Default::default(),
+                council,
             }),
         }
     }
 }
 
-/// The ID of a banner. WIP. TODO make banner id categories
-#[derive(Clone, Debug, PartialEq)]
-pub struct BannerId {
-    pub(crate) category: String,
-    pub(crate) number: u16,
-}
-
-impl Display for BannerId {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}{}", self.category.to_ascii_lowercase(), self.number)
-    }
-}
-
-impl BannerId {
-    fn new(category: impl ToString, number: u16) -> Self {
-        Self {
-            category: category.to_string(),
-            number,
-        }
-    }
-}
-
-impl TryFrom<String> for BannerId {
-    type Error = IntoNationError;
-
-    fn try_from(value: String) -> Result<Self, Self::Error> {
-        let split_index = value.chars().position(|c| c.is_ascii_digit());
-        if split_index.is_none() || split_index == Some(0) {
-            return Err(IntoNationError::BadBannerId(value));
-        }
-        let (cat, num) = value.split_at(split_index.unwrap());
-        let num = u16::from_str(num).map_err(|_| IntoNationError::BadBannerId(value.clone()))?;
-        Ok(BannerId::new(cat, num))
+impl From<BadBannerId> for IntoNationError {
+    fn from(value: BadBannerId) -> Self {
+        IntoNationError::BadBannerId(value.0)
     }
 }
@@ -1,40 +1,50 @@
+#[cfg(feature = "client")]
+use crate::client::{parse_response, ClientError};
 use crate::{
-    models::dispatch::{
-        AccountCategory, BulletinCategory, DispatchCategory, FactbookCategory, MetaCategory,
-    },
     parsers::{
+        empty_as_none, from_xml_bytes, from_xml_reader, from_xml_str,
         happenings::Event,
         nation::{
-            BannerId, Cause, FreedomScores, Freedoms, Government, IntoNationError, Nation, Policy,
-            Sectors, StandardNation, WAStatus, WAVote,
+            BannerId, Cause, FreedomScore, FreedomScores, Freedoms, Government, IntoNationError,
+            Nation, ParseWarning, Policy, Sectors, StandardNation, WAStatus, WAVoteState,
         },
-        CensusCurrentData, CensusData, CensusHistoricalData, DefaultOrCustom, Dispatch,
-        MaybeRelativeTime, MaybeSystemTime, RawEvent,
+        DefaultOrCustom, MaybeRelativeTime, MaybeSystemTime, NSResponse, RawCensus, RawDispatch,
+        RawDispatchList, RawHappenings, Text,
     },
     pretty_name,
+    shards::wa::WACouncil,
 };
+#[cfg(feature = "client")]
+use reqwest::Response;
 use serde::Deserialize;
-use std::num::{NonZeroU16, NonZeroU32, NonZeroU64};
+use std::io::BufRead;
+use std::num::{NonZeroU16, NonZeroU32};
+use std::str::FromStr;
 
 //noinspection SpellCheckingInspection
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "UPPERCASE")]
-struct RawNation {
+struct RawNation<'x> {
     // default shards
-    #[serde(rename = "@id")]
-    id: Option<String>,
+    #[serde(rename = "@id", borrow)]
+    id: Option<Text<'x>>,
     name: Option<String>,
     #[serde(rename = "TYPE")]
     kind: Option<String>,
     fullname: Option<String>,
     motto: Option<String>,
     category: Option<String>,
-    unstatus: Option<String>,
-    endorsements: Option<String>,
+    #[serde(borrow)]
+    unstatus: Option<Text<'x>>,
+    #[serde(borrow)]
+    endorsements: Option<Text<'x>>,
+    #[serde(default, deserialize_with = "empty_as_none")]
     issues_answered: Option<u32>,
     freedom: Option<RawFreedoms>,
     region: Option<String>,
+    #[serde(default, deserialize_with = "empty_as_none")]
     population: Option<u32>,
+    #[serde(default, deserialize_with = "empty_as_none")]
     tax: Option<f64>,
     animal: Option<String>,
     currency: Option<String>,
@@ -46,18 +56,24 @@ struct RawNation {
     govtpriority: Option<String>,
     govt: Option<RawGovernment>,
     founded: Option<String>,
+    #[serde(default, deserialize_with = "empty_as_none")]
     firstlogin: Option<u64>,
+    #[serde(default, deserialize_with = "empty_as_none")]
     lastlogin: Option<u64>,
     lastactivity: Option<String>,
     influence: Option<String>,
     freedomscores: Option<RawFreedomScores>,
+    #[serde(default, deserialize_with = "empty_as_none")]
     publicsector: Option<f64>,
     deaths: Option<Deaths>,
     leader: Option<String>,
     capital: Option<String>,
     religion: Option<String>,
+    #[serde(default, deserialize_with = "empty_as_none")]
     factbooks: Option<u16>,
+    #[serde(default, deserialize_with = "empty_as_none")]
     dispatches: Option<u16>,
+    #[serde(default, deserialize_with = "empty_as_none")]
     dbid: Option<u32>,
     // END default
     admirable: Option<String>,
@@ -65,44 +81,59 @@ struct RawNation {
     animaltrait: Option<String>,
     banner: Option<String>,
     banners: Option<Banners>,
-    census: Option<Census>,
+    census: Option<RawCensus>,
     crime: Option<String>,
-    dispatchlist: Option<RawDispatchList>,
-    factbooklist: Option<RawFactbookList>,
+    #[serde(borrow)]
+    dispatchlist: Option<RawDispatchList<'x>>,
+    #[serde(borrow)]
+    factbooklist: Option<RawFactbookList<'x>>,
+    #[serde(default, deserialize_with = "empty_as_none")]
     foundedtime: Option<u64>,
-    gavote: Option<String>,
+    #[serde(borrow)]
+    gavote: Option<Text<'x>>,
+    #[serde(default, deserialize_with = "empty_as_none")]
     gdp: Option<u64>,
     govtdesc: Option<String>,
-    happenings: Option<Happenings>,
+    happenings: Option<RawHappenings>,
+    #[serde(default, deserialize_with = "empty_as_none")]
     income: Option<u32>,
     industrydesc: Option<String>,
     legislation: Option<Legislation>,
     notable: Option<String>,
     notables: Option<Notables>,
     policies: Option<Policies>,
+    #[serde(default, deserialize_with = "empty_as_none")]
     poorest: Option<u32>,
+    #[serde(default, deserialize_with = "empty_as_none")]
     rcensus: Option<NonZeroU16>,
+    #[serde(default, deserialize_with = "empty_as_none")]
     richest: Option<u32>,
-    scvote: Option<String>,
+    #[serde(borrow)]
+    scvote: Option<Text<'x>>,
     sectors: Option<RawSectors>,
     sensibilities: Option<String>,
+    #[serde(default, deserialize_with = "empty_as_none")]
     tgcanrecruit: Option<u8>,
+    #[serde(default, deserialize_with = "empty_as_none")]
     tgcancampaign: Option<u8>,
+    #[serde(default, deserialize_with = "empty_as_none")]
     wcensus: Option<NonZeroU32>,
 }
 
 //noinspection SpellCheckingInspection
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "UPPERCASE")]
-struct RawStandardNation {
+struct RawStandardNation<'x> {
     name: String,
     #[serde(rename = "TYPE")]
     kind: String,
     fullname: String,
     motto: String,
     category: String,
-    unstatus: String,
-    endorsements: String,
+    #[serde(borrow)]
+    unstatus: Text<'x>,
+    #[serde(borrow)]
+    endorsements: Text<'x>,
     issues_answered: u32,
     freedom: RawFreedoms,
     region: String,
@@ -152,28 +183,9 @@ struct Banners {
 }
 
 #[derive(Debug, Deserialize)]
-struct Census {
-    #[serde(rename = "SCALE", default)]
-    inner: Vec<RawCensusData>,
-}
-
-#[derive(Debug, Deserialize)]
-struct RawDispatchList {
-    #[serde(rename = "DISPATCH", default)]
-    inner: Vec<RawDispatch>,
-}
-
-#[derive(Debug, Deserialize)]
-struct RawFactbookList {
-    #[serde(rename = "FACTBOOK", default)]
-    inner: Vec<RawDispatch>, // only containing factbooks!
-}
-
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "UPPERCASE")]
-struct Happenings {
-    #[serde(rename = "EVENT", default)]
-    inner: Vec<RawEvent>,
+struct RawFactbookList<'x> {
+    #[serde(rename = "FACTBOOK", default, borrow)]
+    inner: Vec<RawDispatch<'x>>, // only containing factbooks!
 }
 
 #[derive(Debug, Deserialize)]
@@ -231,95 +243,6 @@ impl From<RawCause> for Cause {
     }
 }
 
-//noinspection SpellCheckingInspection
-#[derive(Debug, Deserialize)]
-struct RawCensusData {
-    #[serde(rename = "@id")]
-    id: u8,
-    #[serde(rename = "SCORE")]
-    score: Option<f64>,
-    #[serde(rename = "RANK")]
-    world_rank: Option<NonZeroU32>,
-    #[serde(rename = "RRANK")]
-    region_rank: Option<NonZeroU32>,
-    #[serde(rename = "PRANK")]
-    percent_world_rank: Option<f64>,
-    #[serde(rename = "PRRANK")]
-    percent_region_rank: Option<f64>,
-    #[serde(rename = "TIMESTAMP")]
-    timestamp: Option<NonZeroU64>,
-}
-
-impl From<RawCensusData> for CensusCurrentData {
-    fn from(value: RawCensusData) -> Self {
-        let RawCensusData {
-            id,
-            score,
-            world_rank,
-            region_rank,
-            percent_world_rank,
-            percent_region_rank,
-            ..
-        } = value;
-        Self {
-            id,
-            score,
-            world_rank,
-            region_rank,
-            percent_world_rank,
-            percent_region_rank,
-        }
-    }
-}
-
-impl From<RawCensusData> for CensusHistoricalData {
-    fn from(value: RawCensusData) -> Self {
-        let RawCensusData {
-            id,
-            timestamp,
-            score,
-            ..
-        } = value;
-        Self {
-            id,
-            timestamp,
-            score,
-        }
-    }
-}
-
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "UPPERCASE")]
-struct RawDispatch {
-    #[serde(rename = "@id")]
-    id: u32,
-    title: String,
-    author: String,
-    category: String,
-    subcategory: String,
-    created: u64,
-    edited: u64,
-    views: u32,
-    score: u32,
-}
-
-impl TryFrom<RawDispatch> for Dispatch {
-    type Error = IntoNationError;
-
-    fn try_from(value: RawDispatch) -> Result<Self, Self::Error> {
-        Ok(Dispatch {
-            id: value.id,
-            title: value.title,
-            author: pretty_name(value.author),
-            category: try_into_dispatch_category(&value.category, &value.subcategory)?,
-            created: value.created,
-            edited: NonZeroU64::try_from(value.edited).ok(), // field is 0 if never edited
-            views: value.views,
-            score: value.score,
-        })
-    }
-}
-
 //noinspection SpellCheckingInspection
 #[derive(Debug, Deserialize)]
 struct RawFreedoms {
@@ -357,18 +280,26 @@ struct RawFreedomScores {
     political_freedom: u8,
 }
 
-impl From<RawFreedomScores> for FreedomScores {
-    fn from(value: RawFreedomScores) -> Self {
+impl TryFrom<RawFreedomScores> for FreedomScores {
+    type Error = IntoNationError;
+
+    fn try_from(value: RawFreedomScores) -> Result<Self, Self::Error> {
         let RawFreedomScores {
             civil_rights,
             economy,
             political_freedom,
         } = value;
-        Self {
-            civil_rights,
-            economy,
-            political_freedom,
-        }
+        let score = |score: u8, path: &'static str| {
+            FreedomScore::new(score).ok_or_else(|| IntoNationError::BadFieldError {
+                field: path,
+                value: score.to_string(),
+            })
+        };
+        Ok(Self {
+            civil_rights: score(civil_rights, "NATION/FREEDOMSCORES/CIVILRIGHTS")?,
+            economy: score(economy, "NATION/FREEDOMSCORES/ECONOMY")?,
+            political_freedom: score(political_freedom, "NATION/FREEDOMSCORES/POLITICALFREEDOM")?,
+        })
     }
 }
 
@@ -455,75 +386,115 @@ impl From<RawSectors> for Sectors {
     }
 }
 
-fn try_into_dispatch_category(
-    main_category: &str,
-    sub_category: &str,
-) -> Result<DispatchCategory, IntoNationError> {
-    match main_category {
-        "Factbook" => Ok(DispatchCategory::Factbook(match sub_category {
-            "Overview" => Ok(FactbookCategory::Overview),
-            "History" => Ok(FactbookCategory::History),
-            "Geography" => Ok(FactbookCategory::Geography),
-            "Culture" => Ok(FactbookCategory::Culture),
-            "Politics" => Ok(FactbookCategory::Politics),
-            "Legislation" => Ok(FactbookCategory::Legislation),
-            "Religion" => Ok(FactbookCategory::Religion),
-            "Military" => Ok(FactbookCategory::Military),
-            "Economy" => Ok(FactbookCategory::Economy),
-            "International" => Ok(FactbookCategory::International),
-            "Trivia" => Ok(FactbookCategory::Trivia),
-            "Miscellaneous" => Ok(FactbookCategory::Miscellaneous),
-            other => Err(IntoNationError::BadDispatchCategory(format!(
-                "Factbook:{other}"
-            ))),
-        }?)),
-        "Bulletin" => Ok(DispatchCategory::Bulletin(match sub_category {
-            "Policy" => Ok(BulletinCategory::Policy),
-            "News" => Ok(BulletinCategory::News),
-            "Opinion" => Ok(BulletinCategory::Opinion),
-            "Campaign" => Ok(BulletinCategory::Campaign),
-            other => Err(IntoNationError::BadDispatchCategory(format!(
-                "Bulletin:{other}"
-            ))),
-        }?)),
-        "Account" => Ok(DispatchCategory::Account(match sub_category {
-            "Military" => Ok(AccountCategory::Military),
-            "Trade" => Ok(AccountCategory::Trade),
-            "Sport" => Ok(AccountCategory::Sport),
-            "Drama" => Ok(AccountCategory::Drama),
-            "Diplomacy" => Ok(AccountCategory::Diplomacy),
-            "Science" => Ok(AccountCategory::Science),
-            "Culture" => Ok(AccountCategory::Culture),
-            "Other" => Ok(AccountCategory::Other),
-            other => Err(IntoNationError::BadDispatchCategory(format!(
-                "Account:{other}"
-            ))),
-        }?)),
-        "Meta" => Ok(DispatchCategory::Meta(match sub_category {
-            "Gameplay" => Ok(MetaCategory::Gameplay),
-            "Reference" => Ok(MetaCategory::Reference),
-            other => Err(IntoNationError::BadDispatchCategory(format!(
-                "Meta:{other}"
-            ))),
-        }?)),
-        other => Err(IntoNationError::BadDispatchCategory(other.to_string())),
-    }
-}
-
 impl Nation {
     /// Converts the XML response from NationStates to a [`Nation`].
     pub fn from_xml(xml: &str) -> Result<Self, IntoNationError> {
-        Self::try_from(quick_xml::de::from_str::<RawNation>(xml)?)
+        Self::try_from(from_xml_str::<RawNation>(xml)?)
+    }
+
+    /// Converts the XML response from NationStates, as bytes, to a [`Nation`].
+    ///
+    /// Fails with [`IntoNationError::DeserializationError`] if the response is not UTF-8.
+    pub fn from_bytes(xml: &[u8]) -> Result<Self, IntoNationError> {
+        Self::try_from(from_xml_bytes::<RawNation>(xml)?)
+    }
+
+    /// Reads the XML response from NationStates and converts it to a [`Nation`],
+    /// without reading the whole response into memory first.
+    ///
+    /// Unlike [`Nation::from_xml`], errors do not include the value that could not be parsed.
+    pub fn from_reader(reader: impl BufRead) -> Result<Self, IntoNationError> {
+        Self::try_from(from_xml_reader::<RawNation>(reader, "NATION")?)
+    }
+
+    /// Reads a response from NationStates and converts it to a [`Nation`],
+    /// parsing the body as it arrives instead of reading all of it into memory first.
+    ///
+    /// As with [`Self::from_reader`], errors do not include the value that could not be parsed.
+    ///
+    /// Fails with [`ClientError::ReqwestError`] if the API responded with an error status
+    /// or the body could not be read,
+    /// and with [`ClientError::ParseError`] if the body could not be parsed.
+    #[cfg(feature = "client")]
+    pub async fn from_response(response: Response) -> Result<Self, ClientError> {
+        parse_response(response, Self::from_reader).await
+    }
+
+    /// Converts the XML response from NationStates to a [`Nation`],
+    /// leaving out fields whose values could not be understood instead of failing.
+    ///
+    /// Each field that was left out has a [`ParseWarning`] explaining why.
+    /// Parsing still fails if the XML itself is malformed or the nation has no name.
+    /// ```rust
+    /// # use crustacean_states::parsers::nation::Nation;
+    /// let xml = r#"<NATION id="aramos"><GAVOTE>MAYBE</GAVOTE><FLAG>flag.svg</FLAG></NATION>"#;
+    /// assert!(Nation::from_xml(xml).is_err());
+    /// let (nation, warnings) = Nation::from_xml_lenient(xml).unwrap();
+    /// assert_eq!(nation.flag.as_deref(), Some("flag.svg"));
+    /// assert!(nation.ga_vote.is_none());
+    /// assert_eq!(warnings[0].field, "ga_vote");
+    /// ```
+    pub fn from_xml_lenient(xml: &str) -> Result<(Self, Vec<ParseWarning>), IntoNationError> {
+        let mut warnings = vec![];
+        let nation = Self::from_raw(
+            from_xml_str::<RawNation>(xml)?,
+            &mut Leniency(Some(&mut warnings)),
+        )?;
+        Ok((nation, warnings))
     }
 }
 
-impl TryFrom<RawNation> for Nation {
+/// Whether a field that cannot be converted fails the whole parse.
+struct Leniency<'w>(Option<&'w mut Vec<ParseWarning>>);
+
+impl Leniency<'_> {
+    /// Passes the field through if it was converted.
+    /// Otherwise, when lenient, records a warning and leaves the field out.
+    fn field<T>(
+        &mut self,
+        field: &'static str,
+        result: Result<Option<T>, IntoNationError>,
+    ) -> Result<Option<T>, IntoNationError> {
+        match (result, &mut self.0) {
+            (Err(error), Some(warnings)) => {
+                warnings.push(ParseWarning { field, error });
+                Ok(None)
+            }
+            (result, _) => result,
+        }
+    }
+}
+
+impl NSResponse for Nation {
     type Error = IntoNationError;
 
-    fn try_from(value: RawNation) -> Result<Self, Self::Error> {
+    fn from_xml(xml: &str) -> Result<Self, Self::Error> {
+        Nation::from_xml(xml)
+    }
+}
+
+impl FromStr for Nation {
+    type Err = IntoNationError;
+
+    /// Same as [`Nation::from_xml`].
+    fn from_str(xml: &str) -> Result<Self, Self::Err> {
+        Nation::from_xml(xml)
+    }
+}
+
+impl TryFrom<RawNation<'_>> for Nation {
+    type Error = IntoNationError;
+
+    fn try_from(value: RawNation<'_>) -> Result<Self, Self::Error> {
+        Self::from_raw(value, &mut Leniency(None))
+    }
+}
+
+impl Nation {
+    fn from_raw(value: RawNation<'_>, lenient: &mut Leniency) -> Result<Self, IntoNationError> {
         let name = match (value.name, value.id) {
             (Some(n), _) => Ok(n),
-            (None, Some(i)) => Ok(pretty_name(i)),
+            (None, Some(i)) => Ok(pretty_name(&*i)),
             (None, None) => Err(IntoNationError::NoNameError),
         }?;
 
@@ -531,24 +502,25 @@ impl TryFrom<RawNation> for Nation {
             .happenings
             .map(|h| h.inner.into_iter().map(Event::from).collect());
 
-        let wa_status = match value.unstatus {
-            Some(s) => match s.as_str() {
-                "WA Delegate" => Ok(Some(WAStatus::Delegate)),
-                "WA Member" => Ok(Some(WAStatus::Member)),
-                "Non-member" => Ok(Some(WAStatus::NonMember)),
-                other => Err(IntoNationError::BadWAStatusError(other.to_string())),
-            },
-            None => Ok(None),
-        }?;
+        let wa_status = value.unstatus.map(|s| {
+            let Ok(status) = s.parse::<WAStatus>();
+            status
+        });
 
-        let ga_vote = match wa_status {
-            Some(WAStatus::NonMember) => None,
-            _ => value.gavote.map(WAVote::try_from).transpose()?,
-        };
-        let sc_vote = match wa_status {
-            Some(WAStatus::NonMember) => None,
-            _ => value.scvote.map(WAVote::try_from).transpose()?,
-        };
+        let ga_vote = lenient.field(
+            "ga_vote",
+            value
+                .gavote
+                .map(|v| WAVoteState::parse(&v, wa_status.as_ref(), WACouncil::GeneralAssembly))
+                .transpose(),
+        )?;
+        let sc_vote = lenient.field(
+            "sc_vote",
+            value
+                .scvote
+                .map(|v| WAVoteState::parse(&v, wa_status.as_ref(), WACouncil::SecurityCouncil))
+                .transpose(),
+        )?;
 
         Ok(Self {
             name,
@@ -558,11 +530,10 @@ impl TryFrom<RawNation> for Nation {
             category: value.category,
             wa_status,
             endorsements: value.endorsements.as_ref().map(|e| {
-                if !e.is_empty() {
-                    e.split(',').map(pretty_name).collect()
-                } else {
-                    vec![]
-                }
+                e.split(',')
+                    .filter(|n| !n.is_empty())
+                    .map(pretty_name)
+                    .collect()
             }),
             issues_answered: value.issues_answered,
             freedom: value.freedom.map(Freedoms::from),
@@ -583,7 +554,10 @@ impl TryFrom<RawNation> for Nation {
             last_login: value.lastlogin,
             last_activity: value.lastactivity,
             influence: value.influence,
-            freedom_scores: value.freedomscores.map(FreedomScores::from),
+            freedom_scores: lenient.field(
+                "freedom_scores",
+                value.freedomscores.map(FreedomScores::try_from).transpose(),
+            )?,
             public_sector: value.publicsector,
             deaths: value
                 .deaths
@@ -597,50 +571,67 @@ impl TryFrom<RawNation> for Nation {
             admirable: value.admirable,
             admirables: value.admirables.map(|a| a.inner),
             animal_trait: value.animaltrait,
-            banner: value.banner.map(BannerId::try_from).transpose()?,
-            banners: value
-                .banners
-                .map(|a| {
-                    a.inner
-                        .into_iter()
-                        .map(BannerId::try_from)
-                        .collect::<Result<Vec<_>, _>>()
-                })
-                .transpose()?,
-            census: value
-                .census
-                .map(|c| match c.inner.first() {
-                    Some(f) if f.timestamp.is_some() => Ok(CensusData::Historical(
-                        c.inner
+            banner: lenient.field(
+                "banner",
+                value
+                    .banner
+                    .map(BannerId::try_from)
+                    .transpose()
+                    .map_err(IntoNationError::from),
+            )?,
+            banners: lenient.field(
+                "banners",
+                value
+                    .banners
+                    .map(|a| {
+                        a.inner
                             .into_iter()
-                            .map(CensusHistoricalData::from)
-                            .collect(),
-                    )),
-                    Some(_) => Ok(CensusData::Current(
-                        c.inner.into_iter().map(CensusCurrentData::from).collect(),
-                    )),
-                    None => Err(IntoNationError::NoCensusDataError),
-                })
-                .transpose()?,
+                            .map(|b| BannerId::try_from(b).map_err(IntoNationError::from))
+                            .collect::<Result<Vec<_>, _>>()
+                    })
+                    .transpose(),
+            )?,
+            census: lenient.field(
+                "census",
+                value
+                    .census
+                    .map(|c| {
+                        c.into_census_data()
+                            .ok_or(IntoNationError::NoCensusDataError)
+                    })
+                    .transpose(),
+            )?,
             crime: value.crime,
-            dispatch_list: value
-                .dispatchlist
-                .map(|v| {
-                    v.inner
-                        .into_iter()
-                        .map(Dispatch::try_from)
-                        .collect::<Result<Vec<_>, _>>()
-                })
-                .transpose()?,
-            factbook_list: value
-                .factbooklist
-                .map(|v| {
-                    v.inner
-                        .into_iter()
-                        .map(Dispatch::try_from)
-                        .collect::<Result<Vec<_>, _>>()
-                })
-                .transpose()?,
+            dispatch_list: lenient.field(
+                "dispatch_list",
+                value
+                    .dispatchlist
+                    .map(|v| {
+                        v.inner
+                            .into_iter()
+                            .map(|d| {
+                                d.try_into_dispatch()
+                                    .map_err(IntoNationError::BadDispatchCategory)
+                            })
+                            .collect::<Result<Vec<_>, _>>()
+                    })
+                    .transpose(),
+            )?,
+            factbook_list: lenient.field(
+                "factbook_list",
+                value
+                    .factbooklist
+                    .map(|v| {
+                        v.inner
+                            .into_iter()
+                            .map(|d| {
+                                d.try_into_dispatch()
+                                    .map_err(IntoNationError::BadDispatchCategory)
+                            })
+                            .collect::<Result<Vec<_>, _>>()
+                    })
+                    .transpose(),
+            )?,
             founded_time: value.foundedtime.map(MaybeSystemTime::from),
             ga_vote,
             gdp: value.gdp,
@@ -657,15 +648,18 @@ impl TryFrom<RawNation> for Nation {
             //     [first.to_string(), second.to_string(), third.to_string()]
             // })
             notables: value.notables.map(|n| n.inner),
-            policies: value
-                .policies
-                .map(|v| {
-                    v.inner
-                        .into_iter()
-                        .map(Policy::try_from)
-                        .collect::<Result<Vec<_>, _>>()
-                })
-                .transpose()?,
+            policies: lenient.field(
+                "policies",
+                value
+                    .policies
+                    .map(|v| {
+                        v.inner
+                            .into_iter()
+                            .map(Policy::try_from)
+                            .collect::<Result<Vec<_>, _>>()
+                    })
+                    .transpose(),
+            )?,
             poorest: value.poorest,
             regional_census: value.rcensus,
             richest: value.richest,
@@ -676,22 +670,28 @@ impl TryFrom<RawNation> for Nation {
             //     let v = s.split(", ").collect::<Vec<_>>();
             //     [v[0].to_string(), v[1].to_string()]
             // })
-            tg_can_recruit: value
-                .tgcanrecruit
-                .map(|x| match x {
-                    0 => Ok(false),
-                    1 => Ok(true),
-                    e => Err(IntoNationError::BadBooleanError(e)),
-                })
-                .transpose()?,
-            tg_can_campaign: value
-                .tgcancampaign
-                .map(|x| match x {
-                    0 => Ok(false),
-                    1 => Ok(true),
-                    e => Err(IntoNationError::BadBooleanError(e)),
-                })
-                .transpose()?,
+            tg_can_recruit: lenient.field(
+                "tg_can_recruit",
+                value
+                    .tgcanrecruit
+                    .map(|x| match x {
+                        0 => Ok(false),
+                        1 => Ok(true),
+                        e => Err(IntoNationError::BadBooleanError(e)),
+                    })
+                    .transpose(),
+            )?,
+            tg_can_campaign: lenient.field(
+                "tg_can_campaign",
+                value
+                    .tgcancampaign
+                    .map(|x| match x {
+                        0 => Ok(false),
+                        1 => Ok(true),
+                        e => Err(IntoNationError::BadBooleanError(e)),
+                    })
+                    .transpose(),
+            )?,
             world_census: value.wcensus,
         })
     }
@@ -700,31 +700,75 @@ impl TryFrom<RawNation> for Nation {
 impl StandardNation {
     /// Converts the XML response from NationStates to a [`Nation`].
     pub fn from_xml(xml: &str) -> Result<Self, IntoNationError> {
-        Self::try_from(quick_xml::de::from_str::<RawStandardNation>(xml)?)
+        Self::try_from(from_xml_str::<RawStandardNation>(xml)?)
+    }
+
+    /// Converts the XML response from NationStates, as bytes, to a [`StandardNation`].
+    ///
+    /// Fails with [`IntoNationError::DeserializationError`] if the response is not UTF-8.
+    pub fn from_bytes(xml: &[u8]) -> Result<Self, IntoNationError> {
+        Self::try_from(from_xml_bytes::<RawStandardNation>(xml)?)
+    }
+
+    /// Reads the XML response from NationStates and converts it to a [`StandardNation`],
+    /// without reading the whole response into memory first.
+    ///
+    /// Unlike [`StandardNation::from_xml`], errors do not include the value that could not be parsed.
+    pub fn from_reader(reader: impl BufRead) -> Result<Self, IntoNationError> {
+        Self::try_from(from_xml_reader::<RawStandardNation>(reader, "NATION")?)
+    }
+
+    /// Reads a response from NationStates and converts it to a [`StandardNation`],
+    /// parsing the body as it arrives instead of reading all of it into memory first.
+    ///
+    /// As with [`Self::from_reader`], errors do not include the value that could not be parsed.
+    ///
+    /// Fails with [`ClientError::ReqwestError`] if the API responded with an error status
+    /// or the body could not be read,
+    /// and with [`ClientError::ParseError`] if the body could not be parsed.
+    #[cfg(feature = "client")]
+    pub async fn from_response(response: Response) -> Result<Self, ClientError> {
+        parse_response(response, Self::from_reader).await
+    }
+}
+
+impl NSResponse for StandardNation {
+    type Error = IntoNationError;
+
+    fn from_xml(xml: &str) -> Result<Self, Self::Error> {
+        StandardNation::from_xml(xml)
+    }
+}
+
+impl FromStr for StandardNation {
+    type Err = IntoNationError;
+
+    /// Same as [`StandardNation::from_xml`].
+    fn from_str(xml: &str) -> Result<Self, Self::Err> {
+        StandardNation::from_xml(xml)
     }
 }
 
-impl TryFrom<RawStandardNation> for StandardNation {
+impl TryFrom<RawStandardNation<'_>> for StandardNation {
     type Error = IntoNationError;
 
-    fn try_from(value: RawStandardNation) -> Result<Self, Self::Error> {
+    fn try_from(value: RawStandardNation<'_>) -> Result<Self, Self::Error> {
         Ok(StandardNation {
             name: value.name,
             kind: value.kind,
             full_name: value.fullname,
             motto: value.motto,
             category: value.category,
-            wa_status: match value.unstatus.as_str() {
-                "WA Delegate" => Ok(WAStatus::Delegate),
-                "WA Member" => Ok(WAStatus::Member),
-                "Non-member" => Ok(WAStatus::NonMember),
-                other => Err(IntoNationError::BadWAStatusError(other.to_string())),
-            }?,
-            endorsements: if !value.endorsements.is_empty() {
-                value.endorsements.split(',').map(pretty_name).collect()
-            } else {
-                vec![]
+            wa_status: {
+                let Ok(status) = value.unstatus.parse();
+                status
             },
+            endorsements: value
+                .endorsements
+                .split(',')
+                .filter(|n| !n.is_empty())
+                .map(pretty_name)
+                .collect(),
             issues_answered: value.issues_answered,
             freedom: value.freedom.into(),
             region: value.region,
@@ -744,7 +788,7 @@ impl TryFrom<RawStandardNation> for StandardNation {
             last_login: value.lastlogin,
             last_activity: value.lastactivity,
             influence: value.influence,
-            freedom_scores: value.freedomscores.into(),
+            freedom_scores: value.freedomscores.try_into()?,
             public_sector: value.publicsector,
             deaths: value.deaths.inner.into_iter().map(Cause::from).collect(),
             leader: DefaultOrCustom::leader(value.leader),
@@ -756,3 +800,275 @@ impl TryFrom<RawStandardNation> for StandardNation {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn activity_levels() {
+        use crate::{models::timestamp::DAY, parsers::nation::ActivityLevel};
+
+        let nation =
+            Nation::from_xml(r#"<NATION id="aramos"><LASTLOGIN>864000</LASTLOGIN></NATION>"#)
+                .unwrap();
+        assert_eq!(
+            nation.activity_level(10 * DAY),
+            Some(ActivityLevel::ActiveToday)
+        );
+        assert_eq!(
+            nation.activity_level(15 * DAY),
+            Some(ActivityLevel::ThisWeek)
+        );
+        assert_eq!(
+            nation.activity_level(24 * DAY),
+            Some(ActivityLevel::NearCTE(14))
+        );
+        assert_eq!(
+            nation.activity_level(60 * DAY),
+            Some(ActivityLevel::NearCTE(0))
+        );
+        assert_eq!(
+            Nation::from_xml(r#"<NATION id="aramos"></NATION>"#)
+                .unwrap()
+                .activity_level(0),
+            None
+        );
+    }
+
+    #[test]
+    fn standard_nation_conversion() {
+        let nation = Nation::from_xml(
+            r#"<NATION id="aramos"><POPULATION>42</POPULATION><ANIMAL>dolphin</ANIMAL></NATION>"#,
+        )
+        .unwrap();
+        let missing = StandardNation::try_from(nation).unwrap_err().shards;
+        assert!(missing.contains(&"type".to_string()));
+        assert!(missing.contains(&"dbid".to_string()));
+        assert!(!missing.contains(&"population".to_string()));
+        assert!(!missing.contains(&"animal".to_string()));
+    }
+
+    #[test]
+    fn freedom_scores() {
+        let xml = |economy: u8| {
+            format!(
+                r#"<NATION id="aramos"><FREEDOMSCORES><CIVILRIGHTS>42</CIVILRIGHTS><ECONOMY>{economy}</ECONOMY><POLITICALFREEDOM>0</POLITICALFREEDOM></FREEDOMSCORES></NATION>"#
+            )
+        };
+        let scores = Nation::from_xml(&xml(100)).unwrap().freedom_scores.unwrap();
+        assert_eq!(scores.civil_rights.as_u8(), 42);
+        assert_eq!(scores.economy, FreedomScore::MAX);
+        assert!(scores.political_freedom < scores.civil_rights);
+        assert!(matches!(
+            Nation::from_xml(&xml(101)),
+            Err(IntoNationError::BadFieldError { field: "NATION/FREEDOMSCORES/ECONOMY", value })
+                if value == "101"
+        ));
+        assert!(matches!(
+            FreedomScore::try_from(101),
+            Err(IntoNationError::BadFieldError {
+                field: "NATION/FREEDOMSCORES",
+                ..
+            })
+        ));
+        assert_eq!(
+            crate::parsers::ParseError::from(IntoNationError::MissingShardError("Motto"))
+                .field
+                .as_deref(),
+            Some("NATION/MOTTO")
+        );
+    }
+
+    #[test]
+    fn borrowed_text() {
+        use std::borrow::Cow;
+
+        let raw = from_xml_str::<RawNation>(
+            r#"<NATION id="aramos"><ENDORSEMENTS>a,b</ENDORSEMENTS><UNSTATUS>WA &amp; Co</UNSTATUS></NATION>"#,
+        )
+        .unwrap();
+        assert!(matches!(raw.id, Some(Text(Cow::Borrowed("aramos")))));
+        assert!(matches!(raw.endorsements, Some(Text(Cow::Borrowed("a,b")))));
+        assert!(matches!(raw.unstatus, Some(Text(Cow::Owned(s))) if s == "WA & Co"));
+    }
+
+    #[test]
+    fn from_bytes_and_reader() {
+        let xml =
+            r#"<NATION id="aramos"><ANIMAL>dolphin</ANIMAL><POPULATION>42</POPULATION></NATION>"#;
+        for nation in [
+            Nation::from_bytes(xml.as_bytes()).unwrap(),
+            Nation::from_reader(xml.as_bytes()).unwrap(),
+        ] {
+            assert_eq!(nation.name, "Aramos");
+            assert_eq!(nation.animal.as_deref(), Some("dolphin"));
+            assert_eq!(nation.population, Some(42));
+        }
+        assert!(matches!(
+            Nation::from_bytes(b"<NATION id=\"aramos\"><ANIMAL>\xff</ANIMAL></NATION>"),
+            Err(IntoNationError::DeserializationError { .. })
+        ));
+        match Nation::from_reader(
+            r#"<NATION id="aramos"><POPULATION>lots</POPULATION></NATION>"#.as_bytes(),
+        ) {
+            Err(IntoNationError::DeserializationError { source }) => {
+                assert_eq!(source.path, "NATION/POPULATION");
+                assert_eq!(source.snippet, None);
+            }
+            other => panic!("expected a deserialization error, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "client")]
+    async fn from_response() {
+        let response = |status: u16, body: &'static str| {
+            Response::from(http::Response::builder().status(status).body(body).unwrap())
+        };
+        let nation = Nation::from_response(response(
+            200,
+            r#"<NATION id="aramos"><ANIMAL>dolphin</ANIMAL></NATION>"#,
+        ))
+        .await
+        .unwrap();
+        assert_eq!(nation.animal.as_deref(), Some("dolphin"));
+        assert!(matches!(
+            Nation::from_response(response(404, "Unknown nation")).await,
+            Err(ClientError::ReqwestError { .. })
+        ));
+        assert!(matches!(
+            Nation::from_response(response(200, "<NATION>")).await,
+            Err(ClientError::ParseError { .. })
+        ));
+        let cut_off = reqwest::Body::wrap_stream(futures::stream::iter([
+            Ok(r#"<NATION id="aramos"><ANIMAL>dol"#),
+            Err(std::io::Error::from(std::io::ErrorKind::ConnectionReset)),
+        ]));
+        assert!(matches!(
+            Nation::from_response(Response::from(http::Response::new(cut_off))).await,
+            Err(ClientError::ReqwestError { .. })
+        ));
+    }
+
+    #[test]
+    fn error_path() {
+        let error = |xml: &str| match Nation::from_xml(xml) {
+            Err(IntoNationError::DeserializationError { source }) => (source.path, source.snippet),
+            other => panic!("expected a deserialization error, got {other:?}"),
+        };
+        assert_eq!(
+            error(
+                r#"<NATION id="aramos"><CENSUS><SCALE id="0"><SCORE>1</SCORE></SCALE><SCALE id="1"><SCORE>lots</SCORE></SCALE></CENSUS></NATION>"#
+            ),
+            (
+                String::from("NATION/CENSUS/SCALE[1]/SCORE"),
+                Some(String::from("lots"))
+            )
+        );
+        assert_eq!(
+            error(r#"<NATION id="aramos"><CENSUS><SCALE id="300"></SCALE></CENSUS></NATION>"#),
+            (
+                String::from("NATION/CENSUS/SCALE/@id"),
+                Some(String::from("300"))
+            )
+        );
+        let (path, snippet) = error(&format!(
+            r#"<NATION id="aramos"><POPULATION>{}</POPULATION></NATION>"#,
+            "9".repeat(100)
+        ));
+        assert_eq!(path, "NATION/POPULATION");
+        assert_eq!(snippet, Some(format!("{}...", "9".repeat(64))));
+    }
+
+    #[test]
+    fn wa_votes() {
+        let vote = |xml: &str| Nation::from_xml(xml).unwrap().ga_vote;
+        assert_eq!(
+            vote(r#"<NATION id="aramos"><GAVOTE>UNDECIDED</GAVOTE></NATION>"#),
+            Some(WAVoteState::Unknown)
+        );
+        assert_eq!(
+            vote(r#"<NATION id="aramos"><GAVOTE>FOR</GAVOTE></NATION>"#),
+            Some(WAVoteState::For)
+        );
+        assert_eq!(
+            vote(
+                r#"<NATION id="aramos"><UNSTATUS>Non-member</UNSTATUS><GAVOTE>UNDECIDED</GAVOTE></NATION>"#
+            ),
+            Some(WAVoteState::NonMember)
+        );
+        assert_eq!(
+            vote(
+                r#"<NATION id="aramos"><UNSTATUS>WA Member</UNSTATUS><GAVOTE>UNDECIDED</GAVOTE></NATION>"#
+            ),
+            Some(WAVoteState::Undecided)
+        );
+        assert_eq!(vote(r#"<NATION id="aramos"></NATION>"#), None);
+        assert!(matches!(
+            Nation::from_xml(r#"<NATION id="aramos"><SCVOTE>MAYBE</SCVOTE></NATION>"#),
+            Err(IntoNationError::BadWAVote {
+                council: WACouncil::SecurityCouncil,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn unknown_wa_status() {
+        let nation = Nation::from_xml(
+            r#"<NATION id="aramos"><UNSTATUS>WA Observer</UNSTATUS><GAVOTE>UNDECIDED</GAVOTE></NATION>"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            nation.wa_status,
+            Some(WAStatus::Unknown(status)) if status == "WA Observer"
+        ));
+        assert_eq!(nation.ga_vote, Some(WAVoteState::Unknown));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let nation = Nation::from_xml(
+            r#"<NATION id="aramos"><ENDORSEMENTS>testlandia,maxtopia</ENDORSEMENTS><FOUNDED>0</FOUNDED><FOUNDEDTIME>1500000000</FOUNDEDTIME><UNSTATUS>WA Member</UNSTATUS></NATION>"#,
+        )
+        .unwrap();
+        let json = serde_json::to_value(&nation).unwrap();
+        assert_eq!(json["name"], "Aramos");
+        assert_eq!(
+            json["endorsements"],
+            serde_json::json!(["Testlandia", "Maxtopia"])
+        );
+        assert_eq!(json["founded"], "0");
+        assert_eq!(json["founded_time"], 1500000000);
+        assert_eq!(json["wa_status"], "Member");
+
+        let reloaded: Nation = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(
+            reloaded.endorsements.as_ref().unwrap(),
+            &["Testlandia", "Maxtopia"]
+        );
+        assert!(matches!(
+            reloaded.founded,
+            Some(MaybeRelativeTime::Antiquity)
+        ));
+        assert_eq!(serde_json::to_value(&reloaded).unwrap(), json);
+        assert!(serde_json::from_value::<FreedomScore>(serde_json::json!(101)).is_err());
+    }
+
+    #[cfg(feature = "schemars")]
+    #[test]
+    fn json_schema() {
+        let schema = serde_json::to_value(schemars::schema_for!(Nation)).unwrap();
+        let properties = &schema["properties"];
+        assert_eq!(properties["name"]["type"], "string");
+        assert_eq!(
+            properties["endorsements"]["type"],
+            serde_json::json!(["array", "null"])
+        );
+        assert_eq!(properties["endorsements"]["items"]["type"], "string");
+        let founded_time = &schema["$defs"]["MaybeSystemTime"];
+        assert_eq!(founded_time["type"], "integer");
+    }
+}
@@ -0,0 +1,669 @@
+#[cfg(feature = "client")]
+use crate::client::{parse_response, ClientError};
+use crate::{
+    models::name::NameList,
+    parsers::{
+        empty_as_none, from_xml_bytes, from_xml_reader, from_xml_str,
+        happenings::Event,
+        region::{
+            AuthoritySet, Embassy, EmbassyKind, EmbassyPostingRights, IntoRegionError, Message,
+            MessageStatus, Officer, Region, RegionWAVote, WABadge, WABadgeKind,
+        },
+        MaybeRelativeTime, MaybeSystemTime, NSResponse, RawCensus, RawCensusRanks, RawHappenings,
+        RawPoll, Text,
+    },
+    pretty_name,
+    shards::region::Tag,
+};
+#[cfg(feature = "client")]
+use reqwest::Response;
+use serde::Deserialize;
+use std::io::BufRead;
+use std::num::NonZeroU64;
+use std::str::FromStr;
+
+//noinspection SpellCheckingInspection
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+struct RawRegion<'x> {
+    #[serde(rename = "@id", borrow)]
+    id: Option<Text<'x>>,
+    name: Option<String>,
+    #[serde(borrow)]
+    banlist: Option<Text<'x>>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    banner: Option<u32>,
+    bannerby: Option<String>,
+    bannerurl: Option<String>,
+    census: Option<RawCensus>,
+    censusranks: Option<RawCensusRanks>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    dbid: Option<u32>,
+    delegate: Option<String>,
+    #[serde(borrow)]
+    delegateauth: Option<Text<'x>>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    delegatevotes: Option<u32>,
+    #[serde(borrow)]
+    dispatches: Option<Text<'x>>,
+    embassies: Option<RawEmbassies>,
+    embassyrmb: Option<String>,
+    factbook: Option<String>,
+    flag: Option<String>,
+    founded: Option<String>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    foundedtime: Option<u64>,
+    founder: Option<String>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    frontier: Option<u8>,
+    gavote: Option<RawRegionWAVote>,
+    governor: Option<String>,
+    happenings: Option<RawHappenings>,
+    history: Option<RawHappenings>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    lastupdate: Option<u64>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    lastmajorupdate: Option<u64>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    lastminorupdate: Option<u64>,
+    messages: Option<RawMessages>,
+    #[serde(borrow)]
+    nations: Option<Text<'x>>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    numnations: Option<u32>,
+    #[serde(alias = "NUMWANATIONS")]
+    #[serde(default, deserialize_with = "empty_as_none")]
+    numunnations: Option<u32>,
+    officers: Option<RawOfficers>,
+    poll: Option<RawPoll>,
+    power: Option<String>,
+    scvote: Option<RawRegionWAVote>,
+    tags: Option<RawTags>,
+    wabadges: Option<RawWABadges>,
+    #[serde(alias = "WANATIONS", borrow)]
+    unnations: Option<Text<'x>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawEmbassies {
+    #[serde(rename = "EMBASSY", default)]
+    inner: Vec<RawEmbassy>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawEmbassy {
+    #[serde(rename = "@type")]
+    kind: Option<String>,
+    #[serde(rename = "$text")]
+    region: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+struct RawRegionWAVote {
+    #[serde(rename = "FOR")]
+    for_votes: u32,
+    #[serde(rename = "AGAINST")]
+    against_votes: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawMessages {
+    #[serde(rename = "POST", default)]
+    inner: Vec<RawMessage>,
+}
+
+//noinspection SpellCheckingInspection
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+struct RawMessage {
+    #[serde(rename = "@id")]
+    id: u32,
+    timestamp: u64,
+    nation: String,
+    status: u8,
+    suppressor: Option<String>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    edited: Option<u64>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    likes: Option<u32>,
+    likers: Option<String>,
+    embassy: Option<String>,
+    message: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawOfficers {
+    #[serde(rename = "OFFICER", default)]
+    inner: Vec<RawOfficer>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+struct RawOfficer {
+    nation: String,
+    office: String,
+    authority: String,
+    time: u64,
+    by: String,
+    order: i32,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawTags {
+    #[serde(rename = "TAG", default)]
+    inner: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawWABadges {
+    #[serde(rename = "WABADGE", default)]
+    inner: Vec<RawWABadge>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawWABadge {
+    #[serde(rename = "@type")]
+    kind: String,
+    #[serde(rename = "$text")]
+    resolution: u16,
+}
+
+impl Region {
+    /// Converts the XML response from NationStates to a [`Region`].
+    pub fn from_xml(xml: &str) -> Result<Self, IntoRegionError> {
+        Self::try_from(from_xml_str::<RawRegion>(xml)?)
+    }
+
+    /// Converts the XML response from NationStates, as bytes, to a [`Region`].
+    ///
+    /// Fails with [`IntoRegionError::DeserializationError`] if the response is not UTF-8.
+    pub fn from_bytes(xml: &[u8]) -> Result<Self, IntoRegionError> {
+        Self::try_from(from_xml_bytes::<RawRegion>(xml)?)
+    }
+
+    /// Reads the XML response from NationStates and converts it to a [`Region`],
+    /// without reading the whole response into memory first.
+    ///
+    /// Unlike [`Region::from_xml`], errors do not include the value that could not be parsed.
+    pub fn from_reader(reader: impl BufRead) -> Result<Self, IntoRegionError> {
+        Self::try_from(from_xml_reader::<RawRegion>(reader, "REGION")?)
+    }
+
+    /// Reads a response from NationStates and converts it to a [`Region`],
+    /// parsing the body as it arrives instead of reading all of it into memory first.
+    ///
+    /// As with [`Self::from_reader`], errors do not include the value that could not be parsed.
+    ///
+    /// Fails with [`ClientError::ReqwestError`] if the API responded with an error status
+    /// or the body could not be read,
+    /// and with [`ClientError::ParseError`] if the body could not be parsed.
+    #[cfg(feature = "client")]
+    pub async fn from_response(response: Response) -> Result<Self, ClientError> {
+        parse_response(response, Self::from_reader).await
+    }
+}
+
+impl NSResponse for Region {
+    type Error = IntoRegionError;
+
+    fn from_xml(xml: &str) -> Result<Self, Self::Error> {
+        Region::from_xml(xml)
+    }
+}
+
+impl FromStr for Region {
+    type Err = IntoRegionError;
+
+    /// Same as [`Region::from_xml`].
+    fn from_str(xml: &str) -> Result<Self, Self::Err> {
+        Region::from_xml(xml)
+    }
+}
+
+impl TryFrom<RawRegion<'_>> for Region {
+    type Error = IntoRegionError;
+
+    fn try_from(value: RawRegion<'_>) -> Result<Self, Self::Error> {
+        let name = match (value.name, value.id) {
+            (Some(n), _) => Ok(n),
+            (None, Some(i)) => Ok(pretty_name(&*i)),
+            (None, None) => Err(IntoRegionError::NoNameError),
+        }?;
+
+        let census = value
+            .census
+            .map(|c| {
+                c.into_census_data()
+                    .ok_or(IntoRegionError::NoCensusDataError)
+            })
+            .transpose()?;
+
+        Ok(Self {
+            name,
+            ban_list: value.banlist.map(|b| split_list(&b, ':')),
+            banner: value.banner,
+            banner_by: value.bannerby,
+            banner_url: value.bannerurl,
+            census,
+            census_ranks: value.censusranks.map(Into::into),
+            dbid: value.dbid,
+            delegate: value.delegate.map(none_if_zero),
+            delegate_authority: value
+                .delegateauth
+                .map(|a| a.parse::<AuthoritySet>())
+                .transpose()?,
+            delegate_votes: value.delegatevotes,
+            dispatches: value.dispatches.map(|d| {
+                d.split(',')
+                    .filter_map(|id| id.parse::<u32>().ok())
+                    .collect()
+            }),
+            embassies: value
+                .embassies
+                .map(|e| {
+                    e.inner
+                        .into_iter()
+                        .map(Embassy::try_from)
+                        .collect::<Result<Vec<_>, _>>()
+                })
+                .transpose()?,
+            embassy_rmb: value
+                .embassyrmb
+                .map(EmbassyPostingRights::try_from)
+                .transpose()?,
+            factbook: value.factbook,
+            flag: value.flag,
+            founded: value.founded.map(MaybeRelativeTime::from),
+            founded_time: value.foundedtime.map(MaybeSystemTime::from),
+            founder: value.founder.map(none_if_zero),
+            frontier: value
+                .frontier
+                .map(|x| match x {
+                    0 => Ok(false),
+                    1 => Ok(true),
+                    e => Err(IntoRegionError::BadBooleanError(e)),
+                })
+                .transpose()?,
+            ga_vote: value.gavote.map(RegionWAVote::from),
+            governor: value.governor.map(none_if_zero),
+            happenings: value
+                .happenings
+                .map(|h| h.inner.into_iter().map(Event::from).collect()),
+            history: value
+                .history
+                .map(|h| h.inner.into_iter().map(Event::from).collect()),
+            last_update: value.lastupdate,
+            last_major_update: value.lastmajorupdate,
+            last_minor_update: value.lastminorupdate,
+            messages: value
+                .messages
+                .map(|m| {
+                    m.inner
+                        .into_iter()
+                        .map(Message::try_from)
+                        .collect::<Result<Vec<_>, _>>()
+                })
+                .transpose()?,
+            nations: value.nations.map(|n| NameList::split(&n, ':')),
+            num_nations: value.numnations,
+            num_wa_nations: value.numunnations,
+            officers: value
+                .officers
+                .map(|o| {
+                    o.inner
+                        .into_iter()
+                        .map(Officer::try_from)
+                        .collect::<Result<Vec<_>, _>>()
+                })
+                .transpose()?,
+            poll: value.poll.map(RawPoll::into_poll),
+            power: value.power,
+            sc_vote: value.scvote.map(RegionWAVote::from),
+            tags: value
+                .tags
+                .map(|t| t.inner.into_iter().map(into_tag).collect()),
+            wa_badges: value
+                .wabadges
+                .map(|b| {
+                    b.inner
+                        .into_iter()
+                        .map(WABadge::try_from)
+                        .collect::<Result<Vec<_>, _>>()
+                })
+                .transpose()?,
+            wa_nations: value.unnations.map(|n| NameList::split(&n, ',')),
+        })
+    }
+}
+
+impl TryFrom<RawEmbassy> for Embassy {
+    type Error = IntoRegionError;
+
+    fn try_from(value: RawEmbassy) -> Result<Self, Self::Error> {
+        let kind = match value.kind.as_deref() {
+            None => Ok(EmbassyKind::Established),
+            Some("pending") => Ok(EmbassyKind::Pending),
+            Some("invited") => Ok(EmbassyKind::Invited),
+            Some("requested") => Ok(EmbassyKind::Requested),
+            Some("denied") => Ok(EmbassyKind::Denied),
+            Some("closing") => Ok(EmbassyKind::Closing),
+            Some(other) => Err(IntoRegionError::BadEmbassyKind(other.to_string())),
+        }?;
+        Ok(Self {
+            region: value.region,
+            kind,
+        })
+    }
+}
+
+impl TryFrom<String> for EmbassyPostingRights {
+    type Error = IntoRegionError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match value.as_str() {
+            "0" => Ok(EmbassyPostingRights::NoEmbassyPosting),
+            "con" => Ok(EmbassyPostingRights::DelegatesAndFounders),
+            "off" => Ok(EmbassyPostingRights::Officers),
+            "com" => Ok(EmbassyPostingRights::CommunicationsOfficers),
+            "all" => Ok(EmbassyPostingRights::Everybody),
+            _ => Err(IntoRegionError::BadEmbassyRmb(value)),
+        }
+    }
+}
+
+impl From<RawRegionWAVote> for RegionWAVote {
+    fn from(value: RawRegionWAVote) -> Self {
+        Self {
+            for_votes: value.for_votes,
+            against_votes: value.against_votes,
+        }
+    }
+}
+
+impl TryFrom<RawMessage> for Message {
+    type Error = IntoRegionError;
+
+    fn try_from(value: RawMessage) -> Result<Self, Self::Error> {
+        let status = match value.status {
+            0 => Ok(MessageStatus::Visible),
+            1 => value
+                .suppressor
+                .map(|by| MessageStatus::SuppressedByOfficer { by })
+                .ok_or(IntoRegionError::NoSuppressorError(value.id)),
+            2 => Ok(MessageStatus::Deleted),
+            9 => Ok(MessageStatus::SuppressedByMod),
+            e => Err(IntoRegionError::BadMessageStatus(e)),
+        }?;
+        Ok(Self {
+            id: value.id,
+            timestamp: value.timestamp,
+            nation: value.nation,
+            status,
+            edited: value.edited.and_then(NonZeroU64::new),
+            likes: value.likes.unwrap_or_default(),
+            likers: value
+                .likers
+                .map(|l| split_list(&l, ':'))
+                .unwrap_or_default(),
+            embassy: value.embassy,
+            text: value.message.unwrap_or_default(),
+        })
+    }
+}
+
+impl TryFrom<RawOfficer> for Officer {
+    type Error = IntoRegionError;
+
+    fn try_from(value: RawOfficer) -> Result<Self, Self::Error> {
+        Ok(Self {
+            nation: value.nation,
+            office: value.office,
+            authority: value.authority.parse()?,
+            time: value.time,
+            by: value.by,
+            order: value.order,
+        })
+    }
+}
+
+impl TryFrom<RawWABadge> for WABadge {
+    type Error = IntoRegionError;
+
+    fn try_from(value: RawWABadge) -> Result<Self, Self::Error> {
+        let kind = match value.kind.as_str() {
+            "commend" => Ok(WABadgeKind::Commend),
+            "condemn" => Ok(WABadgeKind::Condemn),
+            "liberate" => Ok(WABadgeKind::Liberate),
+            "injunct" => Ok(WABadgeKind::Injunct),
+            _ => Err(IntoRegionError::BadWABadge(value.kind)),
+        }?;
+        Ok(Self {
+            kind,
+            resolution: value.resolution,
+        })
+    }
+}
+
+fn split_list(list: &str, separator: char) -> Vec<String> {
+    list.split(separator)
+        .filter(|s| !s.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+fn none_if_zero(value: String) -> Option<String> {
+    match value.as_str() {
+        "0" | "" => None,
+        _ => Some(value),
+    }
+}
+
+/// Tags are sent by their display names (e.g. "FT: FTL"),
+/// which are normalized to match the names used in URLs.
+fn into_tag(tag: String) -> Tag {
+    let normalized = tag.to_lowercase().replace(": ", "_").replace(' ', "_");
+    let normalized = match normalized.as_str() {
+        "portal_to_the_multiverse" => "p2tm",
+        other => other,
+    };
+    let Ok(tag) = normalized.parse();
+    tag
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::{region::OfficerAuthority, CensusData};
+
+    #[test]
+    fn parse_region() {
+        let xml = r#"<REGION id="testregionia">
+<NAME>Testregionia</NAME>
+<NUMNATIONS>3</NUMNATIONS>
+<NATIONS>testlandia:maxtopia:the_north_pacific_puppet</NATIONS>
+<DELEGATE>0</DELEGATE>
+<DELEGATEAUTH>XWCE</DELEGATEAUTH>
+<FOUNDER>testlandia</FOUNDER>
+<FRONTIER>0</FRONTIER>
+<EMBASSIES><EMBASSY>Lazarus</EMBASSY><EMBASSY type="pending">Osiris</EMBASSY></EMBASSIES>
+<EMBASSYRMB>con</EMBASSYRMB>
+<TAGS><TAG>FT: FTL</TAG><TAG>Anti-Capitalist</TAG><TAG>Portal to the Multiverse</TAG></TAGS>
+<GAVOTE><FOR>2</FOR><AGAINST>1</AGAINST></GAVOTE>
+<CENSUS><SCALE id="0"><SCORE>12.5</SCORE><RANK>4</RANK></SCALE></CENSUS>
+<POLL></POLL>
+<WABADGES><WABADGE type="liberate">42</WABADGE></WABADGES>
+</REGION>"#;
+        let region = Region::from_xml(xml).unwrap();
+        assert_eq!(region.name, "Testregionia");
+        assert_eq!(region.num_nations, Some(3));
+        assert_eq!(region.nations.unwrap().len(), 3);
+        assert_eq!(region.delegate, Some(None));
+        assert_eq!(
+            region.delegate_authority,
+            Some(AuthoritySet::from_iter([
+                OfficerAuthority::Executive,
+                OfficerAuthority::WorldAssembly,
+                OfficerAuthority::Communications,
+                OfficerAuthority::Embassies,
+            ]))
+        );
+        assert_eq!(region.founder, Some(Some(String::from("testlandia"))));
+        assert_eq!(region.frontier, Some(false));
+        let embassies = region.embassies.unwrap();
+        assert_eq!(embassies[0].kind, EmbassyKind::Established);
+        assert_eq!(embassies[1].kind, EmbassyKind::Pending);
+        assert_eq!(
+            region.embassy_rmb,
+            Some(EmbassyPostingRights::DelegatesAndFounders)
+        );
+        assert_eq!(
+            region.tags,
+            Some(vec![
+                Tag::FutureTechFasterThanLight,
+                Tag::AntiCapitalist,
+                Tag::PortalToTheMultiverse,
+            ])
+        );
+        assert_eq!(region.ga_vote.unwrap().for_votes, 2);
+        assert!(matches!(region.census, Some(CensusData::Current(_))));
+        assert!(matches!(region.poll, Some(None)));
+        assert_eq!(region.wa_badges.unwrap()[0].kind, WABadgeKind::Liberate);
+    }
+
+    #[test]
+    fn parse_messages_and_officers() {
+        let xml = r#"<REGION id="testregionia">
+<MESSAGES><POST id="1"><TIMESTAMP>100</TIMESTAMP><NATION>testlandia</NATION><STATUS>9</STATUS><LIKES>2</LIKES><LIKERS>maxtopia:ns</LIKERS><MESSAGE>Hello!</MESSAGE></POST><POST id="2"><TIMESTAMP>101</TIMESTAMP><NATION>aramos</NATION><STATUS>1</STATUS><SUPPRESSOR>maxtopia</SUPPRESSOR><MESSAGE>Spam</MESSAGE></POST></MESSAGES>
+<OFFICERS><OFFICER><NATION>maxtopia</NATION><OFFICE>Minister</OFFICE><AUTHORITY>BP</AUTHORITY><TIME>5</TIME><BY>testlandia</BY><ORDER>1</ORDER></OFFICER></OFFICERS>
+</REGION>"#;
+        let region = Region::from_xml(xml).unwrap();
+        assert_eq!(region.name, "Testregionia");
+        let messages = region.messages.unwrap();
+        let message = &messages[0];
+        assert_eq!(message.status, MessageStatus::SuppressedByMod);
+        assert_eq!(message.likers, vec!["maxtopia", "ns"]);
+        assert_eq!(message.edited, None);
+        assert_eq!(
+            messages[1].status,
+            MessageStatus::SuppressedByOfficer {
+                by: String::from("maxtopia")
+            }
+        );
+        assert!(matches!(
+            Region::from_xml(&xml.replace("<SUPPRESSOR>maxtopia</SUPPRESSOR>", "")),
+            Err(IntoRegionError::NoSuppressorError(2))
+        ));
+        let officer = &region.officers.unwrap()[0];
+        assert_eq!(
+            officer.authority,
+            OfficerAuthority::BorderControl | OfficerAuthority::Polls
+        );
+    }
+
+    #[test]
+    fn authorities() {
+        let xml = r#"<REGION id="testregionia">
+<GOVERNOR>testlandia</GOVERNOR>
+<DELEGATE>the_north_pacific_puppet</DELEGATE>
+<DELEGATEAUTH>WCE</DELEGATEAUTH>
+<OFFICERS><OFFICER><NATION>maxtopia</NATION><OFFICE>Minister</OFFICE><AUTHORITY>BP</AUTHORITY><TIME>5</TIME><BY>testlandia</BY><ORDER>1</ORDER></OFFICER></OFFICERS>
+</REGION>"#;
+        let region = Region::from_xml(xml).unwrap();
+        assert!(region.can_nation("Testlandia", OfficerAuthority::BorderControl));
+        assert!(!region.can_nation("testlandia", OfficerAuthority::WorldAssembly));
+        assert_eq!(
+            region.delegate_has_authority(OfficerAuthority::Executive),
+            Some(false)
+        );
+        assert!(region.can_nation("The North Pacific Puppet", OfficerAuthority::Communications));
+        assert!(!region.can_nation("the_north_pacific_puppet", OfficerAuthority::BorderControl));
+        assert!(region.can_nation("maxtopia", OfficerAuthority::Polls));
+        assert!(region.can_nation(
+            crate::models::name::NationName::from("Maxtopia"),
+            OfficerAuthority::Polls
+        ));
+        assert!(!region.can_nation("maxtopia", OfficerAuthority::Embassies));
+        assert!(!region.can_nation("ns", OfficerAuthority::Polls));
+    }
+
+    #[test]
+    fn wa_stats() {
+        use crate::models::endorsements::EndorsementGraph;
+
+        let xml = r#"<REGION id="testregionia">
+<NATIONS>testlandia:maxtopia:aramos:the_north_pacific_puppet</NATIONS>
+<UNNATIONS>testlandia,maxtopia,aramos</UNNATIONS>
+</REGION>"#;
+        let region = Region::from_xml(xml).unwrap();
+        let mut graph = EndorsementGraph::new();
+        graph.add_endorsements("testlandia", ["maxtopia", "aramos"]);
+        graph.add_endorsements("maxtopia", ["testlandia"]);
+        graph.add_endorsements("aramos", [] as [&str; 0]);
+
+        let stats = region.wa_stats(Some(&graph)).unwrap();
+        assert_eq!((stats.nations, stats.wa_members), (4, 3));
+        assert_eq!(stats.wa_percentage, 75.0);
+        assert_eq!(stats.endorsement_saturation, Some(50.0));
+        assert_eq!(stats.delegate_vote_share, None);
+        assert_eq!(
+            Region::from_xml("<REGION id=\"x\"></REGION>")
+                .unwrap()
+                .wa_stats(None),
+            None
+        );
+    }
+
+    #[test]
+    fn authority_set() {
+        let mut set = "PCB".parse::<AuthoritySet>().unwrap();
+        assert_eq!(set.to_string(), "BCP");
+        assert_eq!(set.len(), 3);
+        set.remove(OfficerAuthority::Polls);
+        set |= OfficerAuthority::Executive;
+        assert_eq!(set.to_string(), "XBC");
+        assert!(set.grants(OfficerAuthority::Embassies));
+        assert!(AuthoritySet::from(OfficerAuthority::Communications).is_subset(set));
+        assert!((set & OfficerAuthority::Polls).is_empty());
+        assert!(matches!(
+            "XQ".parse::<AuthoritySet>(),
+            Err(IntoRegionError::BadAuthority('Q'))
+        ));
+    }
+
+    #[test]
+    fn from_str() {
+        fn num_nations<T: FromStr>(xml: &str) -> Result<T, T::Err> {
+            xml.parse()
+        }
+
+        let xml = r#"<REGION id="anteria"><NUMNATIONS>7</NUMNATIONS></REGION>"#;
+        assert_eq!(num_nations::<Region>(xml).unwrap().num_nations, Some(7));
+        assert!(num_nations::<Region>("<REGION>").is_err());
+    }
+
+    #[test]
+    fn empty_numbers() {
+        let region = Region::from_xml(
+            r#"<REGION id="x"><DELEGATEVOTES></DELEGATEVOTES><NUMNATIONS> 7 </NUMNATIONS><LASTUPDATE/></REGION>"#,
+        )
+        .unwrap();
+        assert_eq!(region.delegate_votes, None);
+        assert_eq!(region.num_nations, Some(7));
+        assert_eq!(region.last_update, None);
+        assert!(
+            Region::from_xml(r#"<REGION id="x"><NUMNATIONS>many</NUMNATIONS></REGION>"#).is_err()
+        );
+    }
+
+    #[test]
+    fn unknown_tag() {
+        let xml = r#"<REGION id="x"><TAGS><TAG>Not A Tag</TAG><TAG>Anime</TAG></TAGS></REGION>"#;
+        let tags = Region::from_xml(xml).unwrap().tags.unwrap();
+        assert_eq!(
+            tags,
+            vec![Tag::Other(String::from("not_a_tag")), Tag::Anime]
+        );
+        assert_eq!(tags[0].to_string(), "not_a_tag");
+    }
+}
@@ -0,0 +1,557 @@
+#[cfg(feature = "client")]
+use crate::client::{parse_response, ClientError};
+use crate::{
+    models::name::{NameList, NationName},
+    parsers::{
+        empty_as_none, from_xml_bytes, from_xml_reader, from_xml_str,
+        happenings::Event,
+        wa::{
+            DelegateAction, DelegateLogEntry, DelegateVote, IntoWAError, Proposal, Resolution,
+            ResolutionCategory, ResolutionOption, WorldAssembly,
+        },
+        NSResponse, RawHappenings,
+    },
+    shards::wa::WACouncil,
+};
+#[cfg(feature = "client")]
+use reqwest::Response;
+use serde::Deserialize;
+use std::io::BufRead;
+use std::str::FromStr;
+
+//noinspection SpellCheckingInspection
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+struct RawWorldAssembly {
+    #[serde(rename = "@council")]
+    council: Option<String>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    numnations: Option<u32>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    numdelegates: Option<u32>,
+    delegates: Option<String>,
+    members: Option<String>,
+    happenings: Option<RawHappenings>,
+    lastresolution: Option<String>,
+    proposals: Option<RawProposals>,
+    resolution: Option<RawResolution>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawProposals {
+    #[serde(rename = "PROPOSAL", default)]
+    inner: Vec<RawProposal>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+struct RawProposal {
+    #[serde(rename = "@id")]
+    id: String,
+    category: String,
+    created: u64,
+    name: String,
+    option: String,
+    proposed_by: String,
+    approvals: Option<String>,
+}
+
+//noinspection SpellCheckingInspection
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+struct RawResolution {
+    name: Option<String>,
+    category: Option<String>,
+    option: Option<String>,
+    desc: Option<String>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    id: Option<u32>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    implemented: Option<u64>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    repealed_by: Option<u32>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    repealed: Option<u64>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    created: Option<u64>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    promoted: Option<u64>,
+    proposed_by: Option<String>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    total_nations_for: Option<u32>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    total_nations_against: Option<u32>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    total_votes_for: Option<u32>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    total_votes_against: Option<u32>,
+    votes_for: Option<RawNationList>,
+    votes_against: Option<RawNationList>,
+    vote_track_for: Option<RawVoteTrack>,
+    vote_track_against: Option<RawVoteTrack>,
+    dellog: Option<RawDelegateLog>,
+    delvotes_for: Option<RawDelegateVotes>,
+    delvotes_against: Option<RawDelegateVotes>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawNationList {
+    #[serde(rename = "N", default)]
+    inner: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawVoteTrack {
+    #[serde(rename = "N", default)]
+    inner: Vec<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawDelegateLog {
+    #[serde(rename = "ENTRY", default)]
+    inner: Vec<RawDelegateLogEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+struct RawDelegateLogEntry {
+    timestamp: u64,
+    nation: String,
+    action: String,
+    votes: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawDelegateVotes {
+    #[serde(rename = "DELEGATE", default)]
+    inner: Vec<RawDelegateVote>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+struct RawDelegateVote {
+    nation: String,
+    votes: u32,
+    timestamp: u64,
+}
+
+impl WorldAssembly {
+    /// Converts the XML response from NationStates to a [`WorldAssembly`].
+    pub fn from_xml(xml: &str) -> Result<Self, IntoWAError> {
+        Self::try_from(from_xml_str::<RawWorldAssembly>(xml)?)
+    }
+
+    /// Converts the XML response from NationStates, as bytes, to a [`WorldAssembly`].
+    ///
+    /// Fails with [`IntoWAError::DeserializationError`] if the response is not UTF-8.
+    pub fn from_bytes(xml: &[u8]) -> Result<Self, IntoWAError> {
+        Self::try_from(from_xml_bytes::<RawWorldAssembly>(xml)?)
+    }
+
+    /// Reads the XML response from NationStates and converts it to a [`WorldAssembly`],
+    /// without reading the whole response into memory first.
+    ///
+    /// Unlike [`WorldAssembly::from_xml`], errors do not include the value that could not be parsed.
+    pub fn from_reader(reader: impl BufRead) -> Result<Self, IntoWAError> {
+        Self::try_from(from_xml_reader::<RawWorldAssembly>(reader, "WA")?)
+    }
+
+    /// Reads a response from NationStates and converts it to a [`WorldAssembly`],
+    /// parsing the body as it arrives instead of reading all of it into memory first.
+    ///
+    /// As with [`Self::from_reader`], errors do not include the value that could not be parsed.
+    ///
+    /// Fails with [`ClientError::ReqwestError`] if the API responded with an error status
+    /// or the body could not be read,
+    /// and with [`ClientError::ParseError`] if the body could not be parsed.
+    #[cfg(feature = "client")]
+    pub async fn from_response(response: Response) -> Result<Self, ClientError> {
+        parse_response(response, Self::from_reader).await
+    }
+}
+
+impl NSResponse for WorldAssembly {
+    type Error = IntoWAError;
+
+    fn from_xml(xml: &str) -> Result<Self, Self::Error> {
+        WorldAssembly::from_xml(xml)
+    }
+}
+
+impl FromStr for WorldAssembly {
+    type Err = IntoWAError;
+
+    /// Same as [`WorldAssembly::from_xml`].
+    fn from_str(xml: &str) -> Result<Self, Self::Err> {
+        WorldAssembly::from_xml(xml)
+    }
+}
+
+impl TryFrom<RawWorldAssembly> for WorldAssembly {
+    type Error = IntoWAError;
+
+    fn try_from(value: RawWorldAssembly) -> Result<Self, Self::Error> {
+        let council = value
+            .council
+            .map(|c| match c.as_str() {
+                "1" => Ok(WACouncil::GeneralAssembly),
+                "2" => Ok(WACouncil::SecurityCouncil),
+                _ => Err(IntoWAError::BadCouncil(c)),
+            })
+            .transpose()?;
+
+        Ok(Self {
+            council,
+            num_nations: value.numnations,
+            num_delegates: value.numdelegates,
+            delegates: value.delegates.map(|d| NameList::split(&d, ',')),
+            members: value.members.map(|m| NameList::split(&m, ',')),
+            happenings: value
+                .happenings
+                .map(|h| h.inner.into_iter().map(Event::from).collect()),
+            last_resolution: value.lastresolution,
+            proposals: value
+                .proposals
+                .map(|p| p.inner.into_iter().map(Proposal::from).collect()),
+            resolution: value
+                .resolution
+                .map(RawResolution::try_into_resolution)
+                .transpose()?,
+        })
+    }
+}
+
+impl From<RawProposal> for Proposal {
+    fn from(value: RawProposal) -> Self {
+        let Ok(category) = value.category.parse::<ResolutionCategory>();
+        Self {
+            id: value.id,
+            option: ResolutionOption::parse(value.option, &category),
+            category,
+            created: value.created,
+            name: value.name,
+            proposed_by: NationName::new(value.proposed_by),
+            approvals: value
+                .approvals
+                .map(|a| {
+                    a.split(':')
+                        .filter(|s| !s.is_empty())
+                        .map(String::from)
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl RawResolution {
+    /// An empty `<RESOLUTION>` tag means that there is no resolution at vote.
+    fn try_into_resolution(self) -> Result<Option<Resolution>, IntoWAError> {
+        let Some(name) = self.name else {
+            return Ok(None);
+        };
+        let Ok(category) = self
+            .category
+            .unwrap_or_default()
+            .parse::<ResolutionCategory>();
+        Ok(Some(Resolution {
+            name,
+            option: ResolutionOption::parse(self.option.unwrap_or_default(), &category),
+            category,
+            created: self.created.unwrap_or_default(),
+            promoted: self.promoted,
+            proposed_by: NationName::new(self.proposed_by.unwrap_or_default()),
+            text: self.desc,
+            id: self.id,
+            implemented: self.implemented,
+            repealed_by: self.repealed_by,
+            repealed: self.repealed,
+            total_nations_for: self.total_nations_for.unwrap_or_default(),
+            total_nations_against: self.total_nations_against.unwrap_or_default(),
+            total_votes_for: self.total_votes_for.unwrap_or_default(),
+            total_votes_against: self.total_votes_against.unwrap_or_default(),
+            votes_for: self.votes_for.map(|v| v.inner),
+            votes_against: self.votes_against.map(|v| v.inner),
+            vote_track_for: self.vote_track_for.map(|v| v.inner),
+            vote_track_against: self.vote_track_against.map(|v| v.inner),
+            delegate_log: self
+                .dellog
+                .map(|l| {
+                    l.inner
+                        .into_iter()
+                        .map(DelegateLogEntry::try_from)
+                        .collect::<Result<Vec<_>, _>>()
+                })
+                .transpose()?,
+            delegate_votes_for: self
+                .delvotes_for
+                .map(|v| v.inner.into_iter().map(DelegateVote::from).collect()),
+            delegate_votes_against: self
+                .delvotes_against
+                .map(|v| v.inner.into_iter().map(DelegateVote::from).collect()),
+        }))
+    }
+}
+
+impl TryFrom<RawDelegateLogEntry> for DelegateLogEntry {
+    type Error = IntoWAError;
+
+    fn try_from(value: RawDelegateLogEntry) -> Result<Self, Self::Error> {
+        let action = match value.action.as_str() {
+            "FOR" => Ok(DelegateAction::For),
+            "AGAINST" => Ok(DelegateAction::Against),
+            "WITHDREW" => Ok(DelegateAction::Withdrew),
+            _ => Err(IntoWAError::BadDelegateAction(value.action)),
+        }?;
+        Ok(Self {
+            timestamp: value.timestamp,
+            nation: value.nation,
+            action,
+            votes: value.votes,
+        })
+    }
+}
+
+impl From<RawDelegateVote> for DelegateVote {
+    fn from(value: RawDelegateVote) -> Self {
+        Self {
+            nation: value.nation,
+            votes: value.votes,
+            timestamp: value.timestamp,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        models::name::RegionName,
+        parsers::wa::{ScAction, ScTarget, Strength, VoteBreakdown, VoteTrackPoint, WaSnapshot},
+        shards::NSRequest,
+    };
+
+    #[test]
+    fn parse_resolution() {
+        let xml = r#"<WA council="1">
+<RESOLUTION>
+<CATEGORY>Civil Rights</CATEGORY>
+<CREATED>100</CREATED>
+<DESC><![CDATA[[i]The World Assembly,[/i]
+
+[b]Believing[/b] that testing matters...]]></DESC>
+<NAME>Repeal "Testing"</NAME>
+<OPTION>Significant</OPTION>
+<PROMOTED>200</PROMOTED>
+<PROPOSED_BY>Testlandia</PROPOSED_BY>
+<TOTAL_NATIONS_AGAINST>1</TOTAL_NATIONS_AGAINST>
+<TOTAL_NATIONS_FOR>2</TOTAL_NATIONS_FOR>
+<TOTAL_VOTES_AGAINST>1</TOTAL_VOTES_AGAINST>
+<TOTAL_VOTES_FOR>12</TOTAL_VOTES_FOR>
+<VOTES_FOR><N>testlandia</N><N>maxtopia</N></VOTES_FOR>
+<VOTES_AGAINST><N>ns</N></VOTES_AGAINST>
+<VOTE_TRACK_FOR><N>0</N><N>11</N><N>12</N></VOTE_TRACK_FOR>
+<VOTE_TRACK_AGAINST><N>0</N><N>0</N><N>1</N></VOTE_TRACK_AGAINST>
+<DELLOG><ENTRY><TIMESTAMP>300</TIMESTAMP><NATION>maxtopia</NATION><ACTION>FOR</ACTION><VOTES>11</VOTES></ENTRY></DELLOG>
+<DELVOTES_FOR><DELEGATE><NATION>maxtopia</NATION><VOTES>11</VOTES><TIMESTAMP>300</TIMESTAMP></DELEGATE></DELVOTES_FOR>
+<DELVOTES_AGAINST></DELVOTES_AGAINST>
+</RESOLUTION>
+</WA>"#;
+        let wa = WorldAssembly::from_xml(xml).unwrap();
+        assert!(matches!(wa.council, Some(WACouncil::GeneralAssembly)));
+        let resolution = wa.resolution.unwrap().unwrap();
+        assert_eq!(resolution.name, r#"Repeal "Testing""#);
+        assert_eq!(resolution.category, ResolutionCategory::CivilRights);
+        assert_eq!(
+            resolution.option,
+            ResolutionOption::Strength(Strength::Significant)
+        );
+        assert_eq!(resolution.proposed_by, NationName::new("testlandia"));
+        assert!(resolution
+            .text
+            .as_deref()
+            .unwrap()
+            .starts_with("[i]The World Assembly,[/i]"));
+        assert_eq!(resolution.total_votes_for, 12);
+        assert_eq!(
+            resolution.vote_track().unwrap()[2],
+            VoteTrackPoint {
+                timestamp: 200 + 2 * 3600,
+                votes_for: 12,
+                votes_against: 1,
+            }
+        );
+        assert_eq!(
+            resolution.vote_breakdown(),
+            Some(VoteBreakdown {
+                delegates_for: 1,
+                delegates_against: 0,
+                delegate_weight_for: 11,
+                delegate_weight_against: 0,
+                members_for: 1,
+                members_against: 1,
+            })
+        );
+        assert_eq!(resolution.votes_for.unwrap().len(), 2);
+        let log = resolution.delegate_log.unwrap();
+        assert_eq!(log[0].action, DelegateAction::For);
+        assert_eq!(resolution.delegate_votes_for.unwrap()[0].votes, 11);
+        assert!(resolution.delegate_votes_against.unwrap().is_empty());
+    }
+
+    #[test]
+    fn no_resolution_at_vote() {
+        let wa =
+            WorldAssembly::from_xml(r#"<WA council="2"><RESOLUTION></RESOLUTION></WA>"#).unwrap();
+        assert!(matches!(wa.council, Some(WACouncil::SecurityCouncil)));
+        assert!(matches!(wa.resolution, Some(None)));
+    }
+
+    #[test]
+    fn past_resolution() {
+        let xml = r#"<WA council="1">
+<RESOLUTION>
+<CATEGORY>Repeal</CATEGORY>
+<COUNCIL>1</COUNCIL>
+<CREATED>100</CREATED>
+<DESC><![CDATA[The World Assembly repeals GA#1.]]></DESC>
+<ID>2</ID>
+<IMPLEMENTED>400</IMPLEMENTED>
+<NAME>Repeal "Testing"</NAME>
+<OPTION>1</OPTION>
+<PROMOTED>200</PROMOTED>
+<PROPOSED_BY>testlandia</PROPOSED_BY>
+<RESID>2</RESID>
+<TOTAL_NATIONS_AGAINST>1</TOTAL_NATIONS_AGAINST>
+<TOTAL_NATIONS_FOR>2</TOTAL_NATIONS_FOR>
+<TOTAL_VOTES_AGAINST>1</TOTAL_VOTES_AGAINST>
+<TOTAL_VOTES_FOR>12</TOTAL_VOTES_FOR>
+</RESOLUTION>
+</WA>"#;
+        let resolution = WorldAssembly::from_xml(xml)
+            .unwrap()
+            .resolution
+            .unwrap()
+            .unwrap();
+        assert_eq!(resolution.option, ResolutionOption::Repeals(1));
+        assert_eq!(resolution.id, Some(2));
+        assert_eq!(resolution.implemented, Some(400));
+        assert_eq!(resolution.repealed_by, None);
+        assert_eq!(resolution.total_votes_for, 12);
+    }
+
+    #[test]
+    fn members_and_happenings() {
+        let xml = r#"<WA council="1">
+<DELEGATES>testlandia,maxtopia</DELEGATES>
+<MEMBERS>testlandia,maxtopia,aramos</MEMBERS>
+<HAPPENINGS>
+<EVENT id="5"><TIMESTAMP>100</TIMESTAMP><TEXT><![CDATA[@@testlandia@@ was admitted to the World Assembly.]]></TEXT></EVENT>
+</HAPPENINGS>
+</WA>"#;
+        let wa = WorldAssembly::from_xml(xml).unwrap();
+        assert_eq!(wa.delegates.unwrap(), ["testlandia", "maxtopia"]);
+        assert_eq!(wa.members.unwrap().len(), 3);
+        let happenings = wa.happenings.unwrap();
+        assert_eq!(happenings[0].id, Some(5));
+    }
+
+    #[test]
+    fn snapshot() {
+        assert_eq!(
+            WaSnapshot::request(WACouncil::GeneralAssembly)
+                .as_url()
+                .unwrap()
+                .query(),
+            Some("wa=1&q=resolution%2Bvotetrack%2Bdelvotes%2Blastresolution&v=12")
+        );
+        let xml = r#"<WA council="2">
+<RESOLUTION></RESOLUTION>
+<LASTRESOLUTION>The resolution was passed.</LASTRESOLUTION>
+</WA>"#;
+        let snapshot = WaSnapshot::from(WorldAssembly::from_xml(xml).unwrap());
+        assert!(matches!(snapshot.council, WACouncil::SecurityCouncil));
+        assert!(snapshot.resolution.is_none());
+        assert!(snapshot.last_resolution.is_some());
+    }
+
+    #[test]
+    fn quorum() {
+        let xml = r#"<WA council="1">
+<NUMDELEGATES>100</NUMDELEGATES>
+<PROPOSALS>
+<PROPOSAL id="testlandia_1">
+<APPROVALS>maxtopia:ns:aramos</APPROVALS>
+<CATEGORY>Health</CATEGORY>
+<CREATED>1000</CREATED>
+<NAME>Testing Access Act</NAME>
+<OPTION>Mild</OPTION>
+<PROPOSED_BY>testlandia</PROPOSED_BY>
+</PROPOSAL>
+</PROPOSALS>
+</WA>"#;
+        let wa = WorldAssembly::from_xml(xml).unwrap();
+        let statuses = wa.quorum_statuses(1000 + 36 * 60 * 60).unwrap();
+        let (proposal, status) = statuses[0];
+        assert_eq!(proposal.option, ResolutionOption::Strength(Strength::Mild));
+        assert_eq!(status.quorum, 6);
+        assert_eq!(status.approvals_needed, 3);
+        assert!(!status.has_quorum());
+        assert_eq!(status.days_left(), 2);
+    }
+
+    #[test]
+    fn sc_target() {
+        let xml = r#"<WA council="2">
+<RESOLUTION>
+<CATEGORY>Liberation</CATEGORY>
+<CREATED>100</CREATED>
+<NAME>Liberate Testregionia</NAME>
+<OPTION>R:testregionia</OPTION>
+<PROPOSED_BY>testlandia</PROPOSED_BY>
+</RESOLUTION>
+</WA>"#;
+        let resolution = WorldAssembly::from_xml(xml)
+            .unwrap()
+            .resolution
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            resolution.sc_target(),
+            Some((
+                ScAction::Liberate,
+                &ScTarget::Region(RegionName::new("testregionia"))
+            ))
+        );
+        assert_eq!(resolution.text, None);
+    }
+
+    #[test]
+    fn resolution_options() {
+        let parse = |option: &str, category: &str| {
+            let category = ResolutionCategory::from_str(category).unwrap();
+            ResolutionOption::parse(option.to_string(), &category)
+        };
+        assert_eq!(parse("512", "Repeal"), ResolutionOption::Repeals(512));
+        assert_eq!(
+            parse("N:testlandia", "Commendation"),
+            ResolutionOption::Target(ScTarget::Nation(NationName::new("testlandia")))
+        );
+        assert_eq!(
+            parse("R:the_pacific", "Liberation"),
+            ResolutionOption::Target(ScTarget::Region(RegionName::new("the_pacific")))
+        );
+        assert_eq!(
+            parse("Consumer Protection", "Regulation"),
+            ResolutionOption::Other("Consumer Protection".to_string())
+        );
+        assert_eq!(
+            ResolutionCategory::from_str("Something New").unwrap(),
+            ResolutionCategory::Unknown("Something New".to_string())
+        );
+        assert_eq!(
+            ResolutionCategory::InternationalSecurity.to_string(),
+            "International Security"
+        );
+    }
+}
@@ -0,0 +1,341 @@
+#[cfg(feature = "client")]
+use crate::client::{parse_response, ClientError};
+use crate::{
+    models::name::NameList,
+    parsers::{
+        empty_as_none, from_xml_bytes, from_xml_reader, from_xml_str,
+        happenings::Event,
+        nation::BannerId,
+        world::{Banner, CensusDescription, IntoWorldError, TGQueue, World},
+        NSResponse, RawCensus, RawCensusData, RawCensusRanks, RawDispatch, RawDispatchList,
+        RawHappenings, RawPoll,
+    },
+};
+#[cfg(feature = "client")]
+use reqwest::Response;
+use serde::Deserialize;
+use std::io::BufRead;
+use std::str::FromStr;
+
+//noinspection SpellCheckingInspection
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+struct RawWorld<'x> {
+    banners: Option<RawBanners>,
+    census: Option<RawWorldCensus>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    censusid: Option<u8>,
+    censusdesc: Option<RawCensusDescription>,
+    censusranks: Option<RawCensusRanks>,
+    censusscale: Option<RawCensusText>,
+    censustitle: Option<RawCensusText>,
+    #[serde(borrow)]
+    dispatch: Option<RawDispatch<'x>>,
+    #[serde(borrow)]
+    dispatchlist: Option<RawDispatchList<'x>>,
+    featuredregion: Option<String>,
+    happenings: Option<RawHappenings>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    lasteventid: Option<u32>,
+    nations: Option<String>,
+    newnations: Option<String>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    numnations: Option<u32>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    numregions: Option<u32>,
+    poll: Option<RawPoll>,
+    regions: Option<String>,
+    tgqueue: Option<RawTGQueue>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawBanners {
+    #[serde(rename = "BANNER", default)]
+    inner: Vec<RawBanner>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+struct RawBanner {
+    #[serde(rename = "@id")]
+    id: String,
+    name: String,
+    validity: String,
+}
+
+/// Both the `census` and `censusname` shards respond with a `<CENSUS>` tag:
+/// the former with a list of scales, the latter with the name of one scale as text.
+#[derive(Debug, Deserialize)]
+struct RawWorldCensus {
+    #[serde(rename = "$text")]
+    name: Option<String>,
+    #[serde(rename = "SCALE", default)]
+    inner: Vec<RawCensusData>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+struct RawCensusDescription {
+    ndesc: String,
+    rdesc: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawCensusText {
+    #[serde(rename = "$text")]
+    inner: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+struct RawTGQueue {
+    manual: u32,
+    mass: u32,
+    api: u32,
+}
+
+impl World {
+    /// Converts the XML response from NationStates to a [`World`].
+    pub fn from_xml(xml: &str) -> Result<Self, IntoWorldError> {
+        Self::try_from(from_xml_str::<RawWorld>(xml)?)
+    }
+
+    /// Converts the XML response from NationStates, as bytes, to a [`World`].
+    ///
+    /// Fails with [`IntoWorldError::DeserializationError`] if the response is not UTF-8.
+    pub fn from_bytes(xml: &[u8]) -> Result<Self, IntoWorldError> {
+        Self::try_from(from_xml_bytes::<RawWorld>(xml)?)
+    }
+
+    /// Reads the XML response from NationStates and converts it to a [`World`],
+    /// without reading the whole response into memory first.
+    ///
+    /// Unlike [`World::from_xml`], errors do not include the value that could not be parsed.
+    pub fn from_reader(reader: impl BufRead) -> Result<Self, IntoWorldError> {
+        Self::try_from(from_xml_reader::<RawWorld>(reader, "WORLD")?)
+    }
+
+    /// Reads a response from NationStates and converts it to a [`World`],
+    /// parsing the body as it arrives instead of reading all of it into memory first.
+    ///
+    /// As with [`Self::from_reader`], errors do not include the value that could not be parsed.
+    ///
+    /// Fails with [`ClientError::ReqwestError`] if the API responded with an error status
+    /// or the body could not be read,
+    /// and with [`ClientError::ParseError`] if the body could not be parsed.
+    #[cfg(feature = "client")]
+    pub async fn from_response(response: Response) -> Result<Self, ClientError> {
+        parse_response(response, Self::from_reader).await
+    }
+}
+
+impl NSResponse for World {
+    type Error = IntoWorldError;
+
+    fn from_xml(xml: &str) -> Result<Self, Self::Error> {
+        World::from_xml(xml)
+    }
+}
+
+impl FromStr for World {
+    type Err = IntoWorldError;
+
+    /// Same as [`World::from_xml`].
+    fn from_str(xml: &str) -> Result<Self, Self::Err> {
+        World::from_xml(xml)
+    }
+}
+
+impl TryFrom<RawWorld<'_>> for World {
+    type Error = IntoWorldError;
+
+    fn try_from(value: RawWorld) -> Result<Self, Self::Error> {
+        let (census, census_name) = match value.census {
+            Some(RawWorldCensus { name, inner }) if inner.is_empty() => (None, name),
+            Some(RawWorldCensus { inner, .. }) => (
+                Some(
+                    RawCensus { inner }
+                        .into_census_data()
+                        .ok_or(IntoWorldError::NoCensusDataError)?,
+                ),
+                None,
+            ),
+            None => (None, None),
+        };
+
+        Ok(Self {
+            banners: value
+                .banners
+                .map(|b| {
+                    b.inner
+                        .into_iter()
+                        .map(Banner::try_from)
+                        .collect::<Result<Vec<_>, _>>()
+                })
+                .transpose()?,
+            census,
+            census_id: value.censusid,
+            census_desc: value.censusdesc.map(|d| CensusDescription {
+                nation: d.ndesc,
+                region: d.rdesc,
+            }),
+            census_name,
+            census_ranks: value.censusranks.map(Into::into),
+            census_scale: value.censusscale.map(|s| s.inner),
+            census_title: value.censustitle.map(|t| t.inner),
+            dispatch: value
+                .dispatch
+                .map(|d| {
+                    d.try_into_dispatch()
+                        .map_err(IntoWorldError::BadDispatchCategory)
+                })
+                .transpose()?,
+            dispatch_list: value
+                .dispatchlist
+                .map(|v| {
+                    v.inner
+                        .into_iter()
+                        .map(|d| {
+                            d.try_into_dispatch()
+                                .map_err(IntoWorldError::BadDispatchCategory)
+                        })
+                        .collect::<Result<Vec<_>, _>>()
+                })
+                .transpose()?,
+            featured_region: value.featuredregion,
+            happenings: value
+                .happenings
+                .map(|h| h.inner.into_iter().map(Event::from).collect()),
+            last_event_id: value.lasteventid,
+            nations: value.nations.map(|n| NameList::split(&n, ',')),
+            new_nations: value.newnations.map(|n| split_list(&n)),
+            num_nations: value.numnations,
+            num_regions: value.numregions,
+            poll: value.poll.and_then(RawPoll::into_poll),
+            regions: value.regions.map(|r| NameList::split(&r, ',')),
+            tg_queue: value.tgqueue.map(|q| TGQueue {
+                manual: q.manual,
+                mass: q.mass,
+                api: q.api,
+            }),
+        })
+    }
+}
+
+impl TryFrom<RawBanner> for Banner {
+    type Error = IntoWorldError;
+
+    fn try_from(value: RawBanner) -> Result<Self, Self::Error> {
+        let id = BannerId::try_from(value.id.clone())
+            .map_err(|_| IntoWorldError::BadBannerId(value.id))?;
+        Ok(Self {
+            id,
+            name: value.name,
+            validity: value.validity,
+        })
+    }
+}
+
+fn split_list(list: &str) -> Vec<String> {
+    list.split(',')
+        .filter(|s| !s.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::CensusData;
+
+    #[test]
+    fn parse_world() {
+        let xml = r#"<WORLD>
+<CENSUSID>46</CENSUSID>
+<CENSUSSCALE id="46">Shekels</CENSUSSCALE>
+<FEATUREDREGION>the_north_pacific</FEATUREDREGION>
+<NEWNATIONS>testlandia,maxtopia</NEWNATIONS>
+<NUMNATIONS>250000</NUMNATIONS>
+<TGQUEUE><MANUAL>1</MANUAL><MASS>20</MASS><API>300</API></TGQUEUE>
+<HAPPENINGS><EVENT id="1"><TIMESTAMP>100</TIMESTAMP><TEXT>@@testlandia@@ was founded in %%the_pacific%%.</TEXT></EVENT></HAPPENINGS>
+<BANNERS><BANNER id="b1"><NAME>Beach</NAME><VALIDITY>Always</VALIDITY></BANNER></BANNERS>
+</WORLD>"#;
+        let world = World::from_xml(xml).unwrap();
+        assert_eq!(world.census_id, Some(46));
+        assert_eq!(world.census_scale.as_deref(), Some("Shekels"));
+        assert_eq!(world.featured_region.as_deref(), Some("the_north_pacific"));
+        assert_eq!(world.new_nations.unwrap(), vec!["testlandia", "maxtopia"]);
+        assert_eq!(world.num_nations, Some(250000));
+        assert_eq!(world.tg_queue.unwrap().api, 300);
+        let happenings = world.happenings.unwrap();
+        assert_eq!(happenings[0].id, Some(1));
+        assert_eq!(happenings[0].timestamp, 100);
+        assert_eq!(world.banners.unwrap()[0].id.to_string(), "b1");
+    }
+
+    #[test]
+    fn census_and_census_name() {
+        let world =
+            World::from_xml(r#"<WORLD><CENSUS id="0">Civil Rights</CENSUS></WORLD>"#).unwrap();
+        assert_eq!(world.census_name.as_deref(), Some("Civil Rights"));
+        assert!(world.census.is_none());
+
+        let world = World::from_xml(
+            r#"<WORLD><CENSUS><SCALE id="0"><SCORE>50.0</SCORE></SCALE></CENSUS></WORLD>"#,
+        )
+        .unwrap();
+        assert!(world.census_name.is_none());
+        assert!(matches!(world.census, Some(CensusData::Current(_))));
+    }
+
+    #[test]
+    fn census_ranks_empty_score() {
+        let world = World::from_xml(
+            r#"<WORLD><CENSUSRANKS id="46"><NATIONS><NATION><NAME>testlandia</NAME><RANK>1</RANK><SCORE/></NATION><NATION><NAME>aramos</NAME><RANK></RANK><SCORE>12</SCORE></NATION></NATIONS></CENSUSRANKS></WORLD>"#,
+        )
+        .unwrap();
+        let nations = world.census_ranks.unwrap().nations;
+        assert_eq!((nations[0].rank, nations[0].score), (Some(1), None));
+        assert_eq!((nations[1].rank, nations[1].score), (None, Some(12.0)));
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn census_csv() {
+        let csv = |write: &dyn Fn(&mut Vec<u8>)| {
+            let mut out = Vec::new();
+            write(&mut out);
+            String::from_utf8(out).unwrap()
+        };
+
+        let world = World::from_xml(
+            r#"<WORLD><CENSUSRANKS id="46"><NATIONS><NATION><NAME>testlandia</NAME><RANK>1</RANK><SCORE>1000.5</SCORE></NATION><NATION><NAME>aramos</NAME><RANK>2</RANK><SCORE>12</SCORE></NATION></NATIONS></CENSUSRANKS></WORLD>"#,
+        )
+        .unwrap();
+        let ranks = world.census_ranks.unwrap();
+        assert_eq!(
+            csv(&|out| ranks.to_csv(out).unwrap()),
+            "scale,nation,rank,score\n46,testlandia,1,1000.5\n46,aramos,2,12\n"
+        );
+
+        let world = World::from_xml(
+            r#"<WORLD><CENSUSRANKS id="46"><NATIONS><NATION><NAME>testlandia</NAME><RANK>1</RANK><SCORE/></NATION></NATIONS></CENSUSRANKS></WORLD>"#,
+        )
+        .unwrap();
+        let ranks = world.census_ranks.unwrap();
+        assert_eq!(
+            csv(&|out| ranks.to_csv(out).unwrap()),
+            "scale,nation,rank,score\n46,testlandia,1,\n"
+        );
+
+        let world = World::from_xml(
+            r#"<WORLD><CENSUS><SCALE id="0"><SCORE>50.5</SCORE><RANK>3</RANK></SCALE></CENSUS></WORLD>"#,
+        )
+        .unwrap();
+        let census = world.census.unwrap();
+        assert_eq!(
+            csv(&|out| census.to_csv(out).unwrap()),
+            "scale,score,world_rank,region_rank,percent_world_rank,percent_region_rank\n0,50.5,3,,,\n"
+        );
+    }
+}
@@ -0,0 +1,876 @@
+//! The region parser module.
+
+use crate::{
+    models::{
+        endorsements::EndorsementGraph,
+        name::{canonical_eq, NameList, NationName},
+    },
+    parsers::{
+        happenings::Event, CensusData, CensusRanks, CensusRanksNation, MaybeRelativeTime,
+        MaybeSystemTime, Poll, XmlError,
+    },
+    shards::{region::Tag, world::IncludeOrExcludeTag},
+};
+#[cfg(feature = "schemars")]
+use std::borrow::Cow;
+use std::{
+    fmt::{Debug, Display, Formatter, Write},
+    num::NonZeroU64,
+    ops::{BitAnd, BitOr, BitOrAssign},
+    str::FromStr,
+    time::Duration,
+};
+use thiserror::Error;
+
+/// A region, with every piece of information you could ask for!
+///
+/// Note that aside from the `name` field, every field is an `Option`.
+/// This is because,
+/// depending on the [`RegionShard`](crate::shards::region::RegionShard)s used
+/// to make the request,
+/// only certain fields will be returned.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct Region {
+    /// The name of the region.
+    /// This is the only field guaranteed to be filled in.
+    /// Note that because of limitations to the way the name is sent by NationStates,
+    /// it may not be capitalized properly by the "pretty name" function.
+    /// The only way to get the accurate capitalization is
+    /// to request [`RegionShard::Name`](crate::shards::region::RegionShard::Name).
+    pub name: String,
+    /// The list of all nations banned from the region.
+    ///
+    /// Requested by using
+    /// [`RegionShard::BanList`](crate::shards::region::RegionShard::BanList).
+    pub ban_list: Option<Vec<String>>,
+    /// The ID of the region's banner.
+    ///
+    /// Requested by using [`RegionShard::Banner`](crate::shards::region::RegionShard::Banner).
+    pub banner: Option<u32>,
+    /// The nation that uploaded the region's banner.
+    ///
+    /// Requested by using
+    /// [`RegionShard::BannerBy`](crate::shards::region::RegionShard::BannerBy).
+    pub banner_by: Option<String>,
+    /// The URL of the region's banner, relative to `https://www.nationstates.net`.
+    ///
+    /// Requested by using
+    /// [`RegionShard::BannerUrl`](crate::shards::region::RegionShard::BannerUrl).
+    pub banner_url: Option<String>,
+    /// Information on the region's score and ranking on the World Census.
+    /// If current data was requested (the default),
+    /// the resulting data will be found in the [`CensusData::Current`] variant,
+    /// but if historical data was requested,
+    /// the resulting data will be found in the [`CensusData::Historical`] variant.
+    ///
+    /// Requested and configured
+    /// using [`RegionShard::Census`](crate::shards::region::RegionShard::Census).
+    pub census: Option<CensusData>,
+    /// How nations in the region rank on a World Census scale.
+    ///
+    /// Requested and configured using
+    /// [`RegionShard::CensusRanks`](crate::shards::region::RegionShard::CensusRanks).
+    pub census_ranks: Option<CensusRanks>,
+    /// The ID of the region in the NationStates database.
+    ///
+    /// Requested by using [`RegionShard::DbId`](crate::shards::region::RegionShard::DbId).
+    pub dbid: Option<u32>,
+    /// The World Assembly Delegate of the region.
+    /// If the region does not have a delegate, the field will be `Some(None)`.
+    ///
+    /// Requested by using
+    /// [`RegionShard::Delegate`](crate::shards::region::RegionShard::Delegate).
+    pub delegate: Option<Option<String>>,
+    /// The authorities that the regional delegate has.
+    ///
+    /// Requested by using
+    /// [`RegionShard::DelegateAuth`](crate::shards::region::RegionShard::DelegateAuth).
+    pub delegate_authority: Option<AuthoritySet>,
+    /// The voting power of the regional delegate (number of verified endorsements + 1).
+    ///
+    /// Requested by using
+    /// [`RegionShard::DelegateVotes`](crate::shards::region::RegionShard::DelegateVotes).
+    pub delegate_votes: Option<u32>,
+    /// The IDs of the dispatches pinned on the region's page.
+    ///
+    /// Requested by using
+    /// [`RegionShard::Dispatches`](crate::shards::region::RegionShard::Dispatches).
+    pub dispatches: Option<Vec<u32>>,
+    /// The embassies of the region, along with their status.
+    ///
+    /// Requested by using
+    /// [`RegionShard::Embassies`](crate::shards::region::RegionShard::Embassies).
+    pub embassies: Option<Vec<Embassy>>,
+    /// Which nations in embassy regions can post on the regional message board.
+    ///
+    /// Requested by using
+    /// [`RegionShard::EmbassyRmb`](crate::shards::region::RegionShard::EmbassyRmb).
+    pub embassy_rmb: Option<EmbassyPostingRights>,
+    /// The region's World Factbook Entry, as BBCode.
+    ///
+    /// Requested by using
+    /// [`RegionShard::Factbook`](crate::shards::region::RegionShard::Factbook).
+    pub factbook: Option<String>,
+    /// The URL of the regional flag.
+    ///
+    /// Requested by using [`RegionShard::Flag`](crate::shards::region::RegionShard::Flag).
+    pub flag: Option<String>,
+    /// When the region was founded as a relative timestamp.
+    /// Note: NationStates did not track this at the beginning.
+    /// For this reason, some regions are considered "founded in antiquity",
+    /// which is represented by [`MaybeRelativeTime::Antiquity`].
+    ///
+    /// Requested by using
+    /// [`RegionShard::Founded`](crate::shards::region::RegionShard::Founded).
+    pub founded: Option<MaybeRelativeTime>,
+    /// The Unix timestamp of when the region was founded.
+    /// Note: NationStates did not track this at the beginning.
+    /// For this reason, some regions are considered "founded in antiquity",
+    /// which is represented by [`MaybeSystemTime::Antiquity`].
+    ///
+    /// Requested by using
+    /// [`RegionShard::FoundedTime`](crate::shards::region::RegionShard::FoundedTime).
+    pub founded_time: Option<MaybeSystemTime>,
+    /// The founder of the region.
+    /// If the region does not have a founder, the field will be `Some(None)`.
+    ///
+    /// Requested by using
+    /// [`RegionShard::Founder`](crate::shards::region::RegionShard::Founder).
+    pub founder: Option<Option<String>>,
+    /// Whether the region is a Frontier.
+    ///
+    /// Requested by using
+    /// [`RegionShard::Frontier`](crate::shards::region::RegionShard::Frontier).
+    pub frontier: Option<bool>,
+    /// How the region's nations are voting on the current General Assembly resolution.
+    ///
+    /// Requested by using
+    /// [`RegionShard::GAVote`](crate::shards::region::RegionShard::GAVote).
+    pub ga_vote: Option<RegionWAVote>,
+    /// The governor of the region.
+    /// If the region does not have a governor, the field will be `Some(None)`.
+    ///
+    /// Only returned as part of the standard region response.
+    pub governor: Option<Option<String>>,
+    /// The 10 most recent [`Event`]s in the region.
+    ///
+    /// Requested by using
+    /// [`RegionShard::Happenings`](crate::shards::region::RegionShard::Happenings).
+    pub happenings: Option<Vec<Event>>,
+    /// The history of the region's delegates and embassies, as [`Event`]s.
+    ///
+    /// Requested by using
+    /// [`RegionShard::History`](crate::shards::region::RegionShard::History).
+    pub history: Option<Vec<Event>>,
+    /// The Unix timestamp of the region's last update.
+    ///
+    /// Requested by using
+    /// [`RegionShard::LastUpdate`](crate::shards::region::RegionShard::LastUpdate).
+    pub last_update: Option<u64>,
+    /// The Unix timestamp of the region's last major update.
+    ///
+    /// Requested by using
+    /// [`RegionShard::LastMajorUpdate`](crate::shards::region::RegionShard::LastMajorUpdate).
+    pub last_major_update: Option<u64>,
+    /// The Unix timestamp of the region's last minor update.
+    ///
+    /// Requested by using
+    /// [`RegionShard::LastMinorUpdate`](crate::shards::region::RegionShard::LastMinorUpdate).
+    pub last_minor_update: Option<u64>,
+    /// Messages posted on the regional message board.
+    ///
+    /// Requested and configured using
+    /// [`RegionShard::Messages`](crate::shards::region::RegionShard::Messages).
+    pub messages: Option<Vec<Message>>,
+    /// The list of all nations in the region.
+    ///
+    /// Requested by using
+    /// [`RegionShard::Nations`](crate::shards::region::RegionShard::Nations).
+    pub nations: Option<NameList>,
+    /// The number of nations in the region.
+    ///
+    /// Requested by using
+    /// [`RegionShard::NumNations`](crate::shards::region::RegionShard::NumNations).
+    pub num_nations: Option<u32>,
+    /// The number of World Assembly nations in the region.
+    ///
+    /// Requested by using
+    /// [`RegionShard::NumWANations`](crate::shards::region::RegionShard::NumWANations).
+    pub num_wa_nations: Option<u32>,
+    /// The regional officers.
+    ///
+    /// Requested by using
+    /// [`RegionShard::Officers`](crate::shards::region::RegionShard::Officers).
+    pub officers: Option<Vec<Officer>>,
+    /// The current regional poll.
+    /// If the region does not have a poll, the field will be `Some(None)`.
+    ///
+    /// Requested by using [`RegionShard::Poll`](crate::shards::region::RegionShard::Poll).
+    pub poll: Option<Option<Poll>>,
+    /// The power rating of the region (e.g. "Very High").
+    /// Note that this is currently a `String` representation,
+    /// but will eventually become its own type.
+    ///
+    /// Requested by using [`RegionShard::Power`](crate::shards::region::RegionShard::Power).
+    pub power: Option<String>,
+    /// How the region's nations are voting on the current Security Council resolution.
+    ///
+    /// Requested by using
+    /// [`RegionShard::SCVote`](crate::shards::region::RegionShard::SCVote).
+    pub sc_vote: Option<RegionWAVote>,
+    /// The tags the region has.
+    ///
+    /// Requested by using [`RegionShard::Tags`](crate::shards::region::RegionShard::Tags).
+    pub tags: Option<Vec<Tag>>,
+    /// The passed Security Council resolutions that target the region.
+    ///
+    /// Requested by using
+    /// [`RegionShard::WABadges`](crate::shards::region::RegionShard::WABadges).
+    pub wa_badges: Option<Vec<WABadge>>,
+    /// The list of World Assembly nations in the region.
+    ///
+    /// Requested by using
+    /// [`RegionShard::WANations`](crate::shards::region::RegionShard::WANations).
+    pub wa_nations: Option<NameList>,
+}
+
+impl Region {
+    /// Whether the delegate has an authority, counting [`OfficerAuthority::Executive`]
+    /// as every authority.
+    ///
+    /// This is `None` unless
+    /// [`RegionShard::DelegateAuth`](crate::shards::region::RegionShard::DelegateAuth)
+    /// was requested.
+    pub fn delegate_has_authority(&self, authority: OfficerAuthority) -> Option<bool> {
+        self.delegate_authority
+            .map(|authorities| authorities.grants(authority))
+    }
+
+    /// Whether a nation has an authority in the region,
+    /// as its governor, its delegate, or one of its officers.
+    ///
+    /// The governor has every authority except [`OfficerAuthority::WorldAssembly`].
+    /// Only the roles that were requested are checked, using
+    /// the standard region response for the governor,
+    /// [`RegionShard::Delegate`](crate::shards::region::RegionShard::Delegate) with
+    /// [`RegionShard::DelegateAuth`](crate::shards::region::RegionShard::DelegateAuth),
+    /// and [`RegionShard::Officers`](crate::shards::region::RegionShard::Officers).
+    pub fn can_nation(&self, nation: impl Into<NationName>, authority: OfficerAuthority) -> bool {
+        let nation = nation.into();
+        let is = |other: &str| canonical_eq(other, nation.as_str());
+        let governor = matches!(&self.governor, Some(Some(governor)) if is(governor))
+            && authority != OfficerAuthority::WorldAssembly;
+        let delegate = matches!(&self.delegate, Some(Some(delegate)) if is(delegate))
+            && self.delegate_has_authority(authority) == Some(true);
+        let officer = self
+            .officers
+            .iter()
+            .flatten()
+            .any(|officer| is(&officer.nation) && officer.has_authority(authority));
+        governor || delegate || officer
+    }
+
+    /// Computes statistics about the region's World Assembly members,
+    /// using [`RegionShard::NumNations`](crate::shards::region::RegionShard::NumNations)
+    /// or [`RegionShard::Nations`](crate::shards::region::RegionShard::Nations),
+    /// and [`RegionShard::NumWANations`](crate::shards::region::RegionShard::NumWANations)
+    /// or [`RegionShard::WANations`](crate::shards::region::RegionShard::WANations).
+    ///
+    /// The delegate's vote share needs
+    /// [`RegionShard::DelegateVotes`](crate::shards::region::RegionShard::DelegateVotes):
+    /// every member has one vote, and the delegate has one more for each endorsement.
+    /// Endorsement saturation needs the `endorsements` of the members,
+    /// such as from [`Client::get_endorsement_graph`](crate::client::Client::get_endorsement_graph).
+    ///
+    /// This is `None` if the number of nations or of members is not known.
+    /// ```rust
+    /// # use crustacean_states::parsers::region::Region;
+    /// let region = Region::from_xml(
+    ///     "<REGION id=\"testregionia\"><NUMNATIONS>10</NUMNATIONS>\
+    ///     <NUMUNNATIONS>4</NUMUNNATIONS><DELEGATEVOTES>4</DELEGATEVOTES></REGION>"
+    /// ).unwrap();
+    /// let stats = region.wa_stats(None).unwrap();
+    /// assert_eq!(stats.wa_percentage, 40.0);
+    /// // The delegate has 4 of the 7 votes.
+    /// assert_eq!(stats.delegate_vote_share.map(f64::round), Some(57.0));
+    /// ```
+    pub fn wa_stats(&self, endorsements: Option<&EndorsementGraph>) -> Option<RegionWaStats> {
+        let nations = self
+            .num_nations
+            .or_else(|| self.nations.as_ref().map(|n| n.len() as u32))?;
+        let wa_members = self
+            .num_wa_nations
+            .or_else(|| self.wa_nations.as_ref().map(|n| n.len() as u32))?;
+        let percent = |part: f64, whole: f64| (whole > 0.0).then(|| part / whole * 100.0);
+        let endorsement_saturation = endorsements.and_then(|graph| {
+            let members = graph.members().len() as f64;
+            let given = graph
+                .members()
+                .iter()
+                .map(|m| graph.endorsements_received(m.clone()))
+                .sum::<usize>();
+            percent(given as f64, members * (members - 1.0))
+        });
+        let delegate_vote_share = self.delegate_votes.and_then(|votes| {
+            let total = f64::from(wa_members.max(1) - 1) + f64::from(votes);
+            percent(f64::from(votes), total)
+        });
+        Some(RegionWaStats {
+            nations,
+            wa_members,
+            wa_percentage: percent(f64::from(wa_members), f64::from(nations)).unwrap_or_default(),
+            endorsement_saturation,
+            delegate_vote_share,
+        })
+    }
+
+    /// Checks the region's tags against a policy of tags it must and must not have,
+    /// returning every way the region breaks the policy.
+    /// An empty list means the region follows it.
+    ///
+    /// This is `None` unless [`RegionShard::Tags`](crate::shards::region::RegionShard::Tags)
+    /// was requested.
+    /// ```rust
+    /// # use crustacean_states::parsers::region::{Region, TagViolation};
+    /// # use crustacean_states::shards::{region::Tag, world::IncludeOrExcludeTag::{Include, Exclude}};
+    /// let region = Region::from_xml(
+    ///     "<REGION id=\"testregionia\"><TAGS><TAG>Fascist</TAG><TAG>Small</TAG></TAGS></REGION>"
+    /// ).unwrap();
+    /// let policy = [Include(Tag::RegionalGovernment), Exclude(Tag::Fascist), Exclude(Tag::Large)];
+    /// assert_eq!(
+    ///     region.tag_violations(&policy),
+    ///     Some(vec![
+    ///         TagViolation::Missing(Tag::RegionalGovernment),
+    ///         TagViolation::Forbidden(Tag::Fascist),
+    ///     ])
+    /// );
+    /// ```
+    pub fn tag_violations(&self, policy: &[IncludeOrExcludeTag]) -> Option<Vec<TagViolation>> {
+        let tags = self.tags.as_ref()?;
+        Some(
+            policy
+                .iter()
+                .filter_map(|rule| match rule {
+                    IncludeOrExcludeTag::Include(tag) if !tags.contains(tag) => {
+                        Some(TagViolation::Missing(tag.clone()))
+                    }
+                    IncludeOrExcludeTag::Exclude(tag) if tags.contains(tag) => {
+                        Some(TagViolation::Forbidden(tag.clone()))
+                    }
+                    _ => None,
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Statistics about the World Assembly members of a region,
+/// as returned by [`Region::wa_stats`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct RegionWaStats {
+    /// The number of nations in the region.
+    pub nations: u32,
+    /// The number of World Assembly members in the region.
+    pub wa_members: u32,
+    /// The percentage of nations that are World Assembly members.
+    pub wa_percentage: f64,
+    /// The percentage of all possible endorsements between members that have been given,
+    /// if the endorsements were fetched.
+    pub endorsement_saturation: Option<f64>,
+    /// The percentage of the region's World Assembly votes that the delegate casts,
+    /// if the delegate's votes were requested.
+    pub delegate_vote_share: Option<f64>,
+}
+
+/// A way that a region breaks a tag policy.
+/// See [`Region::tag_violations`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum TagViolation {
+    /// The region does not have a tag it must have.
+    Missing(Tag),
+    /// The region has a tag it must not have.
+    Forbidden(Tag),
+}
+
+/// An authority that a regional officer (or the delegate) can have.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum OfficerAuthority {
+    /// Can eject and ban nations, and has all other authorities.
+    /// Only the governor and an executive delegate have this authority.
+    Executive,
+    /// Can vote in the World Assembly on behalf of the region.
+    WorldAssembly,
+    /// Can change the governor's successor.
+    Succession,
+    /// Can change the region's flag, banner, World Factbook Entry, and tags.
+    Appearance,
+    /// Can eject and ban nations and change the region's password.
+    BorderControl,
+    /// Can suppress messages and send regional telegrams.
+    Communications,
+    /// Can open and close embassies.
+    Embassies,
+    /// Can create and delete regional polls.
+    Polls,
+}
+
+impl TryFrom<char> for OfficerAuthority {
+    type Error = IntoRegionError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            'X' => Ok(OfficerAuthority::Executive),
+            'W' => Ok(OfficerAuthority::WorldAssembly),
+            'S' => Ok(OfficerAuthority::Succession),
+            'A' => Ok(OfficerAuthority::Appearance),
+            'B' => Ok(OfficerAuthority::BorderControl),
+            'C' => Ok(OfficerAuthority::Communications),
+            'E' => Ok(OfficerAuthority::Embassies),
+            'P' => Ok(OfficerAuthority::Polls),
+            other => Err(IntoRegionError::BadAuthority(other)),
+        }
+    }
+}
+
+impl OfficerAuthority {
+    /// Every authority, in the order the API lists them.
+    pub const ALL: [OfficerAuthority; 8] = [
+        OfficerAuthority::Executive,
+        OfficerAuthority::WorldAssembly,
+        OfficerAuthority::Succession,
+        OfficerAuthority::Appearance,
+        OfficerAuthority::BorderControl,
+        OfficerAuthority::Communications,
+        OfficerAuthority::Embassies,
+        OfficerAuthority::Polls,
+    ];
+
+    /// The letter the API uses for the authority.
+    pub fn as_char(self) -> char {
+        match self {
+            OfficerAuthority::Executive => 'X',
+            OfficerAuthority::WorldAssembly => 'W',
+            OfficerAuthority::Succession => 'S',
+            OfficerAuthority::Appearance => 'A',
+            OfficerAuthority::BorderControl => 'B',
+            OfficerAuthority::Communications => 'C',
+            OfficerAuthority::Embassies => 'E',
+            OfficerAuthority::Polls => 'P',
+        }
+    }
+
+    fn bit(self) -> u8 {
+        1 << self as u8
+    }
+}
+
+/// A set of [`OfficerAuthority`]s, stored as bit flags.
+///
+/// It is parsed from and displayed as the letters the API uses:
+/// ```rust
+/// # use crustacean_states::parsers::region::{AuthoritySet, OfficerAuthority};
+/// let authorities = "BCP".parse::<AuthoritySet>().unwrap();
+/// assert!(authorities.contains(OfficerAuthority::Communications));
+/// assert!(!authorities.contains(OfficerAuthority::Embassies));
+///
+/// let more = authorities | OfficerAuthority::Embassies;
+/// assert_eq!(more.to_string(), "BCEP");
+/// assert_eq!(more.iter().count(), 4);
+/// ```
+#[derive(Clone, Copy, Default, Eq, Hash, PartialEq)]
+pub struct AuthoritySet(u8);
+
+impl AuthoritySet {
+    /// The set with no authorities.
+    pub const EMPTY: AuthoritySet = AuthoritySet(0);
+
+    /// The set with every authority.
+    pub const ALL: AuthoritySet = AuthoritySet(u8::MAX);
+
+    /// Whether the set has an authority.
+    pub fn contains(self, authority: OfficerAuthority) -> bool {
+        self.0 & authority.bit() != 0
+    }
+
+    /// Whether the set grants an authority,
+    /// either by having it or by having [`OfficerAuthority::Executive`].
+    pub fn grants(self, authority: OfficerAuthority) -> bool {
+        self.contains(authority) || self.contains(OfficerAuthority::Executive)
+    }
+
+    /// Adds an authority to the set.
+    pub fn insert(&mut self, authority: OfficerAuthority) {
+        self.0 |= authority.bit();
+    }
+
+    /// Removes an authority from the set.
+    pub fn remove(&mut self, authority: OfficerAuthority) {
+        self.0 &= !authority.bit();
+    }
+
+    /// The authorities in either set.
+    pub fn union(self, other: AuthoritySet) -> AuthoritySet {
+        AuthoritySet(self.0 | other.0)
+    }
+
+    /// The authorities in both sets.
+    pub fn intersection(self, other: AuthoritySet) -> AuthoritySet {
+        AuthoritySet(self.0 & other.0)
+    }
+
+    /// Whether every authority in this set is in `other`.
+    pub fn is_subset(self, other: AuthoritySet) -> bool {
+        self.0 & other.0 == self.0
+    }
+
+    /// The number of authorities in the set.
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    /// Whether the set has no authorities.
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Iterates over the authorities, in the order the API lists them.
+    pub fn iter(self) -> impl Iterator<Item = OfficerAuthority> {
+        OfficerAuthority::ALL
+            .into_iter()
+            .filter(move |authority| self.contains(*authority))
+    }
+}
+
+impl From<OfficerAuthority> for AuthoritySet {
+    fn from(value: OfficerAuthority) -> Self {
+        AuthoritySet(value.bit())
+    }
+}
+
+impl FromIterator<OfficerAuthority> for AuthoritySet {
+    fn from_iter<T: IntoIterator<Item = OfficerAuthority>>(iter: T) -> Self {
+        let mut set = AuthoritySet::EMPTY;
+        set.extend(iter);
+        set
+    }
+}
+
+impl Extend<OfficerAuthority> for AuthoritySet {
+    fn extend<T: IntoIterator<Item = OfficerAuthority>>(&mut self, iter: T) {
+        iter.into_iter()
+            .for_each(|authority| self.insert(authority));
+    }
+}
+
+impl IntoIterator for AuthoritySet {
+    type Item = OfficerAuthority;
+    type IntoIter = std::vec::IntoIter<OfficerAuthority>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter().collect::<Vec<_>>().into_iter()
+    }
+}
+
+impl<A: Into<AuthoritySet>> BitOr<A> for OfficerAuthority {
+    type Output = AuthoritySet;
+
+    fn bitor(self, rhs: A) -> Self::Output {
+        AuthoritySet::from(self).union(rhs.into())
+    }
+}
+
+impl<A: Into<AuthoritySet>> BitOr<A> for AuthoritySet {
+    type Output = AuthoritySet;
+
+    fn bitor(self, rhs: A) -> Self::Output {
+        self.union(rhs.into())
+    }
+}
+
+impl<A: Into<AuthoritySet>> BitOrAssign<A> for AuthoritySet {
+    fn bitor_assign(&mut self, rhs: A) {
+        *self = self.union(rhs.into());
+    }
+}
+
+impl<A: Into<AuthoritySet>> BitAnd<A> for AuthoritySet {
+    type Output = AuthoritySet;
+
+    fn bitand(self, rhs: A) -> Self::Output {
+        self.intersection(rhs.into())
+    }
+}
+
+impl FromStr for AuthoritySet {
+    type Err = IntoRegionError;
+
+    /// Parses the letters the API uses, such as `XWCE`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.chars().map(OfficerAuthority::try_from).collect()
+    }
+}
+
+impl Display for AuthoritySet {
+    /// Writes the letters the API uses, such as `XWCE`.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.iter()
+            .try_for_each(|authority| f.write_char(authority.as_char()))
+    }
+}
+
+impl Debug for AuthoritySet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+/// Serialized as a list of authorities, like a `Vec<OfficerAuthority>`.
+#[cfg(feature = "serde")]
+impl serde::Serialize for AuthoritySet {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for AuthoritySet {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::<OfficerAuthority>::deserialize(deserializer).map(AuthoritySet::from_iter)
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for AuthoritySet {
+    fn inline_schema() -> bool {
+        true
+    }
+
+    fn schema_name() -> Cow<'static, str> {
+        Vec::<OfficerAuthority>::schema_name()
+    }
+
+    fn json_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        Vec::<OfficerAuthority>::json_schema(generator)
+    }
+}
+
+/// Every nation in a region ranked on one World Census scale,
+/// as fetched by [`Client::region_census_report`](crate::client::Client::region_census_report).
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct RegionCensusReport {
+    /// The ID of the World Census scale.
+    pub scale: u8,
+    /// The nations, in order of their ranking.
+    pub nations: Vec<CensusRanksNation>,
+    /// The number of pages of ranks that were fetched.
+    pub pages: usize,
+    /// How long fetching every page took, including waiting for the rate limit.
+    pub elapsed: Duration,
+}
+
+/// A regional officer.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Officer {
+    /// The nation holding the office.
+    pub nation: String,
+    /// The name of the office.
+    pub office: String,
+    /// The authorities the officer has.
+    pub authority: AuthoritySet,
+    /// The Unix timestamp of when the officer was appointed.
+    pub time: u64,
+    /// The nation that appointed the officer.
+    pub by: String,
+    /// Where the officer is listed on the region's page.
+    pub order: i32,
+}
+
+impl Officer {
+    /// Whether the officer has an authority, counting [`OfficerAuthority::Executive`]
+    /// as every authority.
+    pub fn has_authority(&self, authority: OfficerAuthority) -> bool {
+        self.authority.grants(authority)
+    }
+}
+
+/// An embassy between this region and another.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Embassy {
+    /// The other region.
+    pub region: String,
+    /// The state of the embassy.
+    pub kind: EmbassyKind,
+}
+
+/// The state of an embassy.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum EmbassyKind {
+    /// The embassy has been built.
+    Established,
+    /// The embassy has been agreed to and is being built.
+    Pending,
+    /// This region has invited the other region to build an embassy.
+    Invited,
+    /// The other region has asked this region to build an embassy.
+    Requested,
+    /// The request for an embassy was denied.
+    Denied,
+    /// The embassy is being closed.
+    Closing,
+}
+
+/// Which nations in embassy regions can post on the regional message board.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum EmbassyPostingRights {
+    /// Nations in embassy regions cannot post.
+    NoEmbassyPosting,
+    /// Only the delegates and founders of embassy regions can post.
+    DelegatesAndFounders,
+    /// Only officers of embassy regions can post.
+    Officers,
+    /// Only officers of embassy regions with the communications authority can post.
+    CommunicationsOfficers,
+    /// Any nation in an embassy region can post.
+    Everybody,
+}
+
+/// The number of nations in a region voting for and against a World Assembly resolution.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct RegionWAVote {
+    /// The number of nations voting for the resolution.
+    pub for_votes: u32,
+    /// The number of nations voting against the resolution.
+    pub against_votes: u32,
+}
+
+/// A passed Security Council resolution targeting the region.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct WABadge {
+    /// The kind of resolution.
+    pub kind: WABadgeKind,
+    /// The ID of the Security Council resolution.
+    pub resolution: u16,
+}
+
+/// The kinds of Security Council resolutions that can target a region.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum WABadgeKind {
+    /// The region was commended.
+    Commend,
+    /// The region was condemned.
+    Condemn,
+    /// The region was liberated.
+    Liberate,
+    /// The region was injuncted.
+    Injunct,
+}
+
+/// A message posted on the regional message board.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Message {
+    /// The ID of the post.
+    pub id: u32,
+    /// The Unix timestamp of when the message was posted.
+    pub timestamp: u64,
+    /// The nation that posted the message.
+    pub nation: String,
+    /// Whether the message can be seen, and who suppressed it if it was suppressed.
+    pub status: MessageStatus,
+    /// The Unix timestamp of when the message was last edited, if it was edited.
+    pub edited: Option<NonZeroU64>,
+    /// The number of nations that liked the message.
+    pub likes: u32,
+    /// The nations that liked the message.
+    pub likers: Vec<String>,
+    /// If the message was posted by a nation in an embassy region, that region.
+    pub embassy: Option<String>,
+    /// The contents of the message, as BBCode.
+    pub text: String,
+}
+
+/// Whether a message on the regional message board can be seen.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum MessageStatus {
+    /// The message can be seen.
+    Visible,
+    /// The message was suppressed by a regional officer, but can still be viewed.
+    SuppressedByOfficer {
+        /// The nation that suppressed the message.
+        by: String,
+    },
+    /// The message was deleted by its author.
+    Deleted,
+    /// The message was suppressed by a moderator.
+    SuppressedByMod,
+}
+
+/// Represents any one of the errors
+/// that can go wrong between deserialization and creating the Region struct.
+#[derive(Debug, Error)]
+pub enum IntoRegionError {
+    /// A character could not be parsed as an [`OfficerAuthority`].
+    #[error("malformed officer authority: {0}")]
+    BadAuthority(char),
+    /// A `u8` could not be parsed as a `bool` because it was not `0` or `1`.
+    #[error("boolean cannot be derived from {0}")]
+    BadBooleanError(u8),
+    /// A `String` could not be parsed as an [`EmbassyKind`].
+    #[error("malformed embassy kind: {0}")]
+    BadEmbassyKind(String),
+    /// A `String` could not be parsed as [`EmbassyPostingRights`].
+    #[error("malformed embassy posting rights: {0}")]
+    BadEmbassyRmb(String),
+    /// A `u8` could not be parsed as a [`MessageStatus`].
+    #[error("malformed message status: {0}")]
+    BadMessageStatus(u8),
+    /// A `String` could not be parsed as a [`WABadgeKind`].
+    #[error("malformed WA badge: {0}")]
+    BadWABadge(String),
+    /// Something bad happened in deserialization.
+    #[error("deserialization failed")]
+    DeserializationError {
+        /// The error source. Look here for what went wrong.
+        #[from]
+        source: XmlError,
+    },
+    /// A message with the ID was suppressed by an officer, but the response did not say who.
+    #[error("message {0} was suppressed by an officer, but has no suppressor")]
+    NoSuppressorError(u32),
+    /// There was neither an `id` attribute in the `<REGION>` root tag nor a `<NAME>` tag.
+    #[error("could not find a region name in response")]
+    NoNameError,
+    /// No census data was created for this region.
+    #[error("could not find any census data in response")]
+    NoCensusDataError,
+}
@@ -0,0 +1,676 @@
+//! The World Assembly parser module.
+
+use crate::{
+    models::{
+        name::{NameList, NationName, RegionName},
+        timestamp::DAY,
+    },
+    parsers::{happenings::Event, XmlError},
+    shards::wa::{CouncilRequest, ResolutionShard, WACouncil, WACouncilShard, WARequest, WAShard},
+};
+use std::{
+    borrow::Cow,
+    convert::Infallible,
+    fmt::{Display, Formatter},
+    str::FromStr,
+    time::Duration,
+};
+use thiserror::Error;
+
+/// The World Assembly, or one of its councils.
+///
+/// Every field is an `Option`.
+/// This is because,
+/// depending on the [`WAShard`](crate::shards::wa::WAShard)s used
+/// to make the request,
+/// only certain fields will be returned.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct WorldAssembly {
+    /// The council that was requested.
+    /// This is `None` if only information about the World Assembly as a whole was requested.
+    pub council: Option<WACouncil>,
+    /// The number of nations in the World Assembly.
+    ///
+    /// Requested by using
+    /// [`WAGlobalShard::NumNations`](crate::shards::wa::WAGlobalShard::NumNations).
+    pub num_nations: Option<u32>,
+    /// The number of World Assembly delegates.
+    ///
+    /// Requested by using
+    /// [`WAGlobalShard::NumDelegates`](crate::shards::wa::WAGlobalShard::NumDelegates).
+    pub num_delegates: Option<u32>,
+    /// The delegates currently serving in the World Assembly.
+    ///
+    /// Requested by using
+    /// [`WAGlobalShard::Delegates`](crate::shards::wa::WAGlobalShard::Delegates).
+    pub delegates: Option<NameList>,
+    /// Every member of the World Assembly.
+    ///
+    /// Requested by using
+    /// [`WAGlobalShard::Members`](crate::shards::wa::WAGlobalShard::Members).
+    pub members: Option<NameList>,
+    /// Recent events in the council.
+    ///
+    /// Requested by using
+    /// [`WACouncilShard::Happenings`](crate::shards::wa::WACouncilShard::Happenings).
+    pub happenings: Option<Vec<Event>>,
+    /// A description of the most recent resolution to have been voted on, as HTML.
+    ///
+    /// Requested by using
+    /// [`WACouncilShard::LastResolution`](crate::shards::wa::WACouncilShard::LastResolution).
+    pub last_resolution: Option<String>,
+    /// The proposals currently submitted to the council.
+    ///
+    /// Requested by using
+    /// [`WACouncilShard::Proposals`](crate::shards::wa::WACouncilShard::Proposals).
+    pub proposals: Option<Vec<Proposal>>,
+    /// The resolution currently at vote.
+    /// If no resolution is at vote, the field will be `Some(None)`.
+    ///
+    /// Requested and configured using
+    /// [`WAShard::CurrentResolution`](crate::shards::wa::WAShard::CurrentResolution).
+    pub resolution: Option<Option<Resolution>>,
+}
+
+/// The resolution at vote in a World Assembly council, with its vote track and delegate votes,
+/// and the result of the last resolution.
+///
+/// Requested with [`Client::get_wa_snapshot`](crate::client::Client::get_wa_snapshot)
+/// or [`WaSnapshot::request`].
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct WaSnapshot {
+    /// The council that was requested.
+    pub council: WACouncil,
+    /// The resolution currently at vote, if there is one.
+    ///
+    /// Its [`vote_track`](Resolution::vote_track) and
+    /// [`vote_breakdown`](Resolution::vote_breakdown) are always available.
+    pub resolution: Option<Resolution>,
+    /// A description of the most recent resolution to have been voted on, as HTML.
+    pub last_resolution: Option<String>,
+}
+
+impl WaSnapshot {
+    /// The shards that make up a snapshot.
+    pub const SHARDS: &'static [WAShard<'static>] = &[
+        WAShard::CurrentResolution(Cow::Borrowed(&[
+            ResolutionShard::VoteTrack,
+            ResolutionShard::DelVotes,
+        ])),
+        WAShard::CouncilInfo(WACouncilShard::LastResolution),
+    ];
+
+    /// The request for a snapshot of a council, to be used with
+    /// [`Client::get_parsed`](crate::client::Client::get_parsed) or
+    /// [`Client::get_many`](crate::client::Client::get_many)
+    /// and turned into a snapshot with [`WaSnapshot::from`].
+    pub fn request(council: WACouncil) -> WARequest<'static> {
+        WARequest::Council(CouncilRequest::new(council, Self::SHARDS))
+    }
+}
+
+impl From<WorldAssembly> for WaSnapshot {
+    fn from(value: WorldAssembly) -> Self {
+        Self {
+            council: value.council.unwrap_or_default(),
+            resolution: value.resolution.flatten(),
+            last_resolution: value.last_resolution,
+        }
+    }
+}
+
+/// A proposal submitted to a World Assembly council.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Proposal {
+    /// The ID of the proposal.
+    pub id: String,
+    /// The category of the proposal.
+    pub category: ResolutionCategory,
+    /// The Unix timestamp of when the proposal was submitted.
+    pub created: u64,
+    /// The name of the proposal.
+    pub name: String,
+    /// The strength, area of effect, or target of the proposal, depending on the category.
+    pub option: ResolutionOption,
+    /// The nation that submitted the proposal.
+    pub proposed_by: NationName,
+    /// The delegates that approved the proposal.
+    pub approvals: Vec<String>,
+}
+
+/// A resolution at vote in a World Assembly council,
+/// or a resolution that has passed when requested with
+/// [`WARequest::PastResolution`](crate::shards::wa::WARequest::PastResolution).
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Resolution {
+    /// The name of the resolution.
+    pub name: String,
+    /// The category of the resolution.
+    pub category: ResolutionCategory,
+    /// The strength, area of effect, or target of the resolution, depending on the category.
+    pub option: ResolutionOption,
+    /// The Unix timestamp of when the resolution was submitted.
+    pub created: u64,
+    /// The Unix timestamp of when the resolution was brought to vote.
+    pub promoted: Option<u64>,
+    /// The nation that proposed the resolution.
+    pub proposed_by: NationName,
+    /// The text of the resolution, as BBCode.
+    pub text: Option<String>,
+    /// The number of the resolution in its council.
+    /// This is only sent for resolutions that have passed.
+    pub id: Option<u32>,
+    /// The Unix timestamp of when the resolution passed.
+    /// This is only sent for resolutions that have passed.
+    pub implemented: Option<u64>,
+    /// The number of the resolution that repealed this one, if it has been repealed.
+    pub repealed_by: Option<u32>,
+    /// The Unix timestamp of when the resolution was repealed, if it has been repealed.
+    pub repealed: Option<u64>,
+    /// The number of nations voting for the resolution.
+    pub total_nations_for: u32,
+    /// The number of nations voting against the resolution.
+    pub total_nations_against: u32,
+    /// The number of votes for the resolution, including delegate votes.
+    pub total_votes_for: u32,
+    /// The number of votes against the resolution, including delegate votes.
+    pub total_votes_against: u32,
+    /// The nations voting for the resolution.
+    ///
+    /// Requested by using
+    /// [`ResolutionShard::Voters`](crate::shards::wa::ResolutionShard::Voters).
+    pub votes_for: Option<Vec<String>>,
+    /// The nations voting against the resolution.
+    ///
+    /// Requested by using
+    /// [`ResolutionShard::Voters`](crate::shards::wa::ResolutionShard::Voters).
+    pub votes_against: Option<Vec<String>>,
+    /// The total votes for the resolution at the end of every hour since it was brought to vote.
+    /// See [`Resolution::vote_track`] to read them with their timestamps.
+    ///
+    /// Requested by using
+    /// [`ResolutionShard::VoteTrack`](crate::shards::wa::ResolutionShard::VoteTrack).
+    pub vote_track_for: Option<Vec<u32>>,
+    /// The total votes against the resolution at the end of every hour since it was brought to vote.
+    /// See [`Resolution::vote_track`] to read them with their timestamps.
+    ///
+    /// Requested by using
+    /// [`ResolutionShard::VoteTrack`](crate::shards::wa::ResolutionShard::VoteTrack).
+    pub vote_track_against: Option<Vec<u32>>,
+    /// Every vote and vote withdrawal made by a delegate, in chronological order.
+    ///
+    /// Requested by using
+    /// [`ResolutionShard::DelLog`](crate::shards::wa::ResolutionShard::DelLog).
+    pub delegate_log: Option<Vec<DelegateLogEntry>>,
+    /// The delegates currently voting for the resolution.
+    ///
+    /// Requested by using
+    /// [`ResolutionShard::DelVotes`](crate::shards::wa::ResolutionShard::DelVotes).
+    pub delegate_votes_for: Option<Vec<DelegateVote>>,
+    /// The delegates currently voting against the resolution.
+    ///
+    /// Requested by using
+    /// [`ResolutionShard::DelVotes`](crate::shards::wa::ResolutionShard::DelVotes).
+    pub delegate_votes_against: Option<Vec<DelegateVote>>,
+}
+
+//noinspection SpellCheckingInspection
+/// The category of a proposal or resolution.
+///
+/// The General Assembly and the Security Council have different categories,
+/// except for [`ResolutionCategory::Repeal`], which both councils use.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+#[allow(missing_docs)]
+pub enum ResolutionCategory {
+    AdvancementOfIndustry,
+    Bookkeeping,
+    CivilRights,
+    EducationAndCreativity,
+    Environmental,
+    FreeTrade,
+    FurthermentOfDemocracy,
+    GlobalDisarmament,
+    GunControl,
+    Health,
+    HumanRights,
+    InternationalSecurity,
+    MoralDecency,
+    PoliticalStability,
+    RecreationalDrugUse,
+    Regulation,
+    SocialJustice,
+    /// Repeals an earlier resolution of the same council.
+    Repeal,
+    /// A Security Council commendation.
+    Commendation,
+    /// A Security Council condemnation.
+    Condemnation,
+    /// A Security Council declaration.
+    Declaration,
+    /// A Security Council injunction.
+    Injunction,
+    /// A Security Council liberation.
+    Liberation,
+    /// A category that this crate does not know about yet, as it was sent.
+    Unknown(String),
+}
+
+impl ResolutionCategory {
+    /// The name of the category, as it is sent by the API.
+    //noinspection SpellCheckingInspection
+    pub fn as_str(&self) -> &str {
+        match self {
+            ResolutionCategory::AdvancementOfIndustry => "Advancement of Industry",
+            ResolutionCategory::Bookkeeping => "Bookkeeping",
+            ResolutionCategory::CivilRights => "Civil Rights",
+            ResolutionCategory::EducationAndCreativity => "Education and Creativity",
+            ResolutionCategory::Environmental => "Environmental",
+            ResolutionCategory::FreeTrade => "Free Trade",
+            ResolutionCategory::FurthermentOfDemocracy => "Furtherment of Democracy",
+            ResolutionCategory::GlobalDisarmament => "Global Disarmament",
+            ResolutionCategory::GunControl => "Gun Control",
+            ResolutionCategory::Health => "Health",
+            ResolutionCategory::HumanRights => "Human Rights",
+            ResolutionCategory::InternationalSecurity => "International Security",
+            ResolutionCategory::MoralDecency => "Moral Decency",
+            ResolutionCategory::PoliticalStability => "Political Stability",
+            ResolutionCategory::RecreationalDrugUse => "Recreational Drug Use",
+            ResolutionCategory::Regulation => "Regulation",
+            ResolutionCategory::SocialJustice => "Social Justice",
+            ResolutionCategory::Repeal => "Repeal",
+            ResolutionCategory::Commendation => "Commendation",
+            ResolutionCategory::Condemnation => "Condemnation",
+            ResolutionCategory::Declaration => "Declaration",
+            ResolutionCategory::Injunction => "Injunction",
+            ResolutionCategory::Liberation => "Liberation",
+            ResolutionCategory::Unknown(category) => category,
+        }
+    }
+}
+
+impl FromStr for ResolutionCategory {
+    type Err = Infallible;
+
+    /// Categories that are not recognized become [`ResolutionCategory::Unknown`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "Advancement of Industry" => ResolutionCategory::AdvancementOfIndustry,
+            "Bookkeeping" => ResolutionCategory::Bookkeeping,
+            "Civil Rights" => ResolutionCategory::CivilRights,
+            "Education and Creativity" => ResolutionCategory::EducationAndCreativity,
+            "Environmental" => ResolutionCategory::Environmental,
+            "Free Trade" => ResolutionCategory::FreeTrade,
+            "Furtherment of Democracy" => ResolutionCategory::FurthermentOfDemocracy,
+            "Global Disarmament" => ResolutionCategory::GlobalDisarmament,
+            "Gun Control" => ResolutionCategory::GunControl,
+            "Health" => ResolutionCategory::Health,
+            "Human Rights" => ResolutionCategory::HumanRights,
+            "International Security" => ResolutionCategory::InternationalSecurity,
+            "Moral Decency" => ResolutionCategory::MoralDecency,
+            "Political Stability" => ResolutionCategory::PoliticalStability,
+            "Recreational Drug Use" => ResolutionCategory::RecreationalDrugUse,
+            "Regulation" => ResolutionCategory::Regulation,
+            "Social Justice" => ResolutionCategory::SocialJustice,
+            "Repeal" => ResolutionCategory::Repeal,
+            "Commendation" => ResolutionCategory::Commendation,
+            "Condemnation" => ResolutionCategory::Condemnation,
+            "Declaration" => ResolutionCategory::Declaration,
+            "Injunction" => ResolutionCategory::Injunction,
+            "Liberation" => ResolutionCategory::Liberation,
+            other => ResolutionCategory::Unknown(other.to_string()),
+        })
+    }
+}
+
+impl Display for ResolutionCategory {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The strength of a General Assembly proposal or resolution.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[allow(missing_docs)]
+pub enum Strength {
+    Mild,
+    Significant,
+    Strong,
+}
+
+/// What the option of a proposal or resolution means, which depends on its category.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum ResolutionOption {
+    /// The strength of a General Assembly proposal.
+    Strength(Strength),
+    /// The ID of the resolution that a repeal would repeal, in the same council.
+    Repeals(u32),
+    /// The nation or region targeted by a Security Council proposal.
+    Target(ScTarget),
+    /// Any other option, such as an area of effect, as it was sent.
+    Other(String),
+}
+
+impl ResolutionOption {
+    /// Reads the option the way its category uses it.
+    pub(crate) fn parse(option: String, category: &ResolutionCategory) -> Self {
+        if let (ResolutionCategory::Repeal, Ok(id)) = (category, option.parse::<u32>()) {
+            return ResolutionOption::Repeals(id);
+        }
+        match option.as_str() {
+            "Mild" => ResolutionOption::Strength(Strength::Mild),
+            "Significant" => ResolutionOption::Strength(Strength::Significant),
+            "Strong" => ResolutionOption::Strength(Strength::Strong),
+            target => match ScTarget::from_option(target) {
+                Some(target) => ResolutionOption::Target(target),
+                None => ResolutionOption::Other(option),
+            },
+        }
+    }
+}
+
+/// The nation or region that a Security Council resolution is about.
+///
+/// The API sends these as `N:nation_name` and `R:region_name`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum ScTarget {
+    /// A nation.
+    Nation(NationName),
+    /// A region.
+    Region(RegionName),
+}
+
+impl ScTarget {
+    fn from_option(option: &str) -> Option<Self> {
+        if let Some(nation) = option.strip_prefix("N:") {
+            Some(ScTarget::Nation(NationName::new(nation)))
+        } else {
+            option
+                .strip_prefix("R:")
+                .map(|region| ScTarget::Region(RegionName::new(region)))
+        }
+    }
+}
+
+/// What a Security Council resolution does to its target.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum ScAction {
+    /// The target is commended, and receives a commendation badge.
+    Commend,
+    /// The target is condemned, and receives a condemnation badge.
+    Condemn,
+    /// The region is liberated, so its password can be removed.
+    Liberate,
+    /// The target is placed under an injunction.
+    Injunct,
+}
+
+impl ResolutionCategory {
+    /// What a resolution in this category does to its target,
+    /// if it is a Security Council category with a target.
+    pub fn sc_action(&self) -> Option<ScAction> {
+        match self {
+            ResolutionCategory::Commendation => Some(ScAction::Commend),
+            ResolutionCategory::Condemnation => Some(ScAction::Condemn),
+            ResolutionCategory::Liberation => Some(ScAction::Liberate),
+            ResolutionCategory::Injunction => Some(ScAction::Injunct),
+            _ => None,
+        }
+    }
+}
+
+fn sc_target<'a>(
+    category: &ResolutionCategory,
+    option: &'a ResolutionOption,
+) -> Option<(ScAction, &'a ScTarget)> {
+    match option {
+        ResolutionOption::Target(target) => Some((category.sc_action()?, target)),
+        _ => None,
+    }
+}
+
+/// The share of delegates, in percent, that must approve a proposal for it to reach quorum.
+pub const QUORUM_PERCENT: u32 = 6;
+
+/// How long a proposal has to reach quorum before it lapses.
+pub const PROPOSAL_LIFETIME: Duration = Duration::from_secs(3 * DAY);
+
+impl Proposal {
+    /// What the proposal would do, and to whom, if it is a Security Council proposal with a target.
+    pub fn sc_target(&self) -> Option<(ScAction, &ScTarget)> {
+        sc_target(&self.category, &self.option)
+    }
+
+    /// How close the proposal is to quorum,
+    /// given the number of delegates in the World Assembly
+    /// and the current Unix timestamp.
+    pub fn quorum_status(&self, num_delegates: u32, now: u64) -> QuorumStatus {
+        let quorum = (num_delegates * QUORUM_PERCENT).div_ceil(100).max(1);
+        let approvals = self.approvals.len() as u32;
+        let lapses = self.created + PROPOSAL_LIFETIME.as_secs();
+        QuorumStatus {
+            approvals,
+            quorum,
+            approvals_needed: quorum.saturating_sub(approvals),
+            time_left: Duration::from_secs(lapses.saturating_sub(now)),
+        }
+    }
+}
+
+impl WorldAssembly {
+    /// How close each proposal is to quorum, given the current Unix timestamp.
+    ///
+    /// This is `None` unless both
+    /// [`WAGlobalShard::NumDelegates`](crate::shards::wa::WAGlobalShard::NumDelegates) and
+    /// [`WACouncilShard::Proposals`](crate::shards::wa::WACouncilShard::Proposals)
+    /// were requested.
+    pub fn quorum_statuses(&self, now: u64) -> Option<Vec<(&Proposal, QuorumStatus)>> {
+        let num_delegates = self.num_delegates?;
+        Some(
+            self.proposals
+                .as_ref()?
+                .iter()
+                .map(|p| (p, p.quorum_status(num_delegates, now)))
+                .collect(),
+        )
+    }
+}
+
+/// How close a proposal is to reaching quorum and going to vote.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct QuorumStatus {
+    /// The number of delegates that have approved the proposal.
+    pub approvals: u32,
+    /// The number of approvals needed to reach quorum.
+    pub quorum: u32,
+    /// The number of approvals still needed to reach quorum.
+    pub approvals_needed: u32,
+    /// How long is left before the proposal lapses.
+    pub time_left: Duration,
+}
+
+impl QuorumStatus {
+    /// Whether the proposal has reached quorum.
+    pub fn has_quorum(&self) -> bool {
+        self.approvals_needed == 0
+    }
+
+    /// The number of days left before the proposal lapses, counting any part of a day as a day.
+    pub fn days_left(&self) -> u64 {
+        self.time_left.as_secs().div_ceil(DAY)
+    }
+}
+
+impl Resolution {
+    /// What the resolution does, and to whom, if it is a Security Council resolution with a target.
+    pub fn sc_target(&self) -> Option<(ScAction, &ScTarget)> {
+        sc_target(&self.category, &self.option)
+    }
+
+    /// The vote totals at the end of every hour since the resolution was brought to vote,
+    /// oldest first.
+    ///
+    /// This is `None` unless the resolution was requested with
+    /// [`ResolutionShard::VoteTrack`](crate::shards::wa::ResolutionShard::VoteTrack).
+    pub fn vote_track(&self) -> Option<Vec<VoteTrackPoint>> {
+        let start = self.promoted.unwrap_or(self.created);
+        let votes_for = self.vote_track_for.as_ref()?;
+        let votes_against = self.vote_track_against.as_ref()?;
+        Some(
+            votes_for
+                .iter()
+                .zip(votes_against)
+                .enumerate()
+                .map(|(hour, (&votes_for, &votes_against))| VoteTrackPoint {
+                    timestamp: start + hour as u64 * 3600,
+                    votes_for,
+                    votes_against,
+                })
+                .collect(),
+        )
+    }
+
+    /// Splits the votes on the resolution into the votes cast by delegates,
+    /// including the weight of their endorsements,
+    /// and the votes cast by every other member.
+    ///
+    /// This is `None` unless the resolution was requested with
+    /// [`ResolutionShard::DelVotes`](crate::shards::wa::ResolutionShard::DelVotes).
+    pub fn vote_breakdown(&self) -> Option<VoteBreakdown> {
+        let delegates_for = self.delegate_votes_for.as_ref()?;
+        let delegates_against = self.delegate_votes_against.as_ref()?;
+        let weight = |votes: &[DelegateVote]| votes.iter().map(|v| v.votes).sum::<u32>();
+        let delegate_weight_for = weight(delegates_for);
+        let delegate_weight_against = weight(delegates_against);
+        Some(VoteBreakdown {
+            delegates_for: delegates_for.len() as u32,
+            delegates_against: delegates_against.len() as u32,
+            delegate_weight_for,
+            delegate_weight_against,
+            members_for: self
+                .total_nations_for
+                .saturating_sub(delegates_for.len() as u32),
+            members_against: self
+                .total_nations_against
+                .saturating_sub(delegates_against.len() as u32),
+        })
+    }
+}
+
+/// The votes on a resolution, split between delegates and other members.
+///
+/// Delegates' votes are weighted by their endorsements,
+/// so `delegate_weight_for + members_for` is the resolution's
+/// [`total_votes_for`](Resolution::total_votes_for).
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct VoteBreakdown {
+    /// The number of delegates voting for the resolution.
+    pub delegates_for: u32,
+    /// The number of delegates voting against the resolution.
+    pub delegates_against: u32,
+    /// The total weight of the delegates voting for the resolution.
+    pub delegate_weight_for: u32,
+    /// The total weight of the delegates voting against the resolution.
+    pub delegate_weight_against: u32,
+    /// The number of members other than delegates voting for the resolution.
+    pub members_for: u32,
+    /// The number of members other than delegates voting against the resolution.
+    pub members_against: u32,
+}
+
+/// The vote totals on a resolution at one point in time.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct VoteTrackPoint {
+    /// The Unix timestamp of the totals.
+    pub timestamp: u64,
+    /// The total votes for the resolution.
+    pub votes_for: u32,
+    /// The total votes against the resolution.
+    pub votes_against: u32,
+}
+
+/// An action taken by a delegate on a resolution.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct DelegateLogEntry {
+    /// The Unix timestamp of when the action was taken.
+    pub timestamp: u64,
+    /// The delegate.
+    pub nation: String,
+    /// What the delegate did.
+    pub action: DelegateAction,
+    /// The voting power of the delegate at the time.
+    pub votes: u32,
+}
+
+/// What a delegate did on a resolution.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum DelegateAction {
+    /// The delegate voted for the resolution.
+    For,
+    /// The delegate voted against the resolution.
+    Against,
+    /// The delegate withdrew their vote.
+    Withdrew,
+}
+
+/// A delegate's current vote on a resolution.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct DelegateVote {
+    /// The delegate.
+    pub nation: String,
+    /// The voting power of the delegate.
+    pub votes: u32,
+    /// The Unix timestamp of when the delegate voted.
+    pub timestamp: u64,
+}
+
+/// Represents any one of the errors
+/// that can go wrong between deserialization and creating the WorldAssembly struct.
+#[derive(Debug, Error)]
+pub enum IntoWAError {
+    /// A `String` could not be parsed as a [`WACouncil`].
+    #[error("malformed council: {0}")]
+    BadCouncil(String),
+    /// A `String` could not be parsed as a [`DelegateAction`].
+    #[error("malformed delegate action: {0}")]
+    BadDelegateAction(String),
+    /// Something bad happened in deserialization.
+    #[error("deserialization failed")]
+    DeserializationError {
+        /// The error source. Look here for what went wrong.
+        #[from]
+        source: XmlError,
+    },
+}
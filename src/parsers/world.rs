@@ -0,0 +1,189 @@
+//! The world parser module.
+
+use crate::{
+    models::name::NameList,
+    parsers::{
+        happenings::Event, nation::BannerId, CensusData, CensusRanks, Dispatch, Poll, XmlError,
+    },
+};
+use thiserror::Error;
+
+/// The world, with every piece of information you could ask for!
+///
+/// Every field is an `Option`.
+/// This is because,
+/// depending on the [`WorldShard`](crate::shards::world::WorldShard)s used
+/// to make the request,
+/// only certain fields will be returned.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct World {
+    /// Information about the requested banners.
+    ///
+    /// Requested by using [`WorldShard::Banner`](crate::shards::world::WorldShard::Banner).
+    pub banners: Option<Vec<Banner>>,
+    /// Information on the world's score on the World Census.
+    /// If current data was requested (the default),
+    /// the resulting data will be found in the [`CensusData::Current`] variant,
+    /// but if historical data was requested,
+    /// the resulting data will be found in the [`CensusData::Historical`] variant.
+    ///
+    /// Requested and configured
+    /// using [`WorldShard::Census`](crate::shards::world::WorldShard::Census).
+    pub census: Option<CensusData>,
+    /// The ID of the World Census scale featured today.
+    ///
+    /// Requested by using [`WorldShard::CensusId`](crate::shards::world::WorldShard::CensusId).
+    pub census_id: Option<u8>,
+    /// The descriptions of a World Census scale.
+    ///
+    /// Requested by using
+    /// [`WorldShard::CensusDesc`](crate::shards::world::WorldShard::CensusDesc).
+    pub census_desc: Option<CensusDescription>,
+    /// The name of a World Census scale.
+    ///
+    /// Requested by using
+    /// [`WorldShard::CensusName`](crate::shards::world::WorldShard::CensusName).
+    pub census_name: Option<String>,
+    /// How nations rank on a World Census scale.
+    ///
+    /// Requested and configured using
+    /// [`WorldShard::CensusRanks`](crate::shards::world::WorldShard::CensusRanks).
+    pub census_ranks: Option<CensusRanks>,
+    /// The unit a World Census scale is measured in.
+    ///
+    /// Requested by using
+    /// [`WorldShard::CensusScale`](crate::shards::world::WorldShard::CensusScale).
+    pub census_scale: Option<String>,
+    /// The title given to the nation that ranks highest on a World Census scale.
+    ///
+    /// Requested by using
+    /// [`WorldShard::CensusTitle`](crate::shards::world::WorldShard::CensusTitle).
+    pub census_title: Option<String>,
+    /// A dispatch, including its text.
+    ///
+    /// Requested by using [`WorldShard::Dispatch`](crate::shards::world::WorldShard::Dispatch).
+    pub dispatch: Option<Dispatch>,
+    /// A list of dispatches, without their text.
+    ///
+    /// Requested and configured using
+    /// [`WorldShard::DispatchList`](crate::shards::world::WorldShard::DispatchList).
+    pub dispatch_list: Option<Vec<Dispatch>>,
+    /// The region featured today.
+    ///
+    /// Requested by using
+    /// [`WorldShard::FeaturedRegion`](crate::shards::world::WorldShard::FeaturedRegion).
+    pub featured_region: Option<String>,
+    /// Recent [`Event`]s in the world.
+    ///
+    /// Requested and configured using
+    /// [`WorldShard::Happenings`](crate::shards::world::WorldShard::Happenings).
+    pub happenings: Option<Vec<Event>>,
+    /// The ID of the most recent event.
+    ///
+    /// Requested by using
+    /// [`WorldShard::LastEventId`](crate::shards::world::WorldShard::LastEventId).
+    pub last_event_id: Option<u32>,
+    /// The list of all nations in the world.
+    ///
+    /// Requested by using [`WorldShard::Nations`](crate::shards::world::WorldShard::Nations).
+    pub nations: Option<NameList>,
+    /// The 50 most recently founded nations.
+    ///
+    /// Requested by using
+    /// [`WorldShard::NewNations`](crate::shards::world::WorldShard::NewNations).
+    pub new_nations: Option<Vec<String>>,
+    /// The number of nations in the world.
+    ///
+    /// Requested by using
+    /// [`WorldShard::NumNations`](crate::shards::world::WorldShard::NumNations).
+    pub num_nations: Option<u32>,
+    /// The number of regions in the world.
+    ///
+    /// Requested by using
+    /// [`WorldShard::NumRegions`](crate::shards::world::WorldShard::NumRegions).
+    pub num_regions: Option<u32>,
+    /// A regional poll.
+    ///
+    /// Requested by using [`WorldShard::Poll`](crate::shards::world::WorldShard::Poll).
+    pub poll: Option<Poll>,
+    /// The list of all regions in the world,
+    /// or only the regions with the requested tags.
+    ///
+    /// Requested by using [`WorldShard::Regions`](crate::shards::world::WorldShard::Regions)
+    /// or [`WorldShard::RegionsByTag`](crate::shards::world::WorldShard::RegionsByTag).
+    pub regions: Option<NameList>,
+    /// The number of telegrams waiting to be delivered.
+    ///
+    /// Requested by using [`WorldShard::TGQueue`](crate::shards::world::WorldShard::TGQueue).
+    pub tg_queue: Option<TGQueue>,
+}
+
+/// A banner that can be displayed on a nation's page.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Banner {
+    /// The ID of the banner.
+    pub id: BannerId,
+    /// The name of the banner.
+    pub name: String,
+    /// What a nation needs in order to unlock the banner.
+    pub validity: String,
+}
+
+/// The descriptions of a World Census scale.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct CensusDescription {
+    /// The description used for nations.
+    pub nation: String,
+    /// The description used for regions.
+    pub region: String,
+}
+
+/// The number of telegrams in each queue waiting to be delivered.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct TGQueue {
+    /// Telegrams sent manually.
+    pub manual: u32,
+    /// Telegrams sent through mass telegram stamps.
+    pub mass: u32,
+    /// Telegrams sent through the API.
+    pub api: u32,
+}
+
+impl TGQueue {
+    /// The number of telegrams waiting in every queue.
+    pub fn total(&self) -> u64 {
+        u64::from(self.manual) + u64::from(self.mass) + u64::from(self.api)
+    }
+}
+
+/// Represents any one of the errors
+/// that can go wrong between deserialization and creating the World struct.
+#[derive(Debug, Error)]
+pub enum IntoWorldError {
+    /// A string could not be parsed as a banner ID.
+    #[error("malformed banner id: {0}")]
+    BadBannerId(String),
+    /// A `String`
+    /// could not be parsed as a [`DispatchCategory`](crate::models::dispatch::DispatchCategory).
+    #[error("malformed dispatch category: {0}")]
+    BadDispatchCategory(String),
+    /// Something bad happened in deserialization.
+    #[error("deserialization failed")]
+    DeserializationError {
+        /// The error source. Look here for what went wrong.
+        #[from]
+        source: XmlError,
+    },
+    /// No census data was created for the world.
+    #[error("could not find any census data in response")]
+    NoCensusDataError,
+}
@@ -8,41 +8,110 @@
 //! - for the same region, or
 //! - for the same World Assembly council, or
 //! - for the world.
+//!
 //! Second, it is not possible to make two requests that use extra parameters with the same name.
-//! Right now, `crustacean-states` allows for parameters to be overwritten.
-//! In the future, it may be possible to create a series of requests that do not overlap.
+//! Shards that set the same parameter to the same value can be combined,
+//! but if two shards disagree (e.g. two census shards with different scales),
+//! [`NSRequest::as_url`] returns [`RequestBuildError::ConflictingShards`].
+//!
+//! Requests can also be rebuilt from existing API URLs with [`RequestUrl`].
 
 pub mod nation;
 pub mod region;
+pub mod telegram;
+#[cfg(feature = "parsers")]
+pub mod typed;
 pub mod wa;
 pub mod world;
 
+#[cfg(feature = "parsers")]
+use crate::parsers::{NSResponse, WithXml};
 use itertools::Itertools;
-use reqwest::Url;
 use std::{
+    borrow::{Borrow, Cow},
     collections::HashMap,
     fmt::Debug,
     hash::Hash,
     num::{NonZeroU32, NonZeroU64, NonZeroU8},
+    str::FromStr,
+    sync::OnceLock,
 };
-use strum::Display;
+use strum::{Display, EnumString, IntoStaticStr};
+use thiserror::Error;
+use url::{ParseError, Url};
 
 pub(crate) const BASE_URL: &str = "https://www.nationstates.net/cgi-bin/api.cgi?";
 
-/// Type that maps extra parameters in the query to their values.
-/// The HashMap is from parameter keys to values.
-/// The Vec is the order of keys.
+/// The version of the NationStates API that this crate was written for.
+///
+/// Every request asks for it unless told otherwise
+/// with [`NSRequest::with_api_version`].
+pub const API_VERSION: u8 = 12;
+
+/// Parameters that say what is being requested, which cannot be set as extra parameters.
+///
+/// The API version, `v`, is set with [`NSRequest::with_api_version`].
+pub const RESERVED_PARAMS: [&str; 5] = ["nation", "region", "wa", "q", "v"];
+
+/// The parameters in the query, in the order they were first given,
+/// along with the first conflict found between two values for the same key.
+///
+/// Requests only have a handful of parameters, so they are kept in a `Vec` rather than a map.
+/// Values that the request already holds as text are borrowed instead of copied.
+/// Outside of this crate, parameters can only be read; see [`NSRequest::params`].
 #[derive(Debug, Default)]
-pub(crate) struct Params<'a>(HashMap<&'a str, String>, Vec<&'a str>);
+pub struct Params<'a> {
+    params: Vec<(Cow<'a, str>, Cow<'a, str>)>,
+    conflict: Option<RequestBuildError>,
+}
 
 impl<'a> Params<'a> {
+    /// Adds a key and value at `index`, recording a conflict if the key already has a different value.
+    fn add(&mut self, index: usize, k: &'a str, v: Cow<'a, str>) {
+        match self.get(k) {
+            Some(old) if old == v => {}
+            Some(old) => {
+                if self.conflict.is_none() {
+                    self.conflict = Some(RequestBuildError::ConflictingShards {
+                        key: k.to_string(),
+                        first: old.to_string(),
+                        second: v.into_owned(),
+                    });
+                }
+            }
+            None => self.params.insert(index, (Cow::Borrowed(k), v)),
+        }
+    }
+
+    /// The value of a parameter, if it has been set.
+    pub fn get(&self, k: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(key, _)| key == k)
+            .map(|(_, v)| v.as_ref())
+    }
+
+    /// Iterates over the keys and values of the parameters, in order.
+    pub fn iter(&self) -> ParamsIter<'_, 'a> {
+        self.into_iter()
+    }
+
+    /// The number of parameters.
+    pub fn len(&self) -> usize {
+        self.params.len()
+    }
+
+    /// Whether there are no parameters.
+    pub fn is_empty(&self) -> bool {
+        self.params.is_empty()
+    }
+
     pub(crate) fn insert_on<T>(&mut self, k: &'a str, v: &Option<T>) -> &mut Self
     where
         T: ToString,
     {
         if let Some(s) = v {
-            self.0.insert(k, s.to_string());
-            self.1.push(k);
+            self.add(self.params.len(), k, Cow::Owned(s.to_string()));
         }
         self
     }
@@ -53,25 +122,24 @@ impl<'a> Params<'a> {
         Self::insert_on(self, k, &Some(v))
     }
 
-    pub(crate) fn insert_front<T>(&mut self, k: &'a str, v: T) -> &mut Self
-    where
-        T: ToString,
-    {
-        self.0.insert(k, v.to_string());
-        self.1.insert(0, k);
+    /// Adds a value that is already text, without copying it if it is borrowed.
+    pub(crate) fn insert_str(&mut self, k: &'a str, v: impl Into<Cow<'a, str>>) -> &mut Self {
+        self.add(self.params.len(), k, v.into());
+        self
+    }
+
+    pub(crate) fn insert_front(&mut self, k: &'a str, v: impl Into<Cow<'a, str>>) -> &mut Self {
+        self.add(0, k, v.into());
         self
     }
 
     pub(crate) fn insert_scale(&mut self, scale: &CensusScales) -> &mut Self {
-        self.insert_on(
-            "scale",
-            &match scale {
-                CensusScales::One(scale) => Some(scale.to_string()),
-                CensusScales::Many(scales) => Some(scales.iter().join("+")),
-                CensusScales::All => Some(String::from("all")),
-                CensusScales::Today => None,
-            },
-        )
+        match scale {
+            CensusScales::One(scale) => self.insert("scale", scale),
+            CensusScales::Many(scales) => self.insert_str("scale", scales.iter().join("+")),
+            CensusScales::All => self.insert_str("scale", "all"),
+            CensusScales::Today => self,
+        }
     }
 
     pub(crate) fn insert_rank_scale(&mut self, scale: &Option<NonZeroU8>) -> &mut Self {
@@ -79,13 +147,15 @@ impl<'a> Params<'a> {
     }
 
     pub(crate) fn insert_modes(&mut self, modes: &CensusModes) -> &mut Self {
-        self.insert_on(
-            "mode",
-            &match modes {
-                CensusModes::History(..) => Some(String::from("history")),
-                CensusModes::Current(current_modes) => Some(current_modes.iter().join("+")),
-            },
-        );
+        match modes {
+            CensusModes::History(..) => self.insert_str("mode", "history"),
+            // Without any modes, the API uses its default modes.
+            CensusModes::Current(current_modes) if current_modes.is_empty() => self,
+            CensusModes::Current(current_modes) => self.insert_str(
+                "mode",
+                current_modes.iter().map(<&'static str>::from).join("+"),
+            ),
+        };
         if let CensusModes::History(CensusHistoryParams { from, to }) = modes {
             self.insert_on("from", from).insert_on("to", to);
         }
@@ -100,27 +170,100 @@ impl<'a> Params<'a> {
         }
         self
     }
+
+    pub(crate) fn insert_raw(&mut self, raw: &'a RawShard<'_>) -> &mut Self {
+        for (k, v) in &raw.params {
+            let k = k.as_ref();
+            if RESERVED_PARAMS.contains(&k) {
+                self.conflict
+                    .get_or_insert(RequestBuildError::ReservedParam(k.to_string()));
+            } else {
+                self.insert_str(k, v.as_ref());
+            }
+        }
+        self
+    }
+
+    /// Fails if two shards gave different values for the same parameter.
+    pub(crate) fn check(&mut self) -> Result<&Self, RequestBuildError> {
+        match self.conflict.take() {
+            Some(e) => Err(e),
+            None => Ok(self),
+        }
+    }
 }
 
-impl<'a> Iterator for Params<'a> {
-    type Item = (&'a str, String);
+impl<'p, 'a> IntoIterator for &'p Params<'a> {
+    type Item = (&'p str, &'p str);
+    type IntoIter = ParamsIter<'p, 'a>;
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if !self.1.is_empty() {
-            Some(self.0.remove_entry(self.1.remove(0)).unwrap())
-        } else {
-            None
+    fn into_iter(self) -> Self::IntoIter {
+        ParamsIter(self.params.iter())
+    }
+}
+
+impl FromIterator<(String, String)> for Params<'static> {
+    fn from_iter<T: IntoIterator<Item = (String, String)>>(iter: T) -> Self {
+        Self {
+            params: iter
+                .into_iter()
+                .map(|(k, v)| (Cow::Owned(k), Cow::Owned(v)))
+                .collect(),
+            conflict: None,
         }
     }
 }
 
-/* // Error type for any issues with building a request.
+/// An iterator over the keys and values of [`Params`], created by [`Params::iter`].
+#[derive(Clone, Debug)]
+pub struct ParamsIter<'p, 'a>(std::slice::Iter<'p, (Cow<'a, str>, Cow<'a, str>)>);
+
+impl<'p, 'a> Iterator for ParamsIter<'p, 'a> {
+    type Item = (&'p str, &'p str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(k, v)| (k.as_ref(), v.as_ref()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl ExactSizeIterator for ParamsIter<'_, '_> {}
+
+/// Error type for any issues with building a request.
 #[derive(Debug, Error)]
 #[non_exhaustive]
 pub enum RequestBuildError {
     /// A required parameter was never provided, so the request could not be built.
     #[error("Builder does not have {0}")]
     MissingParam(&'static str),
+    /// Two shards in the same request need different values for the same parameter,
+    /// so they cannot be requested together.
+    #[error("shards conflict on parameter {key}: {first} vs. {second}")]
+    ConflictingShards {
+        /// The name of the parameter.
+        key: String,
+        /// The value given first.
+        first: String,
+        /// The value that conflicts with it.
+        second: String,
+    },
+    /// The request does not ask for anything, so the API would only respond with an error.
+    #[error("request has no shards")]
+    EmptyRequest,
+    /// An extra parameter has the same name as one of the [`RESERVED_PARAMS`].
+    #[error("{0} is reserved and cannot be set as an extra parameter")]
+    ReservedParam(String),
+    /// A World Census history window ends before it starts.
+    #[error("census history window ends at {before}, before it starts at {after}")]
+    InvalidTimeWindow {
+        /// The start of the window.
+        after: NonZeroU64,
+        /// The end of the window.
+        before: NonZeroU64,
+    },
     /// The URL parser [`Url::parse_with_params`] broke on a parameter.
     ///
     /// This error should never be expected!
@@ -131,16 +274,600 @@ pub enum RequestBuildError {
         ParseError,
     ),
 }
- */
+
+/// Joins names (like the shards in `q`), lowercasing them as they are written,
+/// so that the value is built in one buffer.
+pub(crate) fn join_lowercase<'s>(
+    names: impl IntoIterator<Item = &'s str>,
+    separator: char,
+) -> String {
+    let mut joined = String::new();
+    for (i, name) in names.into_iter().enumerate() {
+        if i > 0 {
+            joined.push(separator);
+        }
+        joined.extend(name.chars().map(|c| c.to_ascii_lowercase()));
+    }
+    joined
+}
+
+/// Writes text in lowercase without making a lowercase copy first.
+pub(crate) fn write_lowercase(f: &mut impl std::fmt::Write, text: &str) -> std::fmt::Result {
+    text.chars()
+        .try_for_each(|c| f.write_char(c.to_ascii_lowercase()))
+}
+
+/// A name is missing if it was never set, or if it was set to an empty string.
+pub(crate) fn require_name<'a>(
+    param: &'static str,
+    name: Option<&'a str>,
+) -> Result<&'a str, RequestBuildError> {
+    name.filter(|n| !n.is_empty())
+        .ok_or(RequestBuildError::MissingParam(param))
+}
+
+/// An existing API URL, split into the shards and parameters that requests are built from.
+///
+/// Requests borrow their names and scales,
+/// so the URL is parsed first and the request is then built from it,
+/// e.g. with [`PublicNationRequest::from_url`](nation::PublicNationRequest::from_url):
+/// ```rust
+/// # use crustacean_states::shards::{NSRequest, RequestUrl, nation::PublicNationRequest};
+/// let url = RequestUrl::parse(
+///     "https://www.nationstates.net/cgi-bin/api.cgi?nation=aramos&q=capital+animal",
+/// )?;
+/// let request = PublicNationRequest::from_url(&url)?;
+/// assert_eq!(
+///     request.as_url()?.as_str(),
+///     "https://www.nationstates.net/cgi-bin/api.cgi?nation=aramos&q=customcapital%2Banimal&v=12",
+/// );
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RequestUrl {
+    shards: Vec<String>,
+    params: HashMap<String, String>,
+    scales: Vec<u8>,
+}
+
+impl RequestUrl {
+    /// Parses an API URL.
+    pub fn parse(url: &str) -> Result<Self, FromUrlError> {
+        Self::try_from(&Url::parse(url)?)
+    }
+
+    /// The value of a parameter, as it was given.
+    pub(crate) fn param(&self, key: &str) -> Option<&str> {
+        self.params.get(key).map(String::as_str)
+    }
+
+    /// The value of a parameter, parsed into another type.
+    pub(crate) fn parse_param<T: FromStr>(&self, key: &str) -> Result<Option<T>, FromUrlError> {
+        self.param(key)
+            .map(|v| {
+                v.parse().map_err(|_| FromUrlError::BadParam {
+                    key: key.to_string(),
+                    value: v.to_string(),
+                })
+            })
+            .transpose()
+    }
+
+    /// The value of a parameter that the request cannot be built without.
+    pub(crate) fn require(&self, key: &'static str) -> Result<&str, FromUrlError> {
+        self.param(key).ok_or(FromUrlError::MissingParam(key))
+    }
+
+    /// Parses each shard in `q`, then fills in its parameters with `with_params`.
+    pub(crate) fn shards<T, F>(&self, with_params: F) -> Result<ShardSet<T>, FromUrlError>
+    where
+        T: FromStr + PartialEq,
+        F: Fn(T) -> Result<T, FromUrlError>,
+    {
+        self.shards
+            .iter()
+            .map(|name| {
+                T::from_str(name)
+                    .map_err(|_| FromUrlError::UnknownShard(name.clone()))
+                    .and_then(&with_params)
+            })
+            .collect()
+    }
+
+    pub(crate) fn census(&self) -> Result<CensusShard<'_>, FromUrlError> {
+        let scale = match self.param("scale") {
+            None => CensusScales::Today,
+            Some("all") => CensusScales::All,
+            Some(s) if s.contains(LIST_SEPARATORS) => CensusScales::from(self.scales.as_slice()),
+            Some(_) => CensusScales::One(self.parse_param("scale")?.unwrap_or_default()),
+        };
+        let modes = match self.param("mode") {
+            None => CensusModes::default(),
+            Some("history") => {
+                let mut window = CensusHistoryParams::default();
+                if let Some(after) = self.parse_param("from")? {
+                    window.after(after).map_err(|_| FromUrlError::BadParam {
+                        key: String::from("from"),
+                        value: after.to_string(),
+                    })?;
+                }
+                if let Some(before) = self.parse_param("to")? {
+                    window.before(before).map_err(|_| FromUrlError::BadParam {
+                        key: String::from("to"),
+                        value: before.to_string(),
+                    })?;
+                }
+                CensusModes::History(window)
+            }
+            Some(modes) => CensusModes::Current(
+                split_list(modes)
+                    .map(|m| {
+                        m.parse().map_err(|_| FromUrlError::BadParam {
+                            key: String::from("mode"),
+                            value: modes.to_string(),
+                        })
+                    })
+                    .collect::<Result<_, _>>()?,
+            ),
+        };
+        Ok(CensusShard { scale, modes })
+    }
+
+    pub(crate) fn census_ranks(&self) -> Result<CensusRanksShard, FromUrlError> {
+        Ok(CensusRanksShard {
+            scale: self
+                .parse_param::<u8>("scale")?
+                .and_then(|s| NonZeroU8::new(s.saturating_add(1))),
+            start: self.parse_param("start")?,
+        })
+    }
+}
+
+/// What lists of shards, scales and modes are separated by in a URL.
+///
+/// Requests join them with `+`, which is encoded as `%2B`,
+/// but a literal `+` in a hand-written URL is decoded into a space.
+const LIST_SEPARATORS: [char; 2] = ['+', ' '];
+
+/// Splits a list from a URL parameter into its items.
+pub(crate) fn split_list(value: &str) -> impl Iterator<Item = &str> {
+    value.split(LIST_SEPARATORS).filter(|item| !item.is_empty())
+}
+
+impl TryFrom<&Url> for RequestUrl {
+    type Error = FromUrlError;
+
+    fn try_from(url: &Url) -> Result<Self, Self::Error> {
+        if !url.path().ends_with("api.cgi") {
+            return Err(FromUrlError::NotAnApiUrl(url.to_string()));
+        }
+        let mut params = url
+            .query_pairs()
+            .map(|(k, v)| (k.into_owned(), v.into_owned()))
+            .collect::<HashMap<_, _>>();
+        let shards = params
+            .remove("q")
+            .map(|q| split_list(&q).map(String::from).collect())
+            .unwrap_or_default();
+        let scales = match params.get("scale") {
+            Some(s) if s.contains(LIST_SEPARATORS) => split_list(s)
+                .map(|x| {
+                    x.parse().map_err(|_| FromUrlError::BadParam {
+                        key: String::from("scale"),
+                        value: s.clone(),
+                    })
+                })
+                .collect::<Result<_, _>>()?,
+            _ => vec![],
+        };
+        Ok(Self {
+            shards,
+            params,
+            scales,
+        })
+    }
+}
+
+/// Error type for any issues with rebuilding a request from a URL.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum FromUrlError {
+    /// The URL could not be parsed.
+    #[error("could not parse URL")]
+    InvalidUrl(
+        /// The parent error.
+        #[from]
+        ParseError,
+    ),
+    /// The URL does not point to the API.
+    #[error("{0} is not an API URL")]
+    NotAnApiUrl(String),
+    /// A parameter that the request needs is not in the URL.
+    #[error("URL does not have {0}")]
+    MissingParam(&'static str),
+    /// A shard in the URL is not one that this kind of request knows about.
+    #[error("unknown shard {0}")]
+    UnknownShard(String),
+    /// A parameter in the URL has a value that could not be understood.
+    #[error("bad value for {key}: {value}")]
+    BadParam {
+        /// The name of the parameter.
+        key: String,
+        /// The value of the parameter.
+        value: String,
+    },
+}
 
 /// Request type.
+///
+/// This trait is the same whichever features are enabled.
+/// What a response is parsed into is given by [`NSRequestParse`].
 pub trait NSRequest {
     /// Converts internal information into a URL that can be requested.
-    fn as_url(&self) -> Url;
+    ///
+    /// Fails with [`RequestBuildError::ConflictingShards`]
+    /// if two shards need different values for the same parameter.
+    fn as_url(&self) -> Result<Url, RequestBuildError>;
+
+    /// Like [`NSRequest::as_url`], but with the shards and parameters sorted,
+    /// so that requests asking for the same information always have the same URL.
+    ///
+    /// This makes the URL suitable as a key for caching or deduplicating requests.
+    /// ```rust
+    /// # use crustacean_states::shards::{NSRequest, world::{WorldRequest, WorldShard}};
+    /// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+    /// let one = WorldRequest::new(&[WorldShard::NumNations, WorldShard::FeaturedRegion]);
+    /// let two = WorldRequest::new(&[WorldShard::FeaturedRegion, WorldShard::NumNations]);
+    /// assert_eq!(one.canonical_url()?, two.canonical_url()?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn canonical_url(&self) -> Result<Url, RequestBuildError> {
+        let mut url = self.as_url()?;
+        canonicalize(&mut url);
+        Ok(url)
+    }
+
+    /// The parameters that this request sends, in the order they appear in [`NSRequest::as_url`],
+    /// including the ones that say what is being requested.
+    /// ```rust
+    /// # use crustacean_states::shards::{NSRequest, nation::{PublicNationRequest, PublicNationShard}};
+    /// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+    /// let request = PublicNationRequest::new_with_shards("Aramos", vec![PublicNationShard::Animal]);
+    /// let params = request.params()?;
+    /// assert_eq!(params.get("nation"), Some("Aramos"));
+    /// assert_eq!(
+    ///     params.iter().collect::<Vec<_>>(),
+    ///     [("nation", "Aramos"), ("q", "animal"), ("v", "12")],
+    /// );
+    /// # Ok(())
+    /// # }
+    /// # test().unwrap();
+    /// ```
+    fn params(&self) -> Result<Params<'static>, RequestBuildError> {
+        Ok(self
+            .as_url()?
+            .query_pairs()
+            .map(|(k, v)| (k.into_owned(), v.into_owned()))
+            .collect())
+    }
+
+    /// Adds a parameter that `crustacean-states` does not support yet.
+    ///
+    /// Building the URL fails with [`RequestBuildError::ReservedParam`]
+    /// if the key is one of the [`RESERVED_PARAMS`],
+    /// and with [`RequestBuildError::ConflictingShards`]
+    /// if the request already sets the key to a different value.
+    /// ```rust
+    /// # use crustacean_states::shards::{NSRequest, world::{WorldRequest, WorldShard}};
+    /// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+    /// let request = WorldRequest::new(&[WorldShard::NumNations]).extra_param("foo", "bar");
+    /// assert_eq!(
+    ///     request.as_url()?.as_str(),
+    ///     "https://www.nationstates.net/cgi-bin/api.cgi?q=numnations&v=12&foo=bar",
+    /// );
+    /// # Ok(())
+    /// # }
+    /// # test().unwrap();
+    /// ```
+    fn extra_param<'p>(self, key: &'p str, value: impl ToString) -> WithExtraParams<'p, Self>
+    where
+        Self: Sized,
+    {
+        WithExtraParams {
+            request: self,
+            params: vec![],
+        }
+        .extra_param(key, value)
+    }
+
+    /// Sets the version of the API that the request asks for,
+    /// instead of [`API_VERSION`].
+    /// If the version is `None`, the request gets the latest version,
+    /// which may not be one that this crate can parse.
+    /// ```rust
+    /// # use crustacean_states::shards::{NSRequest, world::{WorldRequest, WorldShard}};
+    /// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+    /// let request = WorldRequest::new(&[WorldShard::NumNations]);
+    /// assert_eq!(request.params()?.get("v"), Some("12"));
+    /// let request = request.with_api_version(Some(11));
+    /// assert_eq!(request.params()?.get("v"), Some("11"));
+    /// # Ok(())
+    /// # }
+    /// # test().unwrap();
+    /// ```
+    fn with_api_version(self, version: Option<u8>) -> WithApiVersion<Self>
+    where
+        Self: Sized,
+    {
+        WithApiVersion {
+            request: self,
+            version,
+        }
+    }
+}
+
+/// A request whose response can be parsed, with the `parsers` feature.
+///
+/// Kept apart from [`NSRequest`] so that enabling `parsers` anywhere in a build
+/// does not change what implementing [`NSRequest`] requires.
+#[cfg(feature = "parsers")]
+pub trait NSRequestParse: NSRequest {
+    /// The type that the response to this request is parsed into.
+    ///
+    /// Requests whose responses cannot be parsed yet use [`String`],
+    /// which is the raw text of the response.
+    type Response: NSResponse;
+
+    /// Keeps the XML of the response next to the parsed response.
+    /// See [`WithXml`].
+    /// ```rust
+    /// # use crustacean_states::client::Client;
+    /// # use crustacean_states::shards::{NSRequestParse, world::{WorldRequest, WorldShard}};
+    /// # async fn test() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = Client::new("");
+    /// let request = WorldRequest::new(&[WorldShard::NumNations]).keep_xml();
+    /// let response = client.get_parsed(request).await?;
+    /// println!("{} nations, from {}", response.parsed.num_nations.unwrap(), response.xml);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn keep_xml(self) -> KeepXml<Self>
+    where
+        Self: Sized,
+    {
+        KeepXml(self)
+    }
+}
+
+/// A request whose response keeps its XML. See [`NSRequestParse::keep_xml`].
+#[cfg(feature = "parsers")]
+#[derive(Clone, Debug)]
+pub struct KeepXml<R>(R);
+
+#[cfg(feature = "parsers")]
+impl<R> KeepXml<R> {
+    /// The request that is sent.
+    pub fn into_inner(self) -> R {
+        self.0
+    }
+}
+
+#[cfg(feature = "parsers")]
+impl<R: NSRequest> NSRequest for KeepXml<R> {
+    fn as_url(&self) -> Result<Url, RequestBuildError> {
+        self.0.as_url()
+    }
+}
+
+#[cfg(feature = "parsers")]
+impl<R: NSRequestParse> NSRequestParse for KeepXml<R> {
+    type Response = WithXml<R::Response>;
+}
+
+impl<R: NSRequest + ?Sized> NSRequest for &R {
+    fn as_url(&self) -> Result<Url, RequestBuildError> {
+        (**self).as_url()
+    }
+}
+
+#[cfg(feature = "parsers")]
+impl<R: NSRequestParse + ?Sized> NSRequestParse for &R {
+    type Response = R::Response;
+}
+
+/// A request whose shards can be checked against the response they were sent for.
+///
+/// NationStates silently leaves out shards it does not know or no longer supports,
+/// so a response that parsed correctly might still lack some of the requested data.
+/// Use [`Client::get_verified`](crate::client::Client::get_verified)
+/// to turn this into an error.
+#[cfg(feature = "parsers")]
+pub trait VerifyShards: NSRequestParse {
+    /// The requested shards that have no data in the response.
+    ///
+    /// Shards without a field in the response (like raw shards) are never reported.
+    fn missing_shards(&self, response: &Self::Response) -> Vec<String>;
+
+    /// Fails with [`MissingShardData`] if any requested shard has no data in the response.
+    fn verify(&self, response: &Self::Response) -> Result<(), MissingShardData> {
+        let shards = self.missing_shards(response);
+        if shards.is_empty() {
+            Ok(())
+        } else {
+            Err(MissingShardData { shards })
+        }
+    }
+}
+
+/// Some of the requested shards had no data in the response.
+#[derive(Clone, Debug, Error, PartialEq)]
+#[error("response is missing data for shards: {}", shards.join(", "))]
+pub struct MissingShardData {
+    /// The names of the shards, as they were sent.
+    pub shards: Vec<String>,
+}
+
+/// A request with parameters that `crustacean-states` does not support yet.
+/// See [`NSRequest::extra_param`].
+#[derive(Clone, Debug)]
+pub struct WithExtraParams<'p, R> {
+    request: R,
+    params: Vec<(&'p str, String)>,
+}
+
+impl<'p, R> WithExtraParams<'p, R> {
+    /// Adds another parameter.
+    pub fn extra_param(mut self, key: &'p str, value: impl ToString) -> Self {
+        self.params.push((key, value.to_string()));
+        self
+    }
+}
+
+impl<'p, R: NSRequest> NSRequest for WithExtraParams<'p, R> {
+    fn as_url(&self) -> Result<Url, RequestBuildError> {
+        let mut url = self.request.as_url()?;
+        let mut params = url
+            .query_pairs()
+            .map(|(k, v)| (k.into_owned(), v.into_owned()))
+            .collect::<HashMap<_, _>>();
+        for (k, v) in &self.params {
+            if RESERVED_PARAMS.contains(k) {
+                return Err(RequestBuildError::ReservedParam(k.to_string()));
+            }
+            match params.get(*k) {
+                Some(old) if old == v => {}
+                Some(old) => {
+                    return Err(RequestBuildError::ConflictingShards {
+                        key: k.to_string(),
+                        first: old.clone(),
+                        second: v.clone(),
+                    })
+                }
+                None => {
+                    url.query_pairs_mut().append_pair(k, v);
+                    params.insert(k.to_string(), v.clone());
+                }
+            }
+        }
+        Ok(url)
+    }
+}
+
+#[cfg(feature = "parsers")]
+impl<'p, R: NSRequestParse> NSRequestParse for WithExtraParams<'p, R> {
+    type Response = R::Response;
+}
+
+/// A request that asks for a chosen version of the API.
+/// See [`NSRequest::with_api_version`].
+#[derive(Clone, Debug)]
+pub struct WithApiVersion<R> {
+    request: R,
+    version: Option<u8>,
+}
+
+impl<R: NSRequest> NSRequest for WithApiVersion<R> {
+    fn as_url(&self) -> Result<Url, RequestBuildError> {
+        let mut url = self.request.as_url()?;
+        let pairs = url
+            .query_pairs()
+            .filter(|(k, _)| k != "v")
+            .map(|(k, v)| (k.into_owned(), v.into_owned()))
+            .collect::<Vec<_>>();
+        let version = self.version.map(|v| v.to_string());
+        url.query_pairs_mut()
+            .clear()
+            .extend_pairs(pairs)
+            .extend_pairs(version.iter().map(|v| ("v", v)));
+        Ok(url)
+    }
+}
+
+#[cfg(feature = "parsers")]
+impl<R: NSRequestParse> NSRequestParse for WithApiVersion<R> {
+    type Response = R::Response;
+}
+
+/// The URL of a request, built the first time it is needed.
+///
+/// Builders must [clear](CachedUrl::clear) it whenever they change the request.
+/// It is ignored when comparing requests.
+#[derive(Clone, Default)]
+pub(crate) struct CachedUrl(OnceLock<Url>);
+
+impl CachedUrl {
+    /// Returns the cached URL, or builds and caches it. Errors are not cached.
+    pub(crate) fn get_or_build<F>(&self, build: F) -> Result<Url, RequestBuildError>
+    where
+        F: FnOnce() -> Result<Url, RequestBuildError>,
+    {
+        if let Some(url) = self.0.get() {
+            return Ok(url.clone());
+        }
+        let url = build()?;
+        Ok(self.0.get_or_init(|| url).clone())
+    }
+
+    /// Forgets the cached URL.
+    pub(crate) fn clear(&mut self) {
+        self.0.take();
+    }
+}
+
+impl PartialEq for CachedUrl {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl Debug for CachedUrl {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("CachedUrl")
+            .field(&self.0.get().map(Url::as_str))
+            .finish()
+    }
+}
+
+/// Builds an API URL from its parameters, asking for version [`API_VERSION`] of the API.
+pub(crate) fn api_url<I, K, V>(params: I) -> Result<Url, ParseError>
+where
+    I: IntoIterator,
+    I::Item: Borrow<(K, V)>,
+    K: AsRef<str>,
+    V: AsRef<str>,
+{
+    let mut url = Url::parse_with_params(BASE_URL, params)?;
+    url.query_pairs_mut()
+        .append_pair("v", &API_VERSION.to_string());
+    Ok(url)
+}
+
+/// Sorts the query parameters of a URL by key, and the shards in `q` by name.
+pub(crate) fn canonicalize(url: &mut Url) {
+    let pairs = url
+        .query_pairs()
+        .map(|(k, v)| {
+            let v = if k == "q" {
+                v.split('+').sorted().dedup().join("+")
+            } else {
+                v.into_owned()
+            };
+            (k.into_owned(), v)
+        })
+        .sorted()
+        .collect::<Vec<_>>();
+    url.query_pairs_mut().clear().extend_pairs(pairs);
 }
 
 /// Shard for information from the World Census.
 /// A combination of two subunits: [`CensusScales`] and [`CensusModes`].
+///
+/// A shard asks for either current or historical data, never both.
+/// Since every census shard in a request shares the same parameters,
+/// a request with census shards that disagree
+/// (e.g. one for history and one for current ranks)
+/// fails to build with [`RequestBuildError::ConflictingShards`].
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct CensusShard<'a> {
     scale: CensusScales<'a>,
@@ -153,6 +880,19 @@ impl<'a> CensusShard<'a> {
         CensusShard { scale, modes }
     }
 
+    /// Create a new shard for current data.
+    pub fn current(
+        scale: CensusScales<'a>,
+        modes: impl IntoIterator<Item = CensusCurrentMode>,
+    ) -> CensusShard<'a> {
+        CensusShard::new(scale, CensusModes::Current(modes.into_iter().collect()))
+    }
+
+    /// Create a new shard for historical data.
+    pub fn history(scale: CensusScales<'a>, window: CensusHistoryParams) -> CensusShard<'a> {
+        CensusShard::new(scale, CensusModes::History(window))
+    }
+
     /// Specify the World Census scale(s) to list, using numerical IDs.
     /// For all scales, use [`CensusScales::All`].
     /// For today's World Census Report, use [`CensusScales::Today`].
@@ -177,6 +917,219 @@ impl<'a> CensusShard<'a> {
     }
 }
 
+/// A shard that `crustacean-states` does not support yet.
+///
+/// NationStates adds shards faster than this crate can keep up with,
+/// so every kind of request accepts a raw shard,
+/// which is sent with its name and parameters exactly as they are given.
+/// ```rust
+/// # use crustacean_states::shards::{NSRequest, RawShard, nation::{PublicNationRequest, PublicNationShard}};
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// let request = PublicNationRequest::new_with_shards(
+///     "Aramos",
+///     [PublicNationShard::Raw(RawShard::new("newshard").param("option", "1").to_owned())],
+/// );
+/// assert_eq!(
+///     request.as_url()?.as_str(),
+///     "https://www.nationstates.net/cgi-bin/api.cgi?nation=Aramos&q=newshard&option=1&v=12",
+/// );
+/// # Ok(())
+/// # }
+/// # test().unwrap();
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RawShard<'a> {
+    pub(crate) name: Cow<'a, str>,
+    params: Vec<(Cow<'a, str>, Cow<'a, str>)>,
+}
+
+impl<'a> RawShard<'a> {
+    /// Create a new shard with the name that goes in the query.
+    pub fn new(name: impl Into<Cow<'a, str>>) -> Self {
+        Self {
+            name: name.into(),
+            params: vec![],
+        }
+    }
+
+    /// Add an extra parameter that the shard needs.
+    pub fn param(
+        &mut self,
+        key: impl Into<Cow<'a, str>>,
+        value: impl Into<Cow<'a, str>>,
+    ) -> &mut Self {
+        self.params.push((key.into(), value.into()));
+        self
+    }
+}
+
+/// An ordered set of shards.
+///
+/// Shards keep the order they were first inserted in,
+/// and inserting a shard that is already in the set does nothing,
+/// so shard lists from different places can be combined without sending a shard twice.
+/// ```rust
+/// # use crustacean_states::shards::{ShardSet, nation::PublicNationShard};
+/// let mut ids = ShardSet::from([PublicNationShard::Name, PublicNationShard::DbId]);
+/// let looks = ShardSet::from([PublicNationShard::Name, PublicNationShard::Flag]);
+/// assert!(!ids.insert(PublicNationShard::DbId));
+/// assert_eq!(
+///     ids.union(&looks),
+///     ShardSet::from([
+///         PublicNationShard::Name,
+///         PublicNationShard::DbId,
+///         PublicNationShard::Flag,
+///     ]),
+/// );
+/// assert_eq!(ids.intersection(&looks), ShardSet::from([PublicNationShard::Name]));
+/// assert_eq!(ids.difference(&looks), ShardSet::from([PublicNationShard::DbId]));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ShardSet<T>(Vec<T>);
+
+impl<T> ShardSet<T> {
+    /// Creates an empty set.
+    pub fn new() -> Self {
+        Self(vec![])
+    }
+
+    /// The number of shards in the set.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether the set has no shards.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Iterates over the shards in the order they were inserted.
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.0.iter()
+    }
+
+    /// The shards in the order they were inserted.
+    pub fn as_slice(&self) -> &[T] {
+        &self.0
+    }
+}
+
+impl<T: PartialEq> ShardSet<T> {
+    /// Whether the set contains the shard.
+    pub fn contains(&self, shard: &T) -> bool {
+        self.0.contains(shard)
+    }
+
+    /// Adds a shard to the end of the set.
+    /// Returns whether the shard was new.
+    pub fn insert(&mut self, shard: T) -> bool {
+        let new = !self.contains(&shard);
+        if new {
+            self.0.push(shard);
+        }
+        new
+    }
+
+    /// Removes a shard from the set, keeping the order of the others.
+    /// Returns whether the shard was in the set.
+    pub fn remove(&mut self, shard: &T) -> bool {
+        let len = self.0.len();
+        self.0.retain(|s| s != shard);
+        self.0.len() != len
+    }
+}
+
+impl<T: Clone + PartialEq> ShardSet<T> {
+    /// The shards in either set:
+    /// those in `self`, then those only in `other`.
+    pub fn union(&self, other: &Self) -> Self {
+        self.iter().chain(other.iter()).cloned().collect()
+    }
+
+    /// The shards in both sets, in the order of `self`.
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.iter().filter(|s| other.contains(s)).cloned().collect()
+    }
+
+    /// The shards in `self` but not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        self.iter()
+            .filter(|s| !other.contains(s))
+            .cloned()
+            .collect()
+    }
+}
+
+impl<T> Default for ShardSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> AsRef<[T]> for ShardSet<T> {
+    fn as_ref(&self) -> &[T] {
+        &self.0
+    }
+}
+
+impl<T: PartialEq> Extend<T> for ShardSet<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|shard| {
+            self.insert(shard);
+        });
+    }
+}
+
+impl<T: PartialEq> FromIterator<T> for ShardSet<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T> IntoIterator for ShardSet<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'s, T> IntoIterator for &'s ShardSet<T> {
+    type Item = &'s T;
+    type IntoIter = std::slice::Iter<'s, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl<T: PartialEq> From<Vec<T>> for ShardSet<T> {
+    fn from(value: Vec<T>) -> Self {
+        value.into_iter().collect()
+    }
+}
+
+impl<T: PartialEq, const N: usize> From<[T; N]> for ShardSet<T> {
+    fn from(value: [T; N]) -> Self {
+        value.into_iter().collect()
+    }
+}
+
+impl<T: Clone + PartialEq> From<&[T]> for ShardSet<T> {
+    fn from(value: &[T]) -> Self {
+        value.iter().cloned().collect()
+    }
+}
+
+impl<'a, T: Clone> From<ShardSet<T>> for Cow<'a, [T]> {
+    fn from(value: ShardSet<T>) -> Self {
+        Cow::Owned(value.0)
+    }
+}
+
 /// World census scales as numerical IDs.
 /// The IDs can be found [here](https://forum.nationstates.net/viewtopic.php?f=15&t=159491)
 /// or in the URL of [World Census](https://www.nationstates.net/page=list_nations?censusid=0)
@@ -189,11 +1142,31 @@ pub enum CensusScales<'a> {
     /// Only one scale.
     One(u8),
     /// Multiple scales.
-    Many(&'a [u8]),
+    ///
+    /// The scales can be borrowed, or owned if they are only known at runtime:
+    /// ```rust
+    /// # use crustacean_states::shards::CensusScales;
+    /// let scales: Vec<u8> = (0..5).map(|x| x * 2).collect();
+    /// let scales = CensusScales::from(scales);
+    /// assert_eq!(scales, CensusScales::from([0, 2, 4, 6, 8].as_slice()));
+    /// ```
+    Many(Cow<'a, [u8]>),
     /// All scales.
     All,
 }
 
+impl<'a> From<&'a [u8]> for CensusScales<'a> {
+    fn from(value: &'a [u8]) -> Self {
+        Self::Many(Cow::Borrowed(value))
+    }
+}
+
+impl From<Vec<u8>> for CensusScales<'_> {
+    fn from(value: Vec<u8>) -> Self {
+        Self::Many(Cow::Owned(value))
+    }
+}
+
 /// Either describes current or historical data.
 #[derive(Clone, Debug, PartialEq)]
 pub enum CensusModes {
@@ -202,12 +1175,14 @@ pub enum CensusModes {
     /// When requesting history, you can optionally specify a time window, using Unix epoch times.
     History(CensusHistoryParams),
     /// Represents current data.
-    Current(Vec<CensusCurrentMode>),
+    /// Each mode is only requested once.
+    /// If there are no modes, the API's default modes are used.
+    Current(ShardSet<CensusCurrentMode>),
 }
 
 impl Default for CensusModes {
     fn default() -> Self {
-        Self::Current(vec![
+        Self::from([
             CensusCurrentMode::Score,
             CensusCurrentMode::Rank,
             CensusCurrentMode::RegionRank,
@@ -215,12 +1190,12 @@ impl Default for CensusModes {
     }
 }
 
-impl<'a, T> From<T> for CensusModes
+impl<T> From<T> for CensusModes
 where
-    T: 'a + AsRef<[CensusCurrentMode]>,
+    T: AsRef<[CensusCurrentMode]>,
 {
     fn from(value: T) -> Self {
-        Self::Current(value.as_ref().to_vec())
+        Self::Current(ShardSet::from(value.as_ref()))
     }
 }
 
@@ -240,26 +1215,48 @@ impl CensusHistoryParams {
     /// corresponds with `to`.
     /// This terminology was changed because both `from` and `to` are very ambiguous, and `from`
     /// should be reserved for converting from other types into this one.
-    pub fn new(after: NonZeroU64, before: NonZeroU64) -> Self {
-        Self::default().before(before).after(after).to_owned()
+    ///
+    /// Fails with [`RequestBuildError::InvalidTimeWindow`] if `before` is earlier than `after`.
+    pub fn new(after: NonZeroU64, before: NonZeroU64) -> Result<Self, RequestBuildError> {
+        Ok(Self::default().before(before)?.after(after)?.to_owned())
     }
 
     /// Restricts the data to be after/from a certain timestamp.
-    pub fn after(&mut self, timestamp: NonZeroU64) -> &mut Self {
+    ///
+    /// Fails with [`RequestBuildError::InvalidTimeWindow`]
+    /// if the data is already restricted to be before an earlier timestamp.
+    pub fn after(&mut self, timestamp: NonZeroU64) -> Result<&mut Self, RequestBuildError> {
+        Self::check(Some(timestamp), self.to)?;
         self.from = Some(timestamp);
-        self
+        Ok(self)
     }
 
     /// Restricts the data to be before/until a certain timestamp.
-    pub fn before(&mut self, timestamp: NonZeroU64) -> &mut Self {
+    ///
+    /// Fails with [`RequestBuildError::InvalidTimeWindow`]
+    /// if the data is already restricted to be after a later timestamp.
+    pub fn before(&mut self, timestamp: NonZeroU64) -> Result<&mut Self, RequestBuildError> {
+        Self::check(self.from, Some(timestamp))?;
         self.to = Some(timestamp);
-        self
+        Ok(self)
+    }
+
+    fn check(from: Option<NonZeroU64>, to: Option<NonZeroU64>) -> Result<(), RequestBuildError> {
+        match (from, to) {
+            (Some(after), Some(before)) if after > before => {
+                Err(RequestBuildError::InvalidTimeWindow { after, before })
+            }
+            _ => Ok(()),
+        }
     }
 }
 
 //noinspection SpellCheckingInspection
 /// Describes data that can currently be found on the World Census.
-#[derive(Clone, Debug, Display, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[derive(
+    Clone, Debug, Display, EnumString, IntoStaticStr, Ord, PartialOrd, Eq, PartialEq, Hash,
+)]
+#[strum(ascii_case_insensitive)]
 pub enum CensusCurrentMode {
     /// Raw value.
     Score,
@@ -286,11 +1283,11 @@ pub struct CensusRanksShard {
 impl CensusRanksShard {
     /// Create a new shard.
     /// - `scale`:
-    /// The World Census statistic to use.
-    /// (If you want the World Census daily scale,
-    /// start with [`CensusRanksShard::default`] and use [`CensusRanksShard::daily_scale`].)
+    ///   The World Census statistic to use.
+    ///   (If you want the World Census daily scale,
+    ///   start with [`CensusRanksShard::default`] and use [`CensusRanksShard::daily_scale`].)
     /// - `start`: The ranking to start with
-    /// (e.g. `5` would indicate starting at the fifth nation).
+    ///   (e.g. `5` would indicate starting at the fifth nation).
     pub fn new(scale: u8, start: NonZeroU32) -> Self {
         Self::default().scale(scale).start(start).to_owned()
     }
@@ -317,21 +1314,23 @@ impl CensusRanksShard {
 #[cfg(test)]
 mod tests {
     use crate::shards::{
-        CensusCurrentMode, CensusHistoryParams, CensusModes, CensusScales, Params,
+        canonicalize, CensusCurrentMode, CensusHistoryParams, CensusModes, CensusScales, Params,
+        RequestBuildError, ShardSet,
     };
     use std::num::{NonZeroU64, NonZeroU8};
+    use url::Url;
 
     // test Params
     #[test]
     fn new_params() {
-        assert!(Params::default().0.is_empty());
+        assert!(Params::default().into_iter().next().is_none());
     }
 
     #[test]
     fn insert_param() {
         assert_eq!(
-            Params::default().insert("this", "that").0.get("this"),
-            Some(&String::from("that"))
+            Params::default().insert("this", "that").get("this"),
+            Some("that")
         );
     }
 
@@ -340,9 +1339,8 @@ mod tests {
         assert_eq!(
             Params::default()
                 .insert_scale(&CensusScales::One(3))
-                .0
                 .get("scale"),
-            Some(&3.to_string())
+            Some("3")
         );
     }
 
@@ -350,10 +1348,9 @@ mod tests {
     fn insert_many_scales() {
         assert_eq!(
             Params::default()
-                .insert_scale(&CensusScales::Many(&[3, 4, 5]))
-                .0
+                .insert_scale(&CensusScales::from(vec![3, 4, 5]))
                 .get("scale"),
-            Some(&String::from("3+4+5"))
+            Some("3+4+5")
         );
     }
 
@@ -362,9 +1359,8 @@ mod tests {
         assert_eq!(
             Params::default()
                 .insert_scale(&CensusScales::All)
-                .0
                 .get("scale"),
-            Some(&String::from("all"))
+            Some("all")
         );
     }
 
@@ -373,7 +1369,6 @@ mod tests {
         assert_eq!(
             Params::default()
                 .insert_scale(&CensusScales::Today)
-                .0
                 .get("scale"),
             None
         );
@@ -385,40 +1380,78 @@ mod tests {
         assert_eq!(
             Params::default()
                 .insert_rank_scale(&Some(NonZeroU8::new(10).unwrap()))
-                .0
                 .get("scale"),
-            Some(&9.to_string())
+            Some("9")
         );
     }
 
     #[test]
     fn insert_mode_history_from_and_to() {
         let mut params = Params::default();
-        params.insert_modes(&CensusModes::History(CensusHistoryParams::new(
-            NonZeroU64::new(6900).unwrap(),
-            NonZeroU64::new(42000).unwrap(),
-        )));
-        assert_eq!(params.0.get("mode"), Some(&String::from("history")));
-        assert_eq!(params.0.get("from"), Some(&6900.to_string()));
-        assert_eq!(params.0.get("to"), Some(&42000.to_string()));
+        params.insert_modes(&CensusModes::History(
+            CensusHistoryParams::new(
+                NonZeroU64::new(6900).unwrap(),
+                NonZeroU64::new(42000).unwrap(),
+            )
+            .unwrap(),
+        ));
+        assert_eq!(params.get("mode"), Some("history"));
+        assert_eq!(params.get("from"), Some("6900"));
+        assert_eq!(params.get("to"), Some("42000"));
     }
 
     #[test]
     fn insert_mode_current_one() {
         assert_eq!(
             Params::default()
-                .insert_modes(&CensusModes::Current(vec![CensusCurrentMode::PercentRank]))
-                .0
+                .insert_modes(&CensusModes::from([CensusCurrentMode::PercentRank]))
+                .get("mode"),
+            Some("prank")
+        );
+    }
+
+    #[test]
+    fn insert_mode_current_repeated() {
+        assert_eq!(
+            Params::default()
+                .insert_modes(&CensusModes::from([
+                    CensusCurrentMode::Score,
+                    CensusCurrentMode::Rank,
+                    CensusCurrentMode::Score,
+                ]))
+                .get("mode"),
+            Some("Score+Rank")
+        );
+        assert_eq!(
+            Params::default()
+                .insert_modes(&CensusModes::Current(ShardSet::new()))
                 .get("mode"),
-            Some(&String::from("prank"))
+            None
         );
     }
 
+    #[test]
+    fn history_window() {
+        let early = NonZeroU64::new(6900).unwrap();
+        let late = NonZeroU64::new(42000).unwrap();
+        assert!(matches!(
+            CensusHistoryParams::new(late, early),
+            Err(RequestBuildError::InvalidTimeWindow { after, before })
+                if after == late && before == early
+        ));
+        assert!(CensusHistoryParams::default()
+            .after(late)
+            .unwrap()
+            .before(early)
+            .is_err());
+        assert!(CensusHistoryParams::new(early, early).is_ok());
+    }
+
     #[test]
     fn param_iter_easy() {
         assert_eq!(
-            Params::default().insert("this", "that").next(),
-            Some(("this", String::from("that")))
+            Params::default().insert("this", "that").into_iter().next(),
+            Some(("this", "that"))
         );
     }
 
@@ -429,9 +1462,293 @@ mod tests {
             .insert("this", "that")
             .insert("thing1", "thing2")
             .insert("wow", "yikes");
-        assert_eq!(params.next(), Some(("this", String::from("that"))));
-        assert_eq!(params.next(), Some(("thing1", String::from("thing2"))));
-        assert_eq!(params.next(), Some(("wow", String::from("yikes"))));
-        assert_eq!(params.next(), None);
+        let mut iter = params.iter();
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.next(), Some(("this", "that")));
+        assert_eq!(iter.next(), Some(("thing1", "thing2")));
+        assert_eq!(iter.next(), Some(("wow", "yikes")));
+        assert_eq!(iter.next(), None);
+        // Iterating does not use up the parameters.
+        assert_eq!(params.into_iter().count(), 3);
+        assert_eq!(params.len(), 3);
+    }
+
+    #[test]
+    fn param_same_value_twice() {
+        let mut params = Params::default();
+        params.insert("scale", 3).insert("scale", 3);
+        assert!(params.check().is_ok());
+        assert_eq!(params.iter().collect::<Vec<_>>(), [("scale", "3")]);
+    }
+
+    #[test]
+    fn param_conflict() {
+        let mut params = Params::default();
+        params
+            .insert_scale(&CensusScales::One(3))
+            .insert_scale(&CensusScales::All);
+        assert!(matches!(
+            params.check(),
+            Err(RequestBuildError::ConflictingShards { key, first, second })
+                if key == "scale" && first == "3" && second == "all"
+        ));
+    }
+
+    #[test]
+    fn lowercase_names() {
+        use crate::shards::{
+            wa::{ResolutionRequest, ResolutionShard, WACouncil, WARequest},
+            world::{HappeningsFilterType, HappeningsShardBuilder, WorldRequest},
+            NSRequest,
+        };
+
+        let request = WARequest::AtVoteResolution(ResolutionRequest::new(
+            WACouncil::SecurityCouncil,
+            vec![ResolutionShard::Voters, ResolutionShard::DelLog],
+        ));
+        assert_eq!(
+            request.as_url().unwrap().query(),
+            Some("wa=2&q=resolution%2Bvoters%2Bdellog&v=12")
+        );
+
+        let shards = [HappeningsShardBuilder::new()
+            .view_nations(&["Aramos", "Testlandia"])
+            .add_filter(HappeningsFilterType::Endo)
+            .build()];
+        assert_eq!(
+            WorldRequest::new(&shards).as_url().unwrap().query(),
+            Some("q=happenings&view=nation.aramos%2Ctestlandia&filter=endo&v=12")
+        );
+    }
+
+    #[test]
+    fn canonical_order() {
+        let mut url = Url::parse_with_params(
+            super::BASE_URL,
+            [
+                ("region", "anteria"),
+                ("q", "nations+census+nations"),
+                ("mode", "score"),
+                ("scale", "3"),
+            ],
+        )
+        .unwrap();
+        canonicalize(&mut url);
+        assert_eq!(
+            url.query(),
+            Some("mode=score&q=census%2Bnations&region=anteria&scale=3")
+        );
+    }
+
+    #[test]
+    fn world_from_url_round_trip() {
+        use crate::shards::world::{
+            HappeningsFilterType, HappeningsViewType, WorldRequest, WorldShard,
+        };
+        use crate::shards::{NSRequest, RequestUrl};
+
+        let shards = [
+            WorldShard::Happenings {
+                view: Some(HappeningsViewType::Region(vec![String::from("anteria")])),
+                filter: Some(vec![
+                    HappeningsFilterType::Founding,
+                    HappeningsFilterType::Cte,
+                ]),
+                limit: Some(20),
+                since_id: None,
+                before_id: None,
+                since_time: None,
+                before_time: None,
+            },
+            WorldShard::Poll(123),
+        ];
+        let request = WorldRequest::new(&shards);
+        let url = RequestUrl::try_from(&request.as_url().unwrap()).unwrap();
+        assert_eq!(WorldRequest::from_url(&url).unwrap(), request);
+    }
+
+    #[test]
+    fn request_url_errors() {
+        use crate::shards::{nation::PublicNationRequest, FromUrlError, RequestUrl};
+
+        assert!(matches!(
+            RequestUrl::parse("https://www.nationstates.net/nation=aramos"),
+            Err(FromUrlError::NotAnApiUrl(_))
+        ));
+        let url =
+            RequestUrl::parse("https://www.nationstates.net/cgi-bin/api.cgi?q=animal").unwrap();
+        assert!(matches!(
+            PublicNationRequest::from_url(&url),
+            Err(FromUrlError::MissingParam("nation"))
+        ));
+        let url = RequestUrl::parse(
+            "https://www.nationstates.net/cgi-bin/api.cgi?nation=aramos&q=notashard",
+        )
+        .unwrap();
+        assert!(matches!(
+            PublicNationRequest::from_url(&url),
+            Err(FromUrlError::UnknownShard(s)) if s == "notashard"
+        ));
+    }
+
+    #[test]
+    fn request_url_literal_plus() {
+        use crate::shards::{
+            nation::{PublicNationRequest, PublicNationShard},
+            CensusModes, CensusScales, CensusShard, RequestUrl,
+        };
+
+        let expected = PublicNationRequest::new_with_shards(
+            "aramos",
+            [
+                PublicNationShard::Capital,
+                PublicNationShard::Animal,
+                PublicNationShard::Census(CensusShard::new(
+                    CensusScales::from([1, 2].as_slice()),
+                    CensusModes::default(),
+                )),
+            ],
+        );
+        for url in [
+            "https://www.nationstates.net/cgi-bin/api.cgi?nation=aramos&q=capital+animal+census&scale=1+2",
+            "https://www.nationstates.net/cgi-bin/api.cgi?nation=aramos&q=customcapital%2Banimal%2Bcensus&scale=1%2B2",
+        ] {
+            let url = RequestUrl::parse(url).unwrap();
+            assert_eq!(PublicNationRequest::from_url(&url).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn request_without_parsers() {
+        use crate::shards::{NSRequest, RequestBuildError, BASE_URL};
+        use url::Url;
+
+        // Implementing `NSRequest` must not depend on which features are enabled.
+        struct Version;
+        impl NSRequest for Version {
+            fn as_url(&self) -> Result<Url, RequestBuildError> {
+                Ok(Url::parse_with_params(BASE_URL, [("a", "version")])?)
+            }
+        }
+
+        assert_eq!(
+            Version.params().unwrap().iter().collect::<Vec<_>>(),
+            [("a", "version")]
+        );
+    }
+
+    #[test]
+    fn api_version() {
+        use crate::shards::{
+            nation::{PublicNationRequest, PublicNationShard},
+            NSRequest, API_VERSION,
+        };
+
+        let request = PublicNationRequest::new_with_shards("aramos", [PublicNationShard::Animal]);
+        assert_eq!(API_VERSION, 12);
+        assert_eq!(
+            request.canonical_url().unwrap().query(),
+            Some("nation=aramos&q=animal&v=12")
+        );
+        assert_eq!(request.params().unwrap().get("v"), Some("12"));
+
+        let request = request.with_api_version(Some(11));
+        assert_eq!(
+            request.canonical_url().unwrap().query(),
+            Some("nation=aramos&q=animal&v=11")
+        );
+        let request = request.with_api_version(None);
+        assert_eq!(request.params().unwrap().get("v"), None);
+    }
+
+    #[test]
+    fn extra_params() {
+        use crate::shards::{nation::PublicNationRequest, NSRequest, RawShard, RequestBuildError};
+
+        let request = PublicNationRequest::new("aramos").extra_param("nation", "anteria");
+        assert!(matches!(
+            request.as_url(),
+            Err(RequestBuildError::ReservedParam(k)) if k == "nation"
+        ));
+        let request = PublicNationRequest::new_with_shards(
+            "aramos",
+            [crate::shards::nation::PublicNationShard::Raw(
+                RawShard::new("thing").param("from", "a").to_owned(),
+            )],
+        )
+        .extra_param("from", "b");
+        assert!(matches!(
+            request.as_url(),
+            Err(RequestBuildError::ConflictingShards { key, .. }) if key == "from"
+        ));
+        let request = PublicNationRequest::new_with_shards(
+            "aramos",
+            [crate::shards::nation::PublicNationShard::Raw(
+                RawShard::new("thing").param("q", "a").to_owned(),
+            )],
+        );
+        assert!(matches!(
+            request.as_url(),
+            Err(RequestBuildError::ReservedParam(k)) if k == "q"
+        ));
+    }
+
+    #[test]
+    fn empty_requests() {
+        use crate::shards::{
+            wa::{GlobalRequest, WARequest},
+            world::WorldRequest,
+            NSRequest, RequestBuildError,
+        };
+
+        assert!(matches!(
+            WorldRequest::new_empty().as_url(),
+            Err(RequestBuildError::EmptyRequest)
+        ));
+        assert!(matches!(
+            WARequest::Global(GlobalRequest::new(&[])).as_url(),
+            Err(RequestBuildError::EmptyRequest)
+        ));
+    }
+
+    #[test]
+    fn missing_region() {
+        use crate::shards::{
+            region::{RegionRequest, RegionShard, StandardRegionRequest},
+            NSRequest, RequestBuildError,
+        };
+
+        let mut request = RegionRequest::with_shards([RegionShard::NumNations]);
+        assert!(matches!(
+            request.as_url(),
+            Err(RequestBuildError::MissingParam("region"))
+        ));
+        request.region("Anteria");
+        assert!(request.as_url().is_ok());
+        assert!(matches!(
+            StandardRegionRequest::new("").as_url(),
+            Err(RequestBuildError::MissingParam("region"))
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "parsers")]
+    fn keep_xml() {
+        use crate::parsers::{region::Region, NSResponse, WithXml};
+        use crate::shards::{
+            region::{RegionRequest, RegionShard},
+            NSRequest, NSRequestParse,
+        };
+
+        let request = RegionRequest::new_with_shards("Anteria", [RegionShard::NumNations]);
+        assert_eq!(
+            request.as_url().unwrap(),
+            request.clone().keep_xml().as_url().unwrap()
+        );
+
+        let xml = r#"<REGION id="anteria"><NUMNATIONS>7</NUMNATIONS></REGION>"#;
+        let response = WithXml::<Region>::from_xml(xml).unwrap();
+        assert_eq!(response.xml, xml);
+        assert_eq!(response.parsed.num_nations, Some(7));
     }
 }
@@ -1,8 +1,16 @@
 //! For public nation shard requests.
 
-use crate::shards::{CensusShard, NSRequest, Params, BASE_URL};
-use itertools::Itertools;
-use strum::AsRefStr;
+use crate::shards::{
+    api_url, join_lowercase, require_name, CachedUrl, CensusShard, FromUrlError, NSRequest, Params,
+    RawShard, RequestBuildError, RequestUrl, ShardSet,
+};
+#[cfg(feature = "parsers")]
+use crate::{
+    parsers::nation::{Nation, StandardNation},
+    shards::{NSRequestParse, VerifyShards},
+};
+use std::borrow::Cow;
+use strum::{AsRefStr, EnumString, IntoStaticStr, VariantNames};
 use url::Url;
 
 /// A nation request available to anyone (no login required).
@@ -12,7 +20,8 @@ use url::Url;
 /// [`Nation`](crate::parsers::nation::Nation).
 /// Enum variant docs include the struct field associated with it.
 //noinspection SpellCheckingInspection
-#[derive(AsRefStr, Clone, Debug, PartialEq)]
+#[derive(AsRefStr, Clone, Debug, EnumString, IntoStaticStr, PartialEq, VariantNames)]
+#[strum(ascii_case_insensitive)]
 pub enum PublicNationShard<'a> {
     /// A randomly selected compliment for the nation.
     ///
@@ -39,7 +48,7 @@ pub enum PublicNationShard<'a> {
     /// appended at the end if one has not been chosen yet.
     ///
     // /// See also: [`PublicNationShard::CustomCapital`]
-    #[strum(serialize = "customcapital")]
+    #[strum(to_string = "customcapital", serialize = "capital")]
     Capital,
     /// One of the 27 national classifications that the game assigns based on personal,
     /// economic, and political freedom.
@@ -182,7 +191,7 @@ pub enum PublicNationShard<'a> {
     /// if one has not been chosen yet.
     ///
     // /// See also: [`PublicNationShard::CustomLeader`]
-    #[strum(serialize = "customleader")]
+    #[strum(to_string = "customleader", serialize = "leader")]
     Leader,
     /// The list of law descriptions in the nation found on its nation page.
     Legislation,
@@ -223,7 +232,7 @@ pub enum PublicNationShard<'a> {
     /// or "a major religion" if one has not been chosen yet.
     ///
     // /// See also: [`PublicNationShard::CustomReligion`]
-    #[strum(serialize = "customreligion")]
+    #[strum(to_string = "customreligion", serialize = "religion")]
     Religion,
     /// The average income of the richest 10% in the nation.
     Richest,
@@ -248,13 +257,13 @@ pub enum PublicNationShard<'a> {
     TGCanRecruit {
         /// Whether the nation will deny a recruitment telegram from this region in particular
         /// due to having received one too recently.
-        from: Option<&'a str>,
+        from: Option<Cow<'a, str>>,
     },
     /// Whether a campaign telegram will be blocked by the nation's telegram settings.
     TGCanCampaign {
         /// Whether the nation will deny a campaign telegram from this region in particular
         /// due to having received one too recently.
-        from: Option<&'a str>,
+        from: Option<Cow<'a, str>>,
     },
     /// The pre-title of the nation.
     Type,
@@ -264,6 +273,9 @@ pub enum PublicNationShard<'a> {
     WABadges,
     /// The world rank on today's featured World Census scale.
     WCensus,
+    /// A shard that this crate does not support yet, sent as it is given.
+    /// Its response is not parsed.
+    Raw(RawShard<'a>),
 }
 
 /// A request of the public nation API.
@@ -276,42 +288,94 @@ pub enum PublicNationShard<'a> {
 ///     vec![PublicNationShard::Capital],
 /// );
 /// ```
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, Default, PartialEq)]
 pub struct PublicNationRequest<'a> {
-    nation: &'a str,
-    shards: Vec<PublicNationShard<'a>>,
+    nation: Option<Cow<'a, str>>,
+    shards: ShardSet<PublicNationShard<'a>>,
+    url: CachedUrl,
 }
 
 impl<'a> PublicNationRequest<'a> {
     /// Creates a new builder given a nation name.
+    /// A [`NationName`](crate::models::name::NationName) is sent in its safe form,
+    /// so names from parsed responses can be used for follow-up requests.
     ///
     /// If you do not modify the shards on this request,
     /// you will get a default response using the "standard public nation API shard set".
     /// See [`StandardPublicNationRequest`] for more information.
-    pub fn new(nation: &'a str) -> Self {
+    pub fn new(nation: impl Into<Cow<'a, str>>) -> Self {
         Self {
-            nation,
-            shards: vec![],
+            nation: Some(nation.into()),
+            shards: ShardSet::new(),
+            url: CachedUrl::default(),
+        }
+    }
+
+    /// Creates a new builder with shards but no nation.
+    ///
+    /// The nation must be set with [`PublicNationRequest::nation`] before the request is sent,
+    /// or building the URL fails with [`RequestBuildError::MissingParam`].
+    pub fn with_shards<T>(shards: T) -> Self
+    where
+        T: AsRef<[PublicNationShard<'a>]>,
+    {
+        Self {
+            nation: None,
+            shards: ShardSet::from(shards.as_ref()),
+            url: CachedUrl::default(),
         }
     }
 
     /// Create a new request.
-    pub fn new_with_shards<T>(nation: &'a str, shards: T) -> Self
+    pub fn new_with_shards<T>(nation: impl Into<Cow<'a, str>>, shards: T) -> Self
     where
         T: AsRef<[PublicNationShard<'a>]>,
     {
         Self {
-            nation,
-            shards: shards.as_ref().to_vec(),
+            nation: Some(nation.into()),
+            shards: ShardSet::from(shards.as_ref()),
+            url: CachedUrl::default(),
         }
     }
 
+    /// Rebuilds a request from an API URL.
+    ///
+    /// See [`RequestUrl`] for an example.
+    pub fn from_url(url: &'a RequestUrl) -> Result<Self, FromUrlError> {
+        Ok(Self {
+            nation: Some(Cow::Borrowed(url.require("nation")?)),
+            shards: url.shards(|shard| {
+                Ok(match shard {
+                    PublicNationShard::Census(_) => PublicNationShard::Census(url.census()?),
+                    PublicNationShard::TGCanCampaign { .. } => PublicNationShard::TGCanCampaign {
+                        from: url.param("from").map(Cow::Borrowed),
+                    },
+                    PublicNationShard::TGCanRecruit { .. } => PublicNationShard::TGCanRecruit {
+                        from: url.param("from").map(Cow::Borrowed),
+                    },
+                    // A raw shard's name is not known ahead of time.
+                    PublicNationShard::Raw(_) => {
+                        return Err(FromUrlError::UnknownShard(String::from("raw")))
+                    }
+                    other => other,
+                })
+            })?,
+            url: CachedUrl::default(),
+        })
+    }
+
     /// Sets the nation for the request.
-    pub fn nation(&mut self, nation: &'a str) -> &mut Self {
-        self.nation = nation;
+    pub fn nation(&mut self, nation: impl Into<Cow<'a, str>>) -> &mut Self {
+        self.nation = Some(nation.into());
+        self.url.clear();
         self
     }
 
+    /// The nation the request is for, if it has been set.
+    pub fn nation_name(&self) -> Option<&str> {
+        self.nation.as_deref()
+    }
+
     /// Modify shards using a function.
     ///
     /// ## Example
@@ -319,7 +383,7 @@ impl<'a> PublicNationRequest<'a> {
     /// # use crustacean_states::shards::nation::{PublicNationRequest, PublicNationShard};
     /// let mut request_builder = PublicNationRequest::new("Aramos");
     /// request_builder.shards(|s| {
-    ///     s.push(PublicNationShard::Capital);
+    ///     s.insert(PublicNationShard::Capital);
     /// });
     /// assert_eq!(
     ///     request_builder,
@@ -331,9 +395,10 @@ impl<'a> PublicNationRequest<'a> {
     /// ```
     pub fn shards<F>(&mut self, f: F) -> &mut Self
     where
-        F: FnOnce(&mut Vec<PublicNationShard<'a>>),
+        F: FnOnce(&mut ShardSet<PublicNationShard<'a>>),
     {
         f(&mut self.shards);
+        self.url.clear();
         self
     }
 
@@ -355,7 +420,8 @@ impl<'a> PublicNationRequest<'a> {
     /// );
     /// ```
     pub fn add_shard(&mut self, shard: PublicNationShard<'a>) -> &mut Self {
-        self.shards.push(shard);
+        self.shards.insert(shard);
+        self.url.clear();
         self
     }
 
@@ -392,19 +458,33 @@ impl<'a> PublicNationRequest<'a> {
         I: IntoIterator<Item = PublicNationShard<'a>>,
     {
         self.shards.extend(shards);
+        self.url.clear();
         self
     }
 }
 
 impl<'a> NSRequest for PublicNationRequest<'a> {
+    /// The URL is built once, then reused until the request is changed.
+    fn as_url(&self) -> Result<Url, RequestBuildError> {
+        self.url.get_or_build(|| self.build_url())
+    }
+}
+
+#[cfg(feature = "parsers")]
+impl<'a> NSRequestParse for PublicNationRequest<'a> {
+    type Response = Nation;
+}
+
+impl<'a> PublicNationRequest<'a> {
     //noinspection SpellCheckingInspection
-    fn as_url(&self) -> Url {
-        let query = self
-            .shards
-            .iter()
-            .map(|s| s.as_ref())
-            .join("+")
-            .to_ascii_lowercase();
+    fn build_url(&self) -> Result<Url, RequestBuildError> {
+        let query = join_lowercase(
+            self.shards.iter().map(|s| match s {
+                PublicNationShard::Raw(raw) => raw.name.as_ref(),
+                s => s.into(),
+            }),
+            '+',
+        );
 
         let mut params = Params::default();
         self.shards.iter().for_each(|s| match s {
@@ -415,16 +495,100 @@ impl<'a> NSRequest for PublicNationRequest<'a> {
             | PublicNationShard::TGCanRecruit { from } => {
                 params.insert_on("from", from);
             }
+            PublicNationShard::Raw(raw) => {
+                params.insert_raw(raw);
+            }
             _ => {} // no other public nation shards require parameters
         });
 
-        Url::parse_with_params(
-            BASE_URL,
+        Ok(api_url(
             params
                 .insert_front("q", query)
-                .insert_front("nation", self.nation),
-        )
-        .unwrap()
+                .insert_front("nation", require_name("nation", self.nation.as_deref())?)
+                .check()?,
+        )?)
+    }
+}
+
+#[cfg(feature = "parsers")]
+impl<'a> VerifyShards for PublicNationRequest<'a> {
+    //noinspection SpellCheckingInspection
+    fn missing_shards(&self, nation: &Nation) -> Vec<String> {
+        self.shards
+            .iter()
+            .filter(|s| {
+                !match s {
+                    PublicNationShard::Admirable => nation.admirable.is_some(),
+                    PublicNationShard::Admirables => nation.admirables.is_some(),
+                    PublicNationShard::Animal => nation.animal.is_some(),
+                    PublicNationShard::AnimalTrait => nation.animal_trait.is_some(),
+                    PublicNationShard::Answered => nation.issues_answered.is_some(),
+                    PublicNationShard::Banner => nation.banner.is_some(),
+                    PublicNationShard::Banners => nation.banners.is_some(),
+                    PublicNationShard::Capital => nation.capital.is_some(),
+                    PublicNationShard::Category => nation.category.is_some(),
+                    PublicNationShard::Census(_) => nation.census.is_some(),
+                    PublicNationShard::Crime => nation.crime.is_some(),
+                    PublicNationShard::Currency => nation.currency.is_some(),
+                    PublicNationShard::DbId => nation.dbid.is_some(),
+                    PublicNationShard::Deaths => nation.deaths.is_some(),
+                    PublicNationShard::Demonym => nation.demonym_adjective.is_some(),
+                    PublicNationShard::Demonym2 => nation.demonym_singular.is_some(),
+                    PublicNationShard::Demonym2Plural => nation.demonym_plural.is_some(),
+                    PublicNationShard::Dispatches => nation.dispatches.is_some(),
+                    PublicNationShard::DispatchList => nation.dispatch_list.is_some(),
+                    PublicNationShard::Endorsements => nation.endorsements.is_some(),
+                    PublicNationShard::Factbooks => nation.factbooks.is_some(),
+                    PublicNationShard::FactbookList => nation.factbook_list.is_some(),
+                    PublicNationShard::FirstLogin => nation.first_login.is_some(),
+                    PublicNationShard::Flag => nation.flag.is_some(),
+                    PublicNationShard::Founded => nation.founded.is_some(),
+                    PublicNationShard::FoundedTime => nation.founded_time.is_some(),
+                    PublicNationShard::Freedom => nation.freedom.is_some(),
+                    PublicNationShard::FreedomScores => nation.freedom_scores.is_some(),
+                    PublicNationShard::FullName => nation.full_name.is_some(),
+                    PublicNationShard::GAVote => nation.ga_vote.is_some(),
+                    PublicNationShard::Gdp => nation.gdp.is_some(),
+                    PublicNationShard::Govt => nation.government.is_some(),
+                    PublicNationShard::GovtDesc => nation.govt_desc.is_some(),
+                    PublicNationShard::GovtPriority => nation.government_priority.is_some(),
+                    PublicNationShard::Happenings => nation.happenings.is_some(),
+                    PublicNationShard::Income => nation.income.is_some(),
+                    PublicNationShard::IndustryDesc => nation.industry_desc.is_some(),
+                    PublicNationShard::Influence => nation.influence.is_some(),
+                    PublicNationShard::LastActivity => nation.last_activity.is_some(),
+                    PublicNationShard::LastLogin => nation.last_login.is_some(),
+                    PublicNationShard::Leader => nation.leader.is_some(),
+                    PublicNationShard::Legislation => nation.legislation.is_some(),
+                    PublicNationShard::MajorIndustry => nation.major_industry.is_some(),
+                    PublicNationShard::Motto => nation.motto.is_some(),
+                    PublicNationShard::Notable => nation.notable.is_some(),
+                    PublicNationShard::Notables => nation.notables.is_some(),
+                    PublicNationShard::Policies => nation.policies.is_some(),
+                    PublicNationShard::Poorest => nation.poorest.is_some(),
+                    PublicNationShard::Population => nation.population.is_some(),
+                    PublicNationShard::PublicSector => nation.public_sector.is_some(),
+                    PublicNationShard::RCensus => nation.regional_census.is_some(),
+                    PublicNationShard::Region => nation.region.is_some(),
+                    PublicNationShard::Religion => nation.religion.is_some(),
+                    PublicNationShard::Richest => nation.richest.is_some(),
+                    PublicNationShard::SCVote => nation.sc_vote.is_some(),
+                    PublicNationShard::Sectors => nation.sectors.is_some(),
+                    PublicNationShard::Sensibilities => nation.sensibilities.is_some(),
+                    PublicNationShard::Tax => nation.tax.is_some(),
+                    PublicNationShard::TGCanRecruit { .. } => nation.tg_can_recruit.is_some(),
+                    PublicNationShard::TGCanCampaign { .. } => nation.tg_can_campaign.is_some(),
+                    PublicNationShard::Type => nation.kind.is_some(),
+                    PublicNationShard::WA => nation.wa_status.is_some(),
+                    PublicNationShard::WCensus => nation.world_census.is_some(),
+                    // The name is always filled in, and the others have no field to check.
+                    PublicNationShard::Name
+                    | PublicNationShard::WABadges
+                    | PublicNationShard::Raw(_) => true,
+                }
+            })
+            .map(|s| s.as_ref().to_ascii_lowercase())
+            .collect()
     }
 }
 
@@ -461,25 +625,35 @@ impl<'a> NSRequest for PublicNationRequest<'a> {
 /// [`Dispatches`](PublicNationShard::Dispatches).
 ///
 #[derive(Clone, Debug)]
-pub struct StandardPublicNationRequest<'a>(&'a str);
+pub struct StandardPublicNationRequest<'a>(Cow<'a, str>);
 
 impl<'a> StandardPublicNationRequest<'a> {
     /// Create a new standard public nation request of the provided nation.
-    pub fn new(nation: &'a str) -> Self {
-        Self(nation)
+    pub fn new(nation: impl Into<Cow<'a, str>>) -> Self {
+        Self(nation.into())
     }
 }
 
 impl<'a> NSRequest for StandardPublicNationRequest<'a> {
-    fn as_url(&self) -> Url {
-        Url::parse_with_params(BASE_URL, [("nation", self.0)]).unwrap()
+    fn as_url(&self) -> Result<Url, RequestBuildError> {
+        Ok(api_url([(
+            "nation",
+            require_name("nation", Some(&self.0))?,
+        )])?)
     }
 }
 
+#[cfg(feature = "parsers")]
+impl<'a> NSRequestParse for StandardPublicNationRequest<'a> {
+    type Response = StandardNation;
+}
+
 #[cfg(test)]
 mod tests {
     use crate::shards::nation::PublicNationShard;
-    use crate::shards::{CensusCurrentMode, CensusModes, CensusScales, CensusShard};
+    use crate::shards::{
+        CensusCurrentMode, CensusModes, CensusScales, CensusShard, NSRequest, RequestBuildError,
+    };
 
     #[test]
     fn pns_normal_as_str() {
@@ -500,10 +674,153 @@ mod tests {
     fn add_shards() {
         let mut request_builder = crate::shards::nation::PublicNationRequest::new("Aramos");
         request_builder.add_shards([PublicNationShard::Capital, PublicNationShard::Animal]);
-        assert_eq!(request_builder.nation, "Aramos");
+        assert_eq!(request_builder.nation.as_deref(), Some("Aramos"));
+        assert_eq!(
+            request_builder.shards.as_slice(),
+            [PublicNationShard::Capital, PublicNationShard::Animal]
+        );
+    }
+
+    #[test]
+    fn duplicate_shards() {
+        let mut request = crate::shards::nation::PublicNationRequest::new_with_shards(
+            "Aramos",
+            [PublicNationShard::Capital, PublicNationShard::Animal],
+        );
+        request.add_shards([PublicNationShard::Animal, PublicNationShard::Capital]);
+        assert_eq!(
+            request.as_url().unwrap().as_str(),
+            "https://www.nationstates.net/cgi-bin/api.cgi?nation=Aramos&q=customcapital%2Banimal&v=12"
+        );
+    }
+
+    #[test]
+    fn conflicting_census_shards() {
+        let request = crate::shards::nation::PublicNationRequest::new_with_shards(
+            "Aramos",
+            [
+                PublicNationShard::Census(CensusShard::new(
+                    CensusScales::One(1),
+                    CensusModes::default(),
+                )),
+                PublicNationShard::Census(CensusShard::new(
+                    CensusScales::One(2),
+                    CensusModes::default(),
+                )),
+            ],
+        );
+        assert!(matches!(
+            request.as_url(),
+            Err(RequestBuildError::ConflictingShards { key, .. }) if key == "scale"
+        ));
+    }
+
+    #[test]
+    fn from_url_round_trip() {
+        let request = crate::shards::nation::PublicNationRequest::new_with_shards(
+            "aramos",
+            [
+                PublicNationShard::Census(CensusShard::new(
+                    CensusScales::from([1, 2].as_slice()),
+                    CensusModes::from([CensusCurrentMode::Score]),
+                )),
+                PublicNationShard::TGCanRecruit {
+                    from: Some("anteria".into()),
+                },
+            ],
+        );
+        let url = crate::shards::RequestUrl::try_from(&request.as_url().unwrap()).unwrap();
+        assert_eq!(
+            crate::shards::nation::PublicNationRequest::from_url(&url).unwrap(),
+            request
+        );
+    }
+
+    #[test]
+    fn missing_nation() {
+        let request =
+            crate::shards::nation::PublicNationRequest::with_shards([PublicNationShard::Animal]);
+        assert!(matches!(
+            request.as_url(),
+            Err(RequestBuildError::MissingParam("nation"))
+        ));
+        assert!(matches!(
+            crate::shards::nation::StandardPublicNationRequest::new("").as_url(),
+            Err(RequestBuildError::MissingParam("nation"))
+        ));
+    }
+
+    #[test]
+    fn owned_request() {
+        fn assert_static<T: Send + 'static>(_: T) {}
+
+        let nation = String::from("Aramos");
+        let mut request = crate::shards::nation::PublicNationRequest::new(nation);
+        request.add_shards([PublicNationShard::TGCanRecruit {
+            from: Some(String::from("anteria").into()),
+        }]);
+        assert_static(request);
+    }
+
+    #[test]
+    #[cfg(feature = "parsers")]
+    fn verify_shards() {
+        use crate::parsers::nation::Nation;
+        use crate::shards::{MissingShardData, VerifyShards};
+
+        let request = crate::shards::nation::PublicNationRequest::new_with_shards(
+            "Aramos",
+            [
+                PublicNationShard::Animal,
+                PublicNationShard::Flag,
+                PublicNationShard::Capital,
+                PublicNationShard::Name,
+            ],
+        );
+        let nation =
+            Nation::from_xml(r#"<NATION id="aramos"><ANIMAL>dolphin</ANIMAL></NATION>"#).unwrap();
+        assert_eq!(request.missing_shards(&nation), ["flag", "customcapital"]);
+        let error = request.verify(&nation).unwrap_err();
+        assert_eq!(
+            error,
+            MissingShardData {
+                shards: vec![String::from("flag"), String::from("customcapital")]
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "response is missing data for shards: flag, customcapital"
+        );
+    }
+
+    #[test]
+    fn cached_url() {
+        let mut request = crate::shards::nation::PublicNationRequest::new_with_shards(
+            "Aramos",
+            [PublicNationShard::Animal],
+        );
+        let first = request.as_url().unwrap();
+        assert_eq!(request.as_url().unwrap(), first);
+        assert_eq!(request.clone().as_url().unwrap(), first);
+        assert_eq!(
+            request,
+            crate::shards::nation::PublicNationRequest::new_with_shards(
+                "Aramos",
+                [PublicNationShard::Animal],
+            )
+        );
+
+        request.add_shard(PublicNationShard::Flag);
+        assert_eq!(
+            request.as_url().unwrap().query(),
+            Some("nation=Aramos&q=animal%2Bflag&v=12")
+        );
+        request.nation("");
+        assert!(request.as_url().is_err());
+        request.nation("Testlandia");
         assert_eq!(
-            request_builder.shards,
-            vec![PublicNationShard::Capital, PublicNationShard::Animal]
+            request.as_url().unwrap().query(),
+            Some("nation=Testlandia&q=animal%2Bflag&v=12")
         );
     }
 }
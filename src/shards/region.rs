@@ -1,13 +1,24 @@
 //! For region shard requests.
-use crate::shards::{CensusRanksShard, CensusShard, NSRequest, Params, BASE_URL};
-use itertools::Itertools;
+use crate::shards::{
+    api_url, join_lowercase, require_name, CachedUrl, CensusRanksShard, CensusShard, FromUrlError,
+    NSRequest, Params, RawShard, RequestBuildError, RequestUrl, ShardSet,
+};
+#[cfg(feature = "parsers")]
+use crate::{
+    parsers::region::Region,
+    shards::{NSRequestParse, VerifyShards},
+};
+use std::borrow::Cow;
+use std::convert::Infallible;
 use std::fmt::{Display, Formatter};
 use std::num::{NonZeroU32, NonZeroU8};
-use strum::AsRefStr;
+use std::str::FromStr;
+use strum::{AsRefStr, EnumIter, EnumString, IntoEnumIterator, IntoStaticStr, VariantNames};
 use url::Url;
 
 /// A request of a region.
-#[derive(AsRefStr, Clone, Debug, PartialEq)]
+#[derive(AsRefStr, Clone, Debug, EnumString, IntoStaticStr, PartialEq, VariantNames)]
+#[strum(ascii_case_insensitive)]
 pub enum RegionShard<'a> {
     /// The list of all nations banned from the region.
     BanList,
@@ -97,6 +108,9 @@ pub enum RegionShard<'a> {
     WABadges,
     /// The list of World Assembly nations in the region.
     WANations,
+    /// A shard that this crate does not support yet, sent as it is given.
+    /// Its response is not parsed.
+    Raw(RawShard<'a>),
 }
 
 /// A builder for the [`RegionShard::Messages`] shard.
@@ -106,11 +120,11 @@ pub enum RegionShard<'a> {
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct RmbShard {
     /// Return this many messages. Must be in the range 1-100.
-    limit: Option<NonZeroU8>,
+    pub(crate) limit: Option<NonZeroU8>,
     /// Skip the most recent (number) messages. Begin back farther.
-    offset: Option<NonZeroU32>,
+    pub(crate) offset: Option<NonZeroU32>,
     /// Instead of returning the most recent messages, return messages starting from this post ID.
-    starting_post: Option<NonZeroU32>,
+    pub(crate) starting_post: Option<NonZeroU32>,
 }
 
 impl RmbShard {
@@ -155,37 +169,84 @@ impl RmbShard {
 /// ```
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct RegionRequest<'a> {
-    region: &'a str,
-    shards: Vec<RegionShard<'a>>,
+    region: Option<Cow<'a, str>>,
+    shards: ShardSet<RegionShard<'a>>,
+    url: CachedUrl,
 }
 
 impl<'a> RegionRequest<'a> {
     /// Creates a new builder given a region name.
+    /// A [`RegionName`](crate::models::name::RegionName) is sent in its safe form,
+    /// so names from parsed responses can be used for follow-up requests.
     ///
     /// If you do not modify the shards on this request,
     /// you will get a default response using the "standard region API shard set".
     /// See [`StandardRegionRequest`] for more information.
-    pub fn new(region: &'a str) -> Self {
+    pub fn new(region: impl Into<Cow<'a, str>>) -> Self {
         Self {
-            region,
-            shards: vec![],
+            region: Some(region.into()),
+            shards: ShardSet::new(),
+            url: CachedUrl::default(),
+        }
+    }
+
+    /// Creates a new builder with shards but no region.
+    ///
+    /// The region must be set with [`RegionRequest::region`] before the request is sent,
+    /// or building the URL fails with [`RequestBuildError::MissingParam`].
+    pub fn with_shards<T>(shards: T) -> Self
+    where
+        T: AsRef<[RegionShard<'a>]>,
+    {
+        Self {
+            region: None,
+            shards: ShardSet::from(shards.as_ref()),
+            url: CachedUrl::default(),
         }
     }
 
     /// Create a new request.
-    pub fn new_with_shards<T>(region: &'a str, shards: T) -> Self
+    pub fn new_with_shards<T>(region: impl Into<Cow<'a, str>>, shards: T) -> Self
     where
         T: AsRef<[RegionShard<'a>]>,
     {
         Self {
-            region,
-            shards: shards.as_ref().to_vec(),
+            region: Some(region.into()),
+            shards: ShardSet::from(shards.as_ref()),
+            url: CachedUrl::default(),
         }
     }
 
+    /// Rebuilds a request from an API URL.
+    ///
+    /// See [`RequestUrl`] for an example.
+    pub fn from_url(url: &'a RequestUrl) -> Result<Self, FromUrlError> {
+        Ok(Self {
+            region: Some(Cow::Borrowed(url.require("region")?)),
+            shards: url.shards(|shard| {
+                Ok(match shard {
+                    RegionShard::Census(_) => RegionShard::Census(url.census()?),
+                    RegionShard::CensusRanks(_) => RegionShard::CensusRanks(url.census_ranks()?),
+                    RegionShard::Messages(_) => RegionShard::Messages(RmbShard {
+                        limit: url.parse_param("limit")?,
+                        offset: url.parse_param("offset")?,
+                        starting_post: url.parse_param("fromid")?,
+                    }),
+                    // A raw shard's name is not known ahead of time.
+                    RegionShard::Raw(_) => {
+                        return Err(FromUrlError::UnknownShard(String::from("raw")))
+                    }
+                    other => other,
+                })
+            })?,
+            url: CachedUrl::default(),
+        })
+    }
+
     /// Sets the region for the request.
-    pub fn region(&mut self, region: &'a str) -> &mut Self {
-        self.region = region;
+    pub fn region(&mut self, region: impl Into<Cow<'a, str>>) -> &mut Self {
+        self.region = Some(region.into());
+        self.url.clear();
         self
     }
 
@@ -196,7 +257,7 @@ impl<'a> RegionRequest<'a> {
     /// # use crustacean_states::shards::region::{RegionRequest, RegionShard};
     /// let mut request_builder = RegionRequest::new("Anteria");
     /// request_builder.shards(|s| {
-    ///     s.push(RegionShard::Delegate);
+    ///     s.insert(RegionShard::Delegate);
     /// });
     /// assert_eq!(
     ///     request_builder,
@@ -208,9 +269,10 @@ impl<'a> RegionRequest<'a> {
     /// ```
     pub fn shards<F>(&mut self, f: F) -> &mut Self
     where
-        F: FnOnce(&mut Vec<RegionShard>),
+        F: FnOnce(&mut ShardSet<RegionShard<'a>>),
     {
         f(&mut self.shards);
+        self.url.clear();
         self
     }
 
@@ -232,7 +294,8 @@ impl<'a> RegionRequest<'a> {
     /// );
     /// ```
     pub fn add_shard(&mut self, shard: RegionShard<'a>) -> &mut Self {
-        self.shards.push(shard);
+        self.shards.insert(shard);
+        self.url.clear();
         self
     }
 
@@ -269,19 +332,33 @@ impl<'a> RegionRequest<'a> {
         I: IntoIterator<Item = RegionShard<'a>>,
     {
         self.shards.extend(shards);
+        self.url.clear();
         self
     }
 }
 
 impl<'a> NSRequest for RegionRequest<'a> {
+    /// The URL is built once, then reused until the request is changed.
+    fn as_url(&self) -> Result<Url, RequestBuildError> {
+        self.url.get_or_build(|| self.build_url())
+    }
+}
+
+#[cfg(feature = "parsers")]
+impl<'a> NSRequestParse for RegionRequest<'a> {
+    type Response = Region;
+}
+
+impl<'a> RegionRequest<'a> {
     //noinspection SpellCheckingInspection
-    fn as_url(&self) -> Url {
-        let query = self
-            .shards
-            .iter()
-            .map(|s| s.as_ref())
-            .join("+")
-            .to_ascii_lowercase();
+    fn build_url(&self) -> Result<Url, RequestBuildError> {
+        let query = join_lowercase(
+            self.shards.iter().map(|s| match s {
+                RegionShard::Raw(raw) => raw.name.as_ref(),
+                s => s.into(),
+            }),
+            '+',
+        );
         let mut params = Params::default();
         self.shards.iter().for_each(|s| match s {
             RegionShard::Census(CensusShard { scale, modes }) => {
@@ -300,16 +377,71 @@ impl<'a> NSRequest for RegionRequest<'a> {
                     .insert_on("offset", offset)
                     .insert_on("fromid", starting_post);
             }
+            RegionShard::Raw(raw) => {
+                params.insert_raw(raw);
+            }
             _ => {}
         });
 
-        Url::parse_with_params(
-            BASE_URL,
+        Ok(api_url(
             params
                 .insert_front("q", query)
-                .insert_front("region", self.region),
-        )
-        .unwrap()
+                .insert_front("region", require_name("region", self.region.as_deref())?)
+                .check()?,
+        )?)
+    }
+}
+
+#[cfg(feature = "parsers")]
+impl<'a> VerifyShards for RegionRequest<'a> {
+    //noinspection SpellCheckingInspection
+    fn missing_shards(&self, region: &Region) -> Vec<String> {
+        self.shards
+            .iter()
+            .filter(|s| {
+                !match s {
+                    RegionShard::BanList => region.ban_list.is_some(),
+                    RegionShard::Banner => region.banner.is_some(),
+                    RegionShard::BannerBy => region.banner_by.is_some(),
+                    RegionShard::BannerUrl => region.banner_url.is_some(),
+                    RegionShard::Census(_) => region.census.is_some(),
+                    RegionShard::CensusRanks(_) => region.census_ranks.is_some(),
+                    RegionShard::DbId => region.dbid.is_some(),
+                    RegionShard::Delegate => region.delegate.is_some(),
+                    RegionShard::DelegateAuth => region.delegate_authority.is_some(),
+                    RegionShard::DelegateVotes => region.delegate_votes.is_some(),
+                    RegionShard::Dispatches => region.dispatches.is_some(),
+                    RegionShard::Embassies => region.embassies.is_some(),
+                    RegionShard::EmbassyRmb => region.embassy_rmb.is_some(),
+                    RegionShard::Factbook => region.factbook.is_some(),
+                    RegionShard::Flag => region.flag.is_some(),
+                    RegionShard::Founded => region.founded.is_some(),
+                    RegionShard::FoundedTime => region.founded_time.is_some(),
+                    RegionShard::Founder => region.founder.is_some(),
+                    RegionShard::Frontier => region.frontier.is_some(),
+                    RegionShard::GAVote => region.ga_vote.is_some(),
+                    RegionShard::Happenings => region.happenings.is_some(),
+                    RegionShard::History => region.history.is_some(),
+                    RegionShard::LastUpdate => region.last_update.is_some(),
+                    RegionShard::LastMajorUpdate => region.last_major_update.is_some(),
+                    RegionShard::LastMinorUpdate => region.last_minor_update.is_some(),
+                    RegionShard::Messages(_) => region.messages.is_some(),
+                    RegionShard::Nations => region.nations.is_some(),
+                    RegionShard::NumNations => region.num_nations.is_some(),
+                    RegionShard::NumWANations => region.num_wa_nations.is_some(),
+                    RegionShard::Officers => region.officers.is_some(),
+                    RegionShard::Poll => region.poll.is_some(),
+                    RegionShard::Power => region.power.is_some(),
+                    RegionShard::SCVote => region.sc_vote.is_some(),
+                    RegionShard::Tags => region.tags.is_some(),
+                    RegionShard::WABadges => region.wa_badges.is_some(),
+                    RegionShard::WANations => region.wa_nations.is_some(),
+                    // The name is always filled in, and raw shards are not parsed.
+                    RegionShard::Name | RegionShard::Raw(_) => true,
+                }
+            })
+            .map(|s| s.as_ref().to_ascii_lowercase())
+            .collect()
     }
 }
 
@@ -332,24 +464,33 @@ impl<'a> NSRequest for RegionRequest<'a> {
 /// [`Embassies`](RegionShard::Embassies), [`WABadges`](RegionShard::WABadges),
 /// [`LastUpdate`](RegionShard::LastUpdate), [`LastMajorUpdate`](RegionShard::LastMajorUpdate), and
 /// [`LastMinorUpdate`](RegionShard::LastMinorUpdate).
-pub struct StandardRegionRequest<'a>(&'a str);
+pub struct StandardRegionRequest<'a>(Cow<'a, str>);
 
 impl<'a> StandardRegionRequest<'a> {
     /// Create a new standard region request.
-    pub fn new(region: &'a str) -> Self {
-        Self(region)
+    pub fn new(region: impl Into<Cow<'a, str>>) -> Self {
+        Self(region.into())
     }
 }
 
 impl<'a> NSRequest for StandardRegionRequest<'a> {
-    fn as_url(&self) -> Url {
-        Url::parse_with_params(BASE_URL, [("region", self.0)]).unwrap()
+    fn as_url(&self) -> Result<Url, RequestBuildError> {
+        Ok(api_url([(
+            "region",
+            require_name("region", Some(&self.0))?,
+        )])?)
     }
 }
 
+#[cfg(feature = "parsers")]
+impl<'a> NSRequestParse for StandardRegionRequest<'a> {
+    type Response = Region;
+}
+
 /// All the tags a region can have.
 ///
 /// This list is non-exhaustive as new tags are added on occasion by NationStates.
+/// Tags that are not listed yet are kept as [`Tag::Other`].
 ///
 /// Tags that a region may freely assign and unassign do not have a fixed meaning,
 /// so they are only marked with (self-tag).
@@ -357,7 +498,7 @@ impl<'a> NSRequest for StandardRegionRequest<'a> {
 /// Some tags have been given added clarity in their variant name, and in those cases,
 /// their original name is also documented.
 //noinspection SpellCheckingInspection
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, EnumIter, PartialEq)]
 #[non_exhaustive]
 #[allow(missing_docs)]
 pub enum Tag {
@@ -590,119 +731,171 @@ pub enum Tag {
     Warzone,
     /// (self-tag)
     WorldAssembly,
+    /// A tag that this crate does not know about yet,
+    /// by the name used for it in URLs (e.g. `new_tag`).
+    Other(String),
 }
 
-impl Display for Tag {
+impl FromStr for Tag {
+    type Err = Infallible;
+
+    /// Finds the tag with the name used for it in URLs.
+    /// Names that are not recognized become [`Tag::Other`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Tag::iter()
+            .find(|t| t.as_str() == s)
+            .unwrap_or_else(|| Tag::Other(s.to_string())))
+    }
+}
+
+impl Tag {
+    /// The name of the tag as it is used in URLs, without allocating.
     //noinspection SpellCheckingInspection
+    pub fn as_str(&self) -> &str {
+        match self {
+            Tag::Anarchist => "anarchist",
+            Tag::Anime => "anime",
+            Tag::AntiCapitalist => "anti-capitalist",
+            Tag::AntiCommunist => "anti-communist",
+            Tag::AntiFascist => "anti-fascist",
+            Tag::AntiGeneralAssembly => "anti-general_assembly",
+            Tag::AntiSecurityCouncil => "anti-security_council",
+            Tag::AntiWorldAssembly => "anti-world_assembly",
+            Tag::Capitalist => "capitalist",
+            Tag::Casual => "casual",
+            Tag::Catcher => "catcher",
+            Tag::Class => "class",
+            Tag::Colony => "colony",
+            Tag::Commended => "commended",
+            Tag::Communist => "communist",
+            Tag::Condemned => "condemned",
+            Tag::Conservative => "conservative",
+            Tag::Cyberpunk => "cyberpunk",
+            Tag::Defender => "defender",
+            Tag::Democratic => "democratic",
+            Tag::EcoFriendly => "eco-friendly",
+            Tag::Egalitarian => "egalitarian",
+            Tag::EmbassyCollector => "embassy_collector",
+            Tag::Enormous => "enormous",
+            Tag::ForumSevener => "f7er",
+            Tag::FutureTechFasterThanLight => "ft_ftl",
+            Tag::FutureTechFasterThanLightInhibited => "ft_ftli",
+            Tag::FutureTechSlowerThanLight => "ft_stl",
+            Tag::Fandom => "fandom",
+            Tag::FantasyTech => "fantasy_tech",
+            Tag::Fascist => "fascist",
+            Tag::Featured => "featured",
+            Tag::Feeder => "feeder",
+            Tag::Feminist => "feminist",
+            Tag::Founderless => "founderless",
+            Tag::FreeTrade => "free_trade",
+            Tag::Frontier => "frontier",
+            Tag::FutureTech => "future_tech",
+            Tag::GamePlayer => "game_player",
+            Tag::Gargantuan => "gargantuan",
+            Tag::GeneralAssembly => "general_assembly",
+            Tag::Generalite => "generalite",
+            Tag::Governorless => "governorless",
+            Tag::HumanOnly => "human-only",
+            Tag::Imperialist => "imperialist",
+            Tag::Independent => "independent",
+            Tag::Industrial => "industrial",
+            Tag::Injuncted => "injuncted",
+            Tag::InternationalFederalist => "international_federalist",
+            Tag::Invader => "invader",
+            Tag::Isolationist => "isolationist",
+            Tag::IssuesPlayer => "issues_player",
+            Tag::JumpPoint => "jump_point",
+            Tag::Lgbt => "lgbt",
+            Tag::Large => "large",
+            Tag::Liberal => "liberal",
+            Tag::Liberated => "liberated",
+            Tag::Libertarian => "libertarian",
+            Tag::Magical => "magical",
+            Tag::Map => "map",
+            Tag::Medium => "medium",
+            Tag::Mercenary => "mercenary",
+            Tag::Miniscule => "minuscule",
+            Tag::ModernTech => "modern_tech",
+            Tag::Monarchist => "monarchist",
+            Tag::MultiSpecies => "multi-species",
+            Tag::NationalSovereigntist => "national_sovereigntist",
+            Tag::Neutral => "neutral",
+            Tag::New => "new",
+            Tag::NonEnglish => "non-english",
+            Tag::OffsiteChat => "offsite_chat",
+            Tag::OffsiteForums => "offsite_forums",
+            Tag::OuterSpace => "outer_space",
+            Tag::PortalToTheMultiverse => "p2tm",
+            Tag::Pacifist => "pacifist",
+            Tag::Parody => "parody",
+            Tag::Password => "password",
+            Tag::PastTech => "past_tech",
+            Tag::Patriarchal => "patriarchal",
+            Tag::PostApocalyptic => "post_apocalyptic",
+            Tag::PostModernTech => "post-modern_tech",
+            Tag::PuppetStorage => "puppet_storage",
+            Tag::RegionalGovernment => "regional_government",
+            Tag::Religious => "religious",
+            Tag::Restorer => "restorer",
+            Tag::RolePlayer => "role_player",
+            Tag::SecurityCouncil => "security_council",
+            Tag::Serious => "serious",
+            Tag::Silly => "silly",
+            Tag::Sinker => "sinker",
+            Tag::Small => "small",
+            Tag::Snarky => "snarky",
+            Tag::Social => "social",
+            Tag::Socialist => "socialist",
+            Tag::Sports => "sports",
+            Tag::Steampunk => "steampunk",
+            Tag::Surreal => "surreal",
+            Tag::Theocratic => "theocratic",
+            Tag::Totalitarian => "totalitarian",
+            Tag::TradingCards => "trading_cards",
+            Tag::VideoGame => "video_game",
+            Tag::Warzone => "warzone",
+            Tag::WorldAssembly => "world_assembly",
+            Tag::Other(tag) => tag.as_str(),
+        }
+    }
+}
+
+impl Display for Tag {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            match self {
-                Tag::Anarchist => "anarchist",
-                Tag::Anime => "anime",
-                Tag::AntiCapitalist => "anti-capitalist",
-                Tag::AntiCommunist => "anti-communist",
-                Tag::AntiFascist => "anti-fascist",
-                Tag::AntiGeneralAssembly => "anti-general_assembly",
-                Tag::AntiSecurityCouncil => "anti-security_council",
-                Tag::AntiWorldAssembly => "anti-world_assembly",
-                Tag::Capitalist => "capitalist",
-                Tag::Casual => "casual",
-                Tag::Catcher => "catcher",
-                Tag::Class => "class",
-                Tag::Colony => "colony",
-                Tag::Commended => "commended",
-                Tag::Communist => "communist",
-                Tag::Condemned => "condemned",
-                Tag::Conservative => "conservative",
-                Tag::Cyberpunk => "cyberpunk",
-                Tag::Defender => "defender",
-                Tag::Democratic => "democratic",
-                Tag::EcoFriendly => "eco-friendly",
-                Tag::Egalitarian => "egalitarian",
-                Tag::EmbassyCollector => "embassy_collector",
-                Tag::Enormous => "enormous",
-                Tag::ForumSevener => "f7er",
-                Tag::FutureTechFasterThanLight => "ft_ftl",
-                Tag::FutureTechFasterThanLightInhibited => "ft_ftli",
-                Tag::FutureTechSlowerThanLight => "ft_stl",
-                Tag::Fandom => "fandom",
-                Tag::FantasyTech => "fantasy_tech",
-                Tag::Fascist => "fascist",
-                Tag::Featured => "featured",
-                Tag::Feeder => "feeder",
-                Tag::Feminist => "feminist",
-                Tag::Founderless => "founderless",
-                Tag::FreeTrade => "free_trade",
-                Tag::Frontier => "frontier",
-                Tag::FutureTech => "future_tech",
-                Tag::GamePlayer => "game_player",
-                Tag::Gargantuan => "gargantuan",
-                Tag::GeneralAssembly => "general_assembly",
-                Tag::Generalite => "generalite",
-                Tag::Governorless => "governorless",
-                Tag::HumanOnly => "human-only",
-                Tag::Imperialist => "imperialist",
-                Tag::Independent => "independent",
-                Tag::Industrial => "industrial",
-                Tag::Injuncted => "injuncted",
-                Tag::InternationalFederalist => "international_federalist",
-                Tag::Invader => "invader",
-                Tag::Isolationist => "isolationist",
-                Tag::IssuesPlayer => "issues_player",
-                Tag::JumpPoint => "jump_point",
-                Tag::Lgbt => "lgbt",
-                Tag::Large => "large",
-                Tag::Liberal => "liberal",
-                Tag::Liberated => "liberated",
-                Tag::Libertarian => "libertarian",
-                Tag::Magical => "magical",
-                Tag::Map => "map",
-                Tag::Medium => "medium",
-                Tag::Mercenary => "mercenary",
-                Tag::Miniscule => "minuscule",
-                Tag::ModernTech => "modern_tech",
-                Tag::Monarchist => "monarchist",
-                Tag::MultiSpecies => "multi-species",
-                Tag::NationalSovereigntist => "national_sovereigntist",
-                Tag::Neutral => "neutral",
-                Tag::New => "new",
-                Tag::NonEnglish => "non-english",
-                Tag::OffsiteChat => "offsite_chat",
-                Tag::OffsiteForums => "offsite_forums",
-                Tag::OuterSpace => "outer_space",
-                Tag::PortalToTheMultiverse => "p2tm",
-                Tag::Pacifist => "pacifist",
-                Tag::Parody => "parody",
-                Tag::Password => "password",
-                Tag::PastTech => "past_tech",
-                Tag::Patriarchal => "patriarchal",
-                Tag::PostApocalyptic => "post_apocalyptic",
-                Tag::PostModernTech => "post-modern_tech",
-                Tag::PuppetStorage => "puppet_storage",
-                Tag::RegionalGovernment => "regional_government",
-                Tag::Religious => "religious",
-                Tag::Restorer => "restorer",
-                Tag::RolePlayer => "role_player",
-                Tag::SecurityCouncil => "security_council",
-                Tag::Serious => "serious",
-                Tag::Silly => "silly",
-                Tag::Sinker => "sinker",
-                Tag::Small => "small",
-                Tag::Snarky => "snarky",
-                Tag::Social => "social",
-                Tag::Socialist => "socialist",
-                Tag::Sports => "sports",
-                Tag::Steampunk => "steampunk",
-                Tag::Surreal => "surreal",
-                Tag::Theocratic => "theocratic",
-                Tag::Totalitarian => "totalitarian",
-                Tag::TradingCards => "trading_cards",
-                Tag::VideoGame => "video_game",
-                Tag::Warzone => "warzone",
-                Tag::WorldAssembly => "world_assembly",
-            }
-        )
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Tag {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+/// Names that are not recognized become [`Tag::Other`], as with [`FromStr`].
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Tag {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+        Ok(Tag::iter()
+            .find(|t| t.as_str() == name)
+            .unwrap_or_else(|| Tag::Other(name.into_owned())))
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for Tag {
+    fn inline_schema() -> bool {
+        true
+    }
+
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        String::schema_name()
+    }
+
+    fn json_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        String::json_schema(generator)
     }
 }
@@ -0,0 +1,78 @@
+//! The request for sending a telegram through the API.
+//!
+//! A telegram is sent from a template:
+//! a telegram written in-game and addressed to `tag:api`,
+//! which the game answers with its ID and secret key.
+//! Sending also needs a client key, which is requested from the moderators.
+
+#[cfg(feature = "parsers")]
+use crate::shards::NSRequestParse;
+use crate::shards::{api_url, require_name, NSRequest, RequestBuildError};
+use std::borrow::Cow;
+use url::Url;
+
+/// A request to send a telegram template to a nation.
+///
+/// Send it with [`Client::send_telegram`](crate::client::Client::send_telegram),
+/// which waits for the telegram rate limit as well as the API one.
+/// ```rust
+/// # use crustacean_states::shards::{NSRequest, telegram::TelegramRequest};
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// let request = TelegramRequest::new("client_key", "12345", "secret_key", "Testlandia");
+/// assert_eq!(
+///     request.as_url()?.as_str(),
+///     "https://www.nationstates.net/cgi-bin/api.cgi?a=sendTG&client=client_key&tgid=12345&key=secret_key&to=Testlandia&v=12",
+/// );
+/// # Ok(())
+/// # }
+/// # test().unwrap();
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct TelegramRequest<'a> {
+    client_key: Cow<'a, str>,
+    tgid: Cow<'a, str>,
+    secret_key: Cow<'a, str>,
+    to: Cow<'a, str>,
+}
+
+impl<'a> TelegramRequest<'a> {
+    /// Creates a request to send the template with the ID `tgid` and the key `secret_key`
+    /// to the nation `to`, using the client key `client_key`.
+    pub fn new(
+        client_key: impl Into<Cow<'a, str>>,
+        tgid: impl Into<Cow<'a, str>>,
+        secret_key: impl Into<Cow<'a, str>>,
+        to: impl Into<Cow<'a, str>>,
+    ) -> Self {
+        Self {
+            client_key: client_key.into(),
+            tgid: tgid.into(),
+            secret_key: secret_key.into(),
+            to: to.into(),
+        }
+    }
+
+    /// Sets the nation the telegram is sent to.
+    pub fn to(&mut self, nation: impl Into<Cow<'a, str>>) -> &mut Self {
+        self.to = nation.into();
+        self
+    }
+}
+
+impl<'a> NSRequest for TelegramRequest<'a> {
+    fn as_url(&self) -> Result<Url, RequestBuildError> {
+        Ok(api_url([
+            ("a", "sendTG"),
+            ("client", require_name("client", Some(&self.client_key))?),
+            ("tgid", require_name("tgid", Some(&self.tgid))?),
+            ("key", require_name("key", Some(&self.secret_key))?),
+            ("to", require_name("to", Some(&self.to))?),
+        ])?)
+    }
+}
+
+#[cfg(feature = "parsers")]
+impl<'a> NSRequestParse for TelegramRequest<'a> {
+    /// The API answers with `queued` if the telegram was accepted.
+    type Response = String;
+}
@@ -0,0 +1,454 @@
+//! Requests whose responses only contain the shards that were asked for.
+//!
+//! A [`Nation`] has a field for every shard, so each field is an `Option`.
+//! When the shards are known ahead of time,
+//! a [`TypedNationRequest`] names them in its type instead,
+//! and its response holds exactly those fields:
+//! ```rust
+//! # use crustacean_states::client::Client;
+//! # use crustacean_states::shards::typed::{Animal, Population, TypedNationRequest};
+//! # async fn test() -> Result<(), Box<dyn std::error::Error>> {
+//! # let client = Client::new("");
+//! let request = TypedNationRequest::<(Animal, Population)>::new("Aramos");
+//! let (animal, population) = client.get_parsed(request).await?.into_inner();
+//! println!("{population} million {animal}s");
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! With the `derive` feature, a struct can be used instead of a tuple
+//! by deriving [`NationView`] for it and sending a [`NationViewRequest`].
+//! Each field must be named after a field of [`Nation`]:
+//! ```rust
+//! # #[cfg(feature = "derive")]
+//! # mod view {
+//! use crustacean_states::shards::typed::NationView;
+//!
+//! #[derive(NationView)]
+//! struct MyNationView {
+//!     name: String,
+//!     population: u32,
+//!     flag: String,
+//! }
+//! # }
+//! ```
+
+use crate::{
+    models::name::NameList,
+    parsers::{
+        happenings::Event,
+        nation::{
+            self, BannerId, Freedoms, Government, IntoNationError, Nation, WAStatus, WAVoteState,
+        },
+        DefaultOrCustom, MaybeRelativeTime, NSResponse,
+    },
+    shards::{
+        nation::{PublicNationRequest, PublicNationShard},
+        NSRequest, NSRequestParse, RequestBuildError,
+    },
+};
+use std::{borrow::Cow, marker::PhantomData};
+use url::Url;
+
+/// A field of a [`Nation`] that can be requested on its own.
+pub trait NationField {
+    /// The type of the field, once it has been requested.
+    type Output;
+
+    /// The shard that fills in the field.
+    fn shard() -> PublicNationShard<'static>;
+
+    /// Takes the field out of a parsed nation.
+    /// Fails if the field was not filled in.
+    fn take(nation: &mut Nation) -> Result<Self::Output, IntoNationError>;
+}
+
+/// A struct whose fields are all [`NationField`]s.
+///
+/// Usually derived with `#[derive(NationView)]` (requires the `derive` feature).
+pub trait NationView: Sized {
+    /// The shards that fill in the fields.
+    fn shards() -> Vec<PublicNationShard<'static>>;
+
+    /// Takes the fields out of a parsed nation.
+    fn from_nation(nation: &mut Nation) -> Result<Self, IntoNationError>;
+}
+
+#[cfg(feature = "derive")]
+pub use crustacean_states_derive::NationView;
+
+/// A group of [`NationField`]s, written as a tuple.
+pub trait NationFields {
+    /// The types of the fields, as a tuple in the same order.
+    type Output;
+
+    /// The shards that fill in the fields.
+    fn shards() -> Vec<PublicNationShard<'static>>;
+
+    /// Takes the fields out of a parsed nation.
+    fn take(nation: &mut Nation) -> Result<Self::Output, IntoNationError>;
+}
+
+macro_rules! nation_fields {
+    ($($(#[$meta:meta])* $marker:ident => $field:ident: $output:ty),* $(,)?) => {
+        $(
+            $(#[$meta])*
+            #[derive(Clone, Copy, Debug)]
+            pub struct $marker;
+
+            impl NationField for $marker {
+                type Output = $output;
+
+                fn shard() -> PublicNationShard<'static> {
+                    PublicNationShard::$marker
+                }
+
+                fn take(nation: &mut Nation) -> Result<Self::Output, IntoNationError> {
+                    nation
+                        .$field
+                        .take()
+                        .ok_or(IntoNationError::MissingShardError(stringify!($marker)))
+                }
+            }
+        )*
+
+        /// The marker for each field of [`Nation`], named after the field,
+        /// so that `#[derive(NationView)]` can find it without a list of its own.
+        #[doc(hidden)]
+        #[allow(non_camel_case_types)]
+        pub mod by_field {
+            $(pub type $field = super::$marker;)*
+        }
+    };
+}
+
+nation_fields! {
+    /// See [`Nation::admirable`].
+    Admirable => admirable: String,
+    /// See [`Nation::animal`].
+    Animal => animal: String,
+    /// See [`Nation::issues_answered`].
+    Answered => issues_answered: u32,
+    /// See [`Nation::banner`].
+    Banner => banner: BannerId,
+    /// See [`Nation::capital`].
+    Capital => capital: DefaultOrCustom,
+    /// See [`Nation::category`].
+    Category => category: String,
+    /// See [`Nation::crime`].
+    Crime => crime: String,
+    /// See [`Nation::currency`].
+    Currency => currency: String,
+    /// See [`Nation::dbid`].
+    DbId => dbid: u32,
+    /// See [`Nation::dispatches`].
+    Dispatches => dispatches: u16,
+    /// See [`Nation::endorsements`].
+    Endorsements => endorsements: NameList,
+    /// See [`Nation::factbooks`].
+    Factbooks => factbooks: u16,
+    /// See [`Nation::first_login`].
+    FirstLogin => first_login: u64,
+    /// See [`Nation::flag`].
+    Flag => flag: String,
+    /// See [`Nation::founded`].
+    Founded => founded: MaybeRelativeTime,
+    /// See [`Nation::freedom`].
+    Freedom => freedom: Freedoms,
+    /// See [`Nation::freedom_scores`].
+    FreedomScores => freedom_scores: nation::FreedomScores,
+    /// See [`Nation::full_name`].
+    FullName => full_name: String,
+    /// See [`Nation::ga_vote`].
+    /// Request it with [`WA`] to know whether an undecided nation can vote,
+    /// or use [`NationWaInfoRequest`].
+    GAVote => ga_vote: WAVoteState,
+    /// See [`Nation::gdp`].
+    Gdp => gdp: u64,
+    /// See [`Nation::government`].
+    Govt => government: Government,
+    /// See [`Nation::government_priority`].
+    GovtPriority => government_priority: String,
+    /// See [`Nation::happenings`].
+    Happenings => happenings: Vec<Event>,
+    /// See [`Nation::income`].
+    Income => income: u32,
+    /// See [`Nation::influence`].
+    Influence => influence: String,
+    /// See [`Nation::last_activity`].
+    LastActivity => last_activity: String,
+    /// See [`Nation::last_login`].
+    LastLogin => last_login: u64,
+    /// See [`Nation::leader`].
+    Leader => leader: DefaultOrCustom,
+    /// See [`Nation::major_industry`].
+    MajorIndustry => major_industry: String,
+    /// See [`Nation::motto`].
+    Motto => motto: String,
+    /// See [`Nation::population`].
+    Population => population: u32,
+    /// See [`Nation::public_sector`].
+    PublicSector => public_sector: f64,
+    /// See [`Nation::region`].
+    Region => region: String,
+    /// See [`Nation::religion`].
+    Religion => religion: DefaultOrCustom,
+    /// See [`Nation::sc_vote`].
+    /// Request it with [`WA`] to know whether an undecided nation can vote,
+    /// or use [`NationWaInfoRequest`].
+    SCVote => sc_vote: WAVoteState,
+    /// See [`Nation::sectors`].
+    Sectors => sectors: nation::Sectors,
+    /// See [`Nation::tax`].
+    Tax => tax: f64,
+    /// See [`Nation::kind`].
+    Type => kind: String,
+    /// See [`Nation::wa_status`].
+    WA => wa_status: WAStatus,
+}
+
+macro_rules! impl_nation_fields {
+    ($($field:ident),+) => {
+        impl<$($field: NationField),+> NationFields for ($($field,)+) {
+            type Output = ($($field::Output,)+);
+
+            fn shards() -> Vec<PublicNationShard<'static>> {
+                vec![$($field::shard()),+]
+            }
+
+            fn take(nation: &mut Nation) -> Result<Self::Output, IntoNationError> {
+                Ok(($($field::take(nation)?,)+))
+            }
+        }
+    };
+}
+
+impl_nation_fields!(A);
+impl_nation_fields!(A, B);
+impl_nation_fields!(A, B, C);
+impl_nation_fields!(A, B, C, D);
+impl_nation_fields!(A, B, C, D, E);
+impl_nation_fields!(A, B, C, D, E, F);
+impl_nation_fields!(A, B, C, D, E, F, G);
+impl_nation_fields!(A, B, C, D, E, F, G, H);
+
+/// A nation request whose shards are the [`NationFields`] `F`.
+#[derive(Clone, Debug)]
+pub struct TypedNationRequest<'a, F> {
+    nation: Cow<'a, str>,
+    fields: PhantomData<F>,
+}
+
+impl<'a, F: NationFields> TypedNationRequest<'a, F> {
+    /// Creates a new request of the provided nation.
+    pub fn new(nation: impl Into<Cow<'a, str>>) -> Self {
+        Self {
+            nation: nation.into(),
+            fields: PhantomData,
+        }
+    }
+}
+
+impl<'a, F: NationFields> NSRequest for TypedNationRequest<'a, F> {
+    fn as_url(&self) -> Result<Url, RequestBuildError> {
+        PublicNationRequest::new_with_shards(self.nation.as_ref(), F::shards()).as_url()
+    }
+}
+
+impl<'a, F: NationFields> NSRequestParse for TypedNationRequest<'a, F> {
+    type Response = TypedNation<F>;
+}
+
+/// A nation request whose shards are the fields of the [`NationView`] `V`.
+#[derive(Clone, Debug)]
+pub struct NationViewRequest<'a, V> {
+    nation: Cow<'a, str>,
+    view: PhantomData<V>,
+}
+
+impl<'a, V: NationView> NationViewRequest<'a, V> {
+    /// Creates a new request of the provided nation.
+    pub fn new(nation: impl Into<Cow<'a, str>>) -> Self {
+        Self {
+            nation: nation.into(),
+            view: PhantomData,
+        }
+    }
+}
+
+impl<'a, V: NationView + NSResponse> NSRequest for NationViewRequest<'a, V> {
+    fn as_url(&self) -> Result<Url, RequestBuildError> {
+        PublicNationRequest::new_with_shards(self.nation.as_ref(), V::shards()).as_url()
+    }
+}
+
+impl<'a, V: NationView + NSResponse> NSRequestParse for NationViewRequest<'a, V> {
+    type Response = V;
+}
+
+/// A request for a nation's World Assembly status and its votes in both councils.
+///
+/// The votes are always requested together with the status,
+/// so an undecided vote is never [`WAVoteState::Unknown`].
+///
+/// ## Example
+/// ```rust
+/// # use crustacean_states::client::Client;
+/// # use crustacean_states::parsers::nation::WAVoteState;
+/// # use crustacean_states::shards::typed::NationWaInfoRequest;
+/// # async fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// # let client = Client::new("");
+/// let info = client.get_parsed(NationWaInfoRequest::new("Testlandia")).await?;
+/// if info.ga == WAVoteState::Undecided {
+///     println!("{} has not voted in the General Assembly yet", info.name);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub type NationWaInfoRequest<'a> = NationViewRequest<'a, NationWaInfo>;
+
+/// A nation's World Assembly status and its votes in both councils.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct NationWaInfo {
+    /// The name of the nation.
+    pub name: String,
+    /// Whether the nation is in the World Assembly, and whether it is a delegate.
+    pub status: WAStatus,
+    /// The nation's vote in the General Assembly.
+    pub ga: WAVoteState,
+    /// The nation's vote in the Security Council.
+    pub sc: WAVoteState,
+}
+
+impl NationView for NationWaInfo {
+    fn shards() -> Vec<PublicNationShard<'static>> {
+        <(WA, GAVote, SCVote)>::shards()
+    }
+
+    fn from_nation(nation: &mut Nation) -> Result<Self, IntoNationError> {
+        let (status, ga, sc) = <(WA, GAVote, SCVote)>::take(nation)?;
+        Ok(Self {
+            name: nation.name.clone(),
+            status,
+            ga,
+            sc,
+        })
+    }
+}
+
+impl NSResponse for NationWaInfo {
+    type Error = IntoNationError;
+
+    fn from_xml(xml: &str) -> Result<Self, Self::Error> {
+        Self::from_nation(&mut Nation::from_xml(xml)?)
+    }
+}
+
+/// The response to a [`TypedNationRequest`].
+pub struct TypedNation<F: NationFields> {
+    /// The name of the nation.
+    pub name: String,
+    fields: F::Output,
+}
+
+impl<F: NationFields> TypedNation<F> {
+    /// The requested fields, in the order they were requested.
+    pub fn fields(&self) -> &F::Output {
+        &self.fields
+    }
+
+    /// Takes the requested fields, in the order they were requested.
+    pub fn into_inner(self) -> F::Output {
+        self.fields
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<F: NationFields> serde::Serialize for TypedNation<F>
+where
+    F::Output: serde::Serialize,
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+        let mut state = serializer.serialize_struct("TypedNation", 2)?;
+        state.serialize_field("name", &self.name)?;
+        state.serialize_field("fields", &self.fields)?;
+        state.end()
+    }
+}
+
+impl<F: NationFields> NSResponse for TypedNation<F> {
+    type Error = IntoNationError;
+
+    fn from_xml(xml: &str) -> Result<Self, Self::Error> {
+        let mut nation = Nation::from_xml(xml)?;
+        let fields = F::take(&mut nation)?;
+        Ok(Self {
+            name: nation.name,
+            fields,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn typed_nation() {
+        let xml =
+            r#"<NATION id="aramos"><ANIMAL>dolphin</ANIMAL><POPULATION>42</POPULATION></NATION>"#;
+        let nation = TypedNation::<(Animal, Population)>::from_xml(xml).unwrap();
+        assert_eq!(nation.name, "Aramos");
+        assert_eq!(nation.into_inner(), (String::from("dolphin"), 42));
+        assert!(matches!(
+            TypedNation::<(Animal, Motto)>::from_xml(xml),
+            Err(IntoNationError::MissingShardError("Motto"))
+        ));
+    }
+
+    #[test]
+    fn nation_wa_info() {
+        assert_eq!(
+            NationWaInfoRequest::new("Testlandia")
+                .as_url()
+                .unwrap()
+                .query(),
+            Some("nation=Testlandia&q=wa%2Bgavote%2Bscvote&v=12")
+        );
+        let xml = r#"<NATION id="testlandia"><UNSTATUS>WA Member</UNSTATUS><GAVOTE>UNDECIDED</GAVOTE><SCVOTE>FOR</SCVOTE></NATION>"#;
+        let info = NationWaInfo::from_xml(xml).unwrap();
+        assert_eq!(info.status, WAStatus::Member);
+        assert_eq!(info.ga, WAVoteState::Undecided);
+        assert_eq!(info.sc, WAVoteState::For);
+    }
+
+    #[cfg(feature = "derive")]
+    #[test]
+    fn derived_view() {
+        #[derive(NationView)]
+        struct View {
+            name: String,
+            animal: String,
+            population: u64,
+            ga_vote: WAVoteState,
+        }
+
+        assert_eq!(
+            NationViewRequest::<View>::new("Aramos")
+                .as_url()
+                .unwrap()
+                .query(),
+            Some("nation=Aramos&q=animal%2Bpopulation%2Bgavote&v=12")
+        );
+        let xml = r#"<NATION id="aramos"><ANIMAL>dolphin</ANIMAL><POPULATION>42</POPULATION><GAVOTE>AGAINST</GAVOTE></NATION>"#;
+        let view = View::from_xml(xml).unwrap();
+        assert_eq!(
+            (view.name.as_str(), view.animal.as_str(), view.population),
+            ("Aramos", "dolphin", 42)
+        );
+        assert_eq!(view.ga_vote, WAVoteState::Against);
+    }
+}
@@ -1,17 +1,24 @@
 //! For World Assembly shard requests.
 
-use crate::shards::{NSRequest, Params, BASE_URL};
+use crate::shards::{
+    api_url, join_lowercase, write_lowercase, NSRequest, Params, RawShard, RequestBuildError,
+};
+#[cfg(feature = "parsers")]
+use crate::{parsers::wa::WorldAssembly, shards::NSRequestParse};
 use itertools::Itertools;
 use std::{
-    fmt::{Display, Formatter},
-    string::ToString,
+    borrow::Cow,
+    fmt::{Display, Formatter, Write},
+    iter,
 };
-use strum::{AsRefStr, Display};
+use strum::{AsRefStr, Display, IntoStaticStr};
 use url::Url;
 
 /// One of the two World Assembly chambers (or "councils").
 #[repr(u8)]
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum WACouncil {
     /// The General Assembly.
     ///
@@ -40,9 +47,13 @@ pub enum WAShard<'a> {
     CouncilInfo(WACouncilShard),
     /// Information about a resolution in a World Assembly council.
     /// Request more information with [`ResolutionShard`]s.
-    CurrentResolution(&'a [ResolutionShard]),
-    /// Information about a previous resolution.
+    CurrentResolution(Cow<'a, [ResolutionShard]>),
+    /// Information about a previous resolution, by its number in the council,
+    /// including its final vote counts and when it was implemented.
     PreviousResolution(u16),
+    /// A shard that this crate does not support yet, sent as it is given.
+    /// Its response is not parsed.
+    Raw(RawShard<'a>),
 }
 
 impl<'a> From<WAGlobalShard> for WAShard<'a> {
@@ -59,7 +70,13 @@ impl<'a> From<WACouncilShard> for WAShard<'a> {
 
 impl<'a> From<&'a [ResolutionShard]> for WAShard<'a> {
     fn from(value: &'a [ResolutionShard]) -> Self {
-        WAShard::CurrentResolution(value)
+        WAShard::CurrentResolution(Cow::Borrowed(value))
+    }
+}
+
+impl<'a> From<Vec<ResolutionShard>> for WAShard<'a> {
+    fn from(value: Vec<ResolutionShard>) -> Self {
+        WAShard::CurrentResolution(Cow::Owned(value))
     }
 }
 
@@ -71,24 +88,24 @@ impl<'a> From<u16> for WAShard<'a> {
 
 impl<'a> Display for WAShard<'a> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            match self {
-                WAShard::GlobalInfo(g) => g.to_string(),
-                WAShard::CouncilInfo(c) => c.to_string(),
-                WAShard::CurrentResolution(a) => a
-                    .iter()
-                    .fold(String::from("resolution"), |acc, s| format!("{acc}+{s:?}")),
-                WAShard::PreviousResolution(_) => String::from("resolution"),
+        match self {
+            WAShard::GlobalInfo(g) => write_lowercase(f, g.into()),
+            WAShard::CouncilInfo(c) => write_lowercase(f, c.into()),
+            WAShard::CurrentResolution(a) => {
+                f.write_str("resolution")?;
+                a.iter().try_for_each(|s| {
+                    f.write_char('+')?;
+                    write_lowercase(f, s.into())
+                })
             }
-            .to_ascii_lowercase()
-        )
+            WAShard::PreviousResolution(_) => f.write_str("resolution"),
+            WAShard::Raw(raw) => write_lowercase(f, &raw.name),
+        }
     }
 }
 
 /// Information about the World Assembly as a whole.
-#[derive(Clone, Debug, Display)]
+#[derive(Clone, Debug, Display, IntoStaticStr)]
 pub enum WAGlobalShard {
     /// The number of nations in the World Assembly.
     NumNations,
@@ -101,7 +118,7 @@ pub enum WAGlobalShard {
 }
 
 /// Information for the World Assembly that is specific to a council.
-#[derive(Clone, Debug, Display)]
+#[derive(Clone, Debug, Display, IntoStaticStr)]
 pub enum WACouncilShard {
     /// A shard that returns `[Event]`s in the World Assembly.
     ///
@@ -114,7 +131,7 @@ pub enum WACouncilShard {
 }
 
 /// Information about the current at-vote resolution.
-#[derive(Clone, Debug, Display)]
+#[derive(Clone, Debug, Display, IntoStaticStr)]
 pub enum ResolutionShard {
     /// Lists every nation voting for and against the resolution.
     Voters,
@@ -138,20 +155,23 @@ pub enum WARequest<'a> {
     Council(CouncilRequest<'a>),
     /// Information about the at-vote resolution.
     AtVoteResolution(ResolutionRequest<'a>),
-    /// Information about a previous resolution.
+    /// Information about a previous resolution,
+    /// including its final vote counts and when it was implemented.
     PastResolution(ResolutionArchiveRequest),
 }
 
 /// Request information about the WA as a whole.
 #[derive(Clone, Debug)]
 pub struct GlobalRequest<'a> {
-    shards: &'a [WAGlobalShard],
+    shards: Cow<'a, [WAGlobalShard]>,
 }
 
 impl<'a> GlobalRequest<'a> {
     /// Create a new request about the WA as a whole.
-    pub fn new(shards: &'a [WAGlobalShard]) -> Self {
-        Self { shards }
+    pub fn new(shards: impl Into<Cow<'a, [WAGlobalShard]>>) -> Self {
+        Self {
+            shards: shards.into(),
+        }
     }
 }
 
@@ -159,13 +179,16 @@ impl<'a> GlobalRequest<'a> {
 #[derive(Clone, Debug)]
 pub struct CouncilRequest<'a> {
     council: WACouncil,
-    shards: &'a [WAShard<'a>],
+    shards: Cow<'a, [WAShard<'a>]>,
 }
 
 impl<'a> CouncilRequest<'a> {
     /// Create a request about a WA council.
-    pub fn new(council: WACouncil, shards: &'a [WAShard<'a>]) -> Self {
-        Self { council, shards }
+    pub fn new(council: WACouncil, shards: impl Into<Cow<'a, [WAShard<'a>]>>) -> Self {
+        Self {
+            council,
+            shards: shards.into(),
+        }
     }
 }
 
@@ -173,17 +196,31 @@ impl<'a> CouncilRequest<'a> {
 #[derive(Clone, Debug)]
 pub struct ResolutionRequest<'a> {
     council: WACouncil,
-    shards: &'a [ResolutionShard],
+    shards: Cow<'a, [ResolutionShard]>,
 }
 
 impl<'a> ResolutionRequest<'a> {
     /// Create a request about the current at-vote resolution.
-    pub fn new(council: WACouncil, shards: &'a [ResolutionShard]) -> Self {
-        Self { council, shards }
+    pub fn new(council: WACouncil, shards: impl Into<Cow<'a, [ResolutionShard]>>) -> Self {
+        Self {
+            council,
+            shards: shards.into(),
+        }
     }
 }
 
 /// Request information about previous resolutions.
+///
+/// ## Example
+/// ```rust
+/// # use crustacean_states::shards::NSRequest;
+/// # use crustacean_states::shards::wa::{ResolutionArchiveRequest, WACouncil, WARequest};
+/// let request = WARequest::from(ResolutionArchiveRequest::new(WACouncil::GeneralAssembly, 2));
+/// assert_eq!(
+///     request.as_url().unwrap().query(),
+///     Some("wa=1&id=2&q=resolution&v=12")
+/// );
+/// ```
 #[derive(Clone, Debug)]
 pub struct ResolutionArchiveRequest {
     council: WACouncil,
@@ -192,53 +229,87 @@ pub struct ResolutionArchiveRequest {
 
 impl ResolutionArchiveRequest {
     /// Create a request about previous resolutions.
+    /// The `id` is the number of the resolution in its council, starting from 1.
     pub fn new(council: WACouncil, id: u16) -> Self {
         Self { council, id }
     }
 }
 
+impl<'a> From<ResolutionArchiveRequest> for WARequest<'a> {
+    fn from(value: ResolutionArchiveRequest) -> Self {
+        WARequest::PastResolution(value)
+    }
+}
+
 impl<'a> NSRequest for WARequest<'a> {
-    fn as_url(&self) -> Url {
-        Url::parse_with_params(
-            BASE_URL,
-            Params::default()
-                .insert(
-                    "wa",
-                    match self {
-                        WARequest::Global(_) => None,
-                        WARequest::Council(CouncilRequest { council, .. }) => Some(council.clone()),
-                        WARequest::AtVoteResolution(ResolutionRequest { council, .. }) => {
-                            Some(council.clone())
-                        }
-                        WARequest::PastResolution(ResolutionArchiveRequest { council, .. }) => {
-                            Some(council.clone())
-                        }
+    fn as_url(&self) -> Result<Url, RequestBuildError> {
+        let empty = match self {
+            WARequest::Global(GlobalRequest { shards }) => shards.is_empty(),
+            WARequest::Council(CouncilRequest { shards, .. }) => shards.is_empty(),
+            WARequest::AtVoteResolution(_) | WARequest::PastResolution(_) => false,
+        };
+        if empty {
+            return Err(RequestBuildError::EmptyRequest);
+        }
+        let mut params = Params::default();
+        params
+            .insert(
+                "wa",
+                match self {
+                    WARequest::Global(_) => None,
+                    WARequest::Council(CouncilRequest { council, .. }) => Some(council.clone()),
+                    WARequest::AtVoteResolution(ResolutionRequest { council, .. }) => {
+                        Some(council.clone())
                     }
-                    .unwrap_or_default() as u8,
-                )
-                .insert_on(
-                    "id",
-                    &if let WARequest::PastResolution(ResolutionArchiveRequest { id, .. }) = self {
-                        Some(id)
-                    } else {
-                        None
-                    },
-                )
-                .insert(
-                    "q",
-                    match self {
-                        WARequest::Global(GlobalRequest { shards }) => shards.iter().join("+"),
-                        WARequest::Council(CouncilRequest { shards, .. }) => {
-                            shards.iter().join("+")
-                        }
-                        WARequest::AtVoteResolution(ResolutionRequest { shards, .. }) => {
-                            format!("resolution+{}", shards.iter().join("+"))
-                        }
-                        WARequest::PastResolution(_) => String::from("resolution"),
+                    WARequest::PastResolution(ResolutionArchiveRequest { council, .. }) => {
+                        Some(council.clone())
                     }
-                    .to_ascii_lowercase(),
-                ),
-        )
-        .unwrap()
+                }
+                .unwrap_or_default() as u8,
+            )
+            .insert_on(
+                "id",
+                &match self {
+                    WARequest::PastResolution(ResolutionArchiveRequest { id, .. }) => Some(*id),
+                    WARequest::Council(CouncilRequest { shards, .. }) => {
+                        shards.iter().find_map(|s| match s {
+                            WAShard::PreviousResolution(id) => Some(*id),
+                            _ => None,
+                        })
+                    }
+                    _ => None,
+                },
+            )
+            .insert_str(
+                "q",
+                match self {
+                    WARequest::Global(GlobalRequest { shards }) => {
+                        join_lowercase(shards.iter().map(<&'static str>::from), '+')
+                    }
+                    // Council shards are already written in lowercase.
+                    WARequest::Council(CouncilRequest { shards, .. }) => shards.iter().join("+"),
+                    WARequest::AtVoteResolution(ResolutionRequest { shards, .. }) => {
+                        join_lowercase(
+                            iter::once("resolution").chain(shards.iter().map(<&'static str>::from)),
+                            '+',
+                        )
+                    }
+                    WARequest::PastResolution(_) => String::from("resolution"),
+                },
+            );
+        if let WARequest::Council(CouncilRequest { shards, .. }) = self {
+            shards.iter().for_each(|s| {
+                if let WAShard::Raw(raw) = s {
+                    params.insert_raw(raw);
+                }
+            });
+        }
+
+        Ok(api_url(params.check()?)?)
     }
 }
+
+#[cfg(feature = "parsers")]
+impl<'a> NSRequestParse for WARequest<'a> {
+    type Response = WorldAssembly;
+}
@@ -2,21 +2,29 @@
 
 use crate::{
     impl_display_as_debug,
-    models::dispatch::DispatchCategory,
-    parsers::nation::BannerId,
+    models::{banner::BannerId, dispatch::DispatchCategory},
     shards::{
+        api_url, join_lowercase,
         region::Tag,
+        split_list,
         world::HappeningsViewType::{Nation, Region},
-        CensusRanksShard, CensusShard, NSRequest, Params, BASE_URL,
+        write_lowercase, CachedUrl, CensusRanksShard, CensusShard, FromUrlError, NSRequest, Params,
+        RawShard, RequestBuildError, RequestUrl, ShardSet,
     },
 };
+#[cfg(feature = "parsers")]
+use crate::{parsers::world::World, shards::NSRequestParse};
 use itertools::Itertools;
-use std::fmt::{Display, Formatter};
-use strum::AsRefStr;
+use std::{
+    borrow::Cow,
+    fmt::{Display, Formatter, Write},
+};
+use strum::{AsRefStr, EnumString, IntoStaticStr};
 use url::Url;
 
 /// A request for the wide world of NationStates.
-#[derive(AsRefStr, Clone, Debug, PartialEq)]
+#[derive(AsRefStr, Clone, Debug, EnumString, IntoStaticStr, PartialEq)]
+#[strum(ascii_case_insensitive)]
 pub enum WorldShard<'a> {
     /// Provides the name of a banner given its ID, as well as the necessary conditions to unlock it.
     Banner(Vec<BannerId>),
@@ -48,7 +56,7 @@ pub enum WorldShard<'a> {
     /// Lists 20 dispatches. The fields can provide more control.
     DispatchList {
         /// If `Some(nation)`, then search only for dispatches written by `nation`.
-        author: Option<&'a str>,
+        author: Option<Cow<'a, str>>,
         /// If `Some(category)`, then search only for dispatches that have a certain category.
         category: Option<DispatchCategory>,
         /// If `Some(sort)`, then sort, according to the dispatch sorting rules.
@@ -114,12 +122,15 @@ pub enum WorldShard<'a> {
     RegionsByTag(Vec<IncludeOrExcludeTag>),
     /// The number of manual, mass, and API telegrams in the queue.
     TGQueue,
+    /// A shard that this crate does not support yet, sent as it is given.
+    /// Its response is not parsed.
+    Raw(RawShard<'a>),
 }
 
 /// A request of the world API.
 /// If you're going to make a request, start here!
 #[derive(Clone, Debug, Default, PartialEq)]
-pub struct WorldRequest<'a>(Vec<WorldShard<'a>>);
+pub struct WorldRequest<'a>(ShardSet<WorldShard<'a>>, CachedUrl);
 
 impl<'a> WorldRequest<'a> {
     /// Make a new [`WorldRequest`].
@@ -132,20 +143,109 @@ impl<'a> WorldRequest<'a> {
     where
         T: AsRef<[WorldShard<'a>]>,
     {
-        Self(shards.as_ref().to_vec())
+        Self(ShardSet::from(shards.as_ref()), CachedUrl::default())
     }
 
     /// Make an empty [`WorldRequest`].
     ///
     /// Please remember to actually modify this before you send it,
-    /// as you will almost definitely get a `400 Bad Request` error from the API.
+    /// as building the URL of an empty request fails with [`RequestBuildError::EmptyRequest`].
     ///
     /// NOTE!
     /// In 0.3, [`new_empty`](Self::new_empty)
     /// will become [`new`](Self::new)
     /// and [`new`](Self::new) will be replaced with an implementation of [`From`].
     pub fn new_empty() -> Self {
-        Self(vec![])
+        Self(ShardSet::new(), CachedUrl::default())
+    }
+
+    /// Rebuilds a request from an API URL.
+    ///
+    /// See [`RequestUrl`] for an example.
+    pub fn from_url(url: &'a RequestUrl) -> Result<Self, FromUrlError> {
+        let bad_param = |key: &str| FromUrlError::BadParam {
+            key: key.to_string(),
+            value: url.param(key).unwrap_or_default().to_string(),
+        };
+        let shards = url.shards(|shard| {
+            Ok(match shard {
+                WorldShard::Banner(_) => WorldShard::Banner(
+                    url.require("banner")?
+                        .split(',')
+                        .map(|b| BannerId::try_from(b.to_string()).map_err(|_| bad_param("banner")))
+                        .collect::<Result<_, _>>()?,
+                ),
+                WorldShard::Census(_) => WorldShard::Census(url.census()?),
+                WorldShard::CensusDesc(_) => WorldShard::CensusDesc(url.parse_param("scale")?),
+                WorldShard::CensusName(_) => WorldShard::CensusName(url.parse_param("scale")?),
+                WorldShard::CensusRanks(_) => WorldShard::CensusRanks(url.census_ranks()?),
+                WorldShard::CensusScale(_) => WorldShard::CensusScale(url.parse_param("scale")?),
+                WorldShard::CensusTitle(_) => WorldShard::CensusTitle(url.parse_param("scale")?),
+                WorldShard::Dispatch(_) => WorldShard::Dispatch(
+                    url.parse_param("dispatchid")?
+                        .ok_or(FromUrlError::MissingParam("dispatchid"))?,
+                ),
+                WorldShard::DispatchList { .. } => WorldShard::DispatchList {
+                    author: url.param("dispatchauthor").map(Cow::Borrowed),
+                    category: url
+                        .param("dispatchcategory")
+                        .map(|c| c.parse().map_err(|_| bad_param("dispatchcategory")))
+                        .transpose()?,
+                    sort: url.parse_param("dispatchsort")?,
+                },
+                WorldShard::Happenings { .. } => WorldShard::Happenings {
+                    view: url
+                        .param("view")
+                        .map(|v| {
+                            let entities = |e: &str| e.split(',').map(String::from).collect();
+                            match v.split_once('.') {
+                                Some(("nation", e)) => Ok(Nation(entities(e))),
+                                Some(("region", e)) => Ok(Region(entities(e))),
+                                _ => Err(bad_param("view")),
+                            }
+                        })
+                        .transpose()?,
+                    filter: url
+                        .param("filter")
+                        .map(|f| {
+                            split_list(f)
+                                .map(|x| x.parse().map_err(|_| bad_param("filter")))
+                                .collect::<Result<_, _>>()
+                        })
+                        .transpose()?,
+                    limit: url.parse_param("limit")?,
+                    since_id: url.parse_param("sinceid")?,
+                    before_id: url.parse_param("beforeid")?,
+                    since_time: url.parse_param("sincetime")?,
+                    before_time: url.parse_param("beforetime")?,
+                },
+                WorldShard::Poll(_) => WorldShard::Poll(
+                    url.parse_param("pollid")?
+                        .ok_or(FromUrlError::MissingParam("pollid"))?,
+                ),
+                WorldShard::RegionsByTag(_) => WorldShard::RegionsByTag(
+                    url.require("tags")?
+                        .split(',')
+                        .map(|t| {
+                            let (include, name) = match t.strip_prefix('-') {
+                                Some(name) => (false, name),
+                                None => (true, t),
+                            };
+                            let Ok(tag) = name.parse::<Tag>();
+                            Ok(if include {
+                                IncludeOrExcludeTag::Include(tag)
+                            } else {
+                                IncludeOrExcludeTag::Exclude(tag)
+                            })
+                        })
+                        .collect::<Result<_, FromUrlError>>()?,
+                ),
+                // A raw shard's name is not known ahead of time.
+                WorldShard::Raw(_) => return Err(FromUrlError::UnknownShard(String::from("raw"))),
+                other => other,
+            })
+        })?;
+        Ok(Self(shards, CachedUrl::default()))
     }
 
     /// Modify shards using a function.
@@ -155,7 +255,7 @@ impl<'a> WorldRequest<'a> {
     /// # use crustacean_states::shards::world::{WorldShard, WorldRequest};
     /// let mut request_builder = WorldRequest::new(&[WorldShard::CensusId]);
     /// request_builder.shards(|s| {
-    ///     s.push(WorldShard::FeaturedRegion);
+    ///     s.insert(WorldShard::FeaturedRegion);
     /// });
     /// assert_eq!(
     ///     request_builder,
@@ -164,9 +264,10 @@ impl<'a> WorldRequest<'a> {
     /// ```
     pub fn shards<F>(&mut self, f: F) -> &mut Self
     where
-        F: FnOnce(&mut Vec<WorldShard<'a>>),
+        F: FnOnce(&mut ShardSet<WorldShard<'a>>),
     {
         f(&mut self.0);
+        self.1.clear();
         self
     }
     /// Add a shard.
@@ -188,7 +289,8 @@ impl<'a> WorldRequest<'a> {
     /// );
     /// ```
     pub fn add_shard(&mut self, shard: WorldShard<'a>) -> &mut Self {
-        self.0.push(shard);
+        self.0.insert(shard);
+        self.1.clear();
         self
     }
 
@@ -214,24 +316,41 @@ impl<'a> WorldRequest<'a> {
     /// ```
     pub fn add_shards<I: IntoIterator<Item = WorldShard<'a>>>(&mut self, shards: I) -> &mut Self {
         self.0.extend(shards);
+        self.1.clear();
         self
     }
 }
 
 impl<'a> NSRequest for WorldRequest<'a> {
+    /// The URL is built once, then reused until the request is changed.
+    fn as_url(&self) -> Result<Url, RequestBuildError> {
+        self.1.get_or_build(|| self.build_url())
+    }
+}
+
+#[cfg(feature = "parsers")]
+impl<'a> NSRequestParse for WorldRequest<'a> {
+    type Response = World;
+}
+
+impl<'a> WorldRequest<'a> {
     //noinspection SpellCheckingInspection
-    fn as_url(&self) -> Url {
-        let query = self
-            .0
-            .iter()
-            .map(|s| s.as_ref())
-            .join("+")
-            .to_ascii_lowercase();
+    fn build_url(&self) -> Result<Url, RequestBuildError> {
+        if self.0.is_empty() {
+            return Err(RequestBuildError::EmptyRequest);
+        }
+        let query = join_lowercase(
+            self.0.iter().map(|s| match s {
+                WorldShard::Raw(raw) => raw.name.as_ref(),
+                s => s.into(),
+            }),
+            '+',
+        );
 
         let mut params = Params::default();
         self.0.iter().for_each(|s| match s {
             WorldShard::Banner(banners) => {
-                params.insert("banner", banners.iter().map(BannerId::to_string).join(","));
+                params.insert_str("banner", banners.iter().join(","));
             }
             WorldShard::Census(CensusShard { scale, modes }) => {
                 params.insert_scale(scale).insert_modes(modes);
@@ -248,6 +367,9 @@ impl<'a> NSRequest for WorldRequest<'a> {
             WorldShard::Dispatch(id) => {
                 params.insert("dispatchid", id);
             }
+            WorldShard::Poll(id) => {
+                params.insert("pollid", id);
+            }
             WorldShard::DispatchList {
                 author,
                 category,
@@ -267,23 +389,21 @@ impl<'a> NSRequest for WorldRequest<'a> {
                 since_time,
                 before_time,
             } => {
+                if let Some(v) = view {
+                    let (mut value, entities) = match v {
+                        Nation(entities) => (String::from("nation."), entities),
+                        Region(entities) => (String::from("region."), entities),
+                    };
+                    value.push_str(&join_lowercase(entities.iter().map(String::as_str), ','));
+                    params.insert_str("view", value);
+                }
+                if let Some(f) = filter {
+                    params.insert_str(
+                        "filter",
+                        join_lowercase(f.iter().map(<&'static str>::from), '+'),
+                    );
+                }
                 params
-                    .insert_on(
-                        "view",
-                        &view.as_ref().map(|v| {
-                            format!(
-                                "{}.{}",
-                                v.as_ref(),
-                                match v {
-                                    Nation(entities) | Region(entities) => {
-                                        entities.iter().join(",")
-                                    }
-                                }
-                            )
-                            .to_ascii_lowercase()
-                        }),
-                    )
-                    .insert_on("filter", &filter.as_ref().map(|f| f.iter().join("+")))
                     .insert_on("limit", limit)
                     .insert_on("sinceid", since_id)
                     .insert_on("beforeid", before_id)
@@ -291,12 +411,15 @@ impl<'a> NSRequest for WorldRequest<'a> {
                     .insert_on("beforetime", before_time);
             }
             WorldShard::RegionsByTag(complex_tags) => {
-                params.insert("tags", complex_tags.iter().join(","));
+                params.insert_str("tags", complex_tags.iter().join(","));
+            }
+            WorldShard::Raw(raw) => {
+                params.insert_raw(raw);
             }
             _ => {}
         });
 
-        Url::parse_with_params(BASE_URL, params.insert_front("q", query)).unwrap()
+        Ok(api_url(params.insert_front("q", query).check()?)?)
     }
 }
 
@@ -416,7 +539,8 @@ impl HappeningsShardBuilder {
 }
 
 /// The ways to sort dispatches.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, EnumString, PartialEq)]
+#[strum(ascii_case_insensitive)]
 pub enum DispatchSort {
     /// Newest first.
     New,
@@ -436,7 +560,8 @@ pub enum HappeningsViewType {
 }
 
 /// The happenings shard can target multiple kinds of events.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, EnumString, IntoStaticStr, PartialEq)]
+#[strum(serialize_all = "lowercase")]
 #[non_exhaustive]
 pub enum HappeningsFilterType {
     /// Triggered by answering an issue (dismissing the issue results in no event).
@@ -492,7 +617,7 @@ pub enum HappeningsFilterType {
 
 impl Display for HappeningsFilterType {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", format!("{:?}", self).to_ascii_lowercase())
+        f.write_str(self.into())
     }
 }
 
@@ -506,14 +631,18 @@ impl Display for HappeningsFilterType {
 ///     region::Tag::{Fandom, Fascist, RegionalGovernment},
 /// };
 ///
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
 /// let shard = [WorldShard::RegionsByTag(vec![
 ///     Include(RegionalGovernment), Include(Fandom), Exclude(Fascist)
 /// ])];
 /// let request = WorldRequest::new(&shard);
 /// assert_eq!(
-///     request.as_url().as_str(),
-///     "https://www.nationstates.net/cgi-bin/api.cgi?q=regionsbytag&tags=regional_government%2Cfandom%2C-fascist",
-/// )
+///     request.as_url()?.as_str(),
+///     "https://www.nationstates.net/cgi-bin/api.cgi?q=regionsbytag&tags=regional_government%2Cfandom%2C-fascist&v=12",
+/// );
+/// # Ok(())
+/// # }
+/// # test().unwrap();
 /// ```
 #[derive(Clone, Debug, PartialEq)]
 pub enum IncludeOrExcludeTag {
@@ -525,18 +654,12 @@ pub enum IncludeOrExcludeTag {
 
 impl Display for IncludeOrExcludeTag {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            match self {
-                IncludeOrExcludeTag::Include(tag) => {
-                    format!("{}", tag)
-                }
-                IncludeOrExcludeTag::Exclude(tag) => {
-                    format!("-{}", tag)
-                }
+        match self {
+            IncludeOrExcludeTag::Include(tag) => write_lowercase(f, tag.as_str()),
+            IncludeOrExcludeTag::Exclude(tag) => {
+                f.write_char('-')?;
+                write_lowercase(f, tag.as_str())
             }
-            .to_ascii_lowercase()
-        )
+        }
     }
 }
@@ -0,0 +1,292 @@
+//! [`proptest`] strategies for names, shards and World Census configurations,
+//! for fuzzing systems built on this crate.
+//!
+//! Every shard and census type here implements [`Arbitrary`],
+//! so it can be generated with [`any`]:
+//! ```rust
+//! use crustacean_states::shards::{nation::PublicNationRequest, NSRequest};
+//! use crustacean_states::strategies::nation_name;
+//! use proptest::{collection::vec, prelude::*};
+//! use crustacean_states::shards::nation::PublicNationShard;
+//!
+//! proptest!(|(name in nation_name(), shards in vec(any::<PublicNationShard>(), 1..5))| {
+//!     let request = PublicNationRequest::new_with_shards(name, shards);
+//!     let _ = request.as_url();
+//! });
+//! ```
+//!
+//! [`RawShard`](crate::shards::RawShard)s are never generated,
+//! since they are sent exactly as they are given.
+use crate::{
+    models::name::{NationName, RegionName},
+    shards::{
+        nation::PublicNationShard, region::RegionShard, region::RmbShard, CensusCurrentMode,
+        CensusHistoryParams, CensusModes, CensusRanksShard, CensusScales, CensusShard,
+    },
+};
+use proptest::{
+    collection::vec,
+    option,
+    prelude::*,
+    sample::{select, subsequence},
+};
+use std::{borrow::Cow, num::NonZeroU64, str::FromStr};
+use strum::VariantNames;
+
+/// Generates well-formed nation names:
+/// one to forty letters, digits, spaces and hyphens,
+/// starting and ending with a letter or digit.
+pub fn nation_name() -> impl Strategy<Value = String> {
+    "[A-Za-z0-9]([A-Za-z0-9 -]{0,38}[A-Za-z0-9])?"
+}
+
+/// Generates well-formed region names:
+/// one to thirty letters, digits, spaces and hyphens,
+/// starting and ending with a letter or digit.
+pub fn region_name() -> impl Strategy<Value = String> {
+    "[A-Za-z0-9]([A-Za-z0-9 -]{0,28}[A-Za-z0-9])?"
+}
+
+impl Arbitrary for NationName {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        nation_name().prop_map(NationName::from).boxed()
+    }
+}
+
+impl Arbitrary for RegionName {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        region_name().prop_map(RegionName::from).boxed()
+    }
+}
+
+/// The shards that are only a name, found by parsing every variant name
+/// and keeping those that are not followed by data.
+fn plain_shards<T, F>(has_data: F) -> Vec<T>
+where
+    T: VariantNames + FromStr + Clone + std::fmt::Debug,
+    F: Fn(&T) -> bool,
+{
+    T::VARIANTS
+        .iter()
+        .filter_map(|name| T::from_str(name).ok())
+        .filter(|shard| !has_data(shard))
+        .collect()
+}
+
+impl Arbitrary for PublicNationShard<'static> {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        let plain = plain_shards(|shard| {
+            matches!(
+                shard,
+                PublicNationShard::Census(_)
+                    | PublicNationShard::TGCanRecruit { .. }
+                    | PublicNationShard::TGCanCampaign { .. }
+                    | PublicNationShard::Raw(_)
+            )
+        });
+        let from = || option::of(region_name().prop_map(Cow::Owned));
+        prop_oneof![
+            12 => select(plain),
+            2 => any::<CensusShard>().prop_map(PublicNationShard::Census),
+            1 => from().prop_map(|from| PublicNationShard::TGCanRecruit { from }),
+            1 => from().prop_map(|from| PublicNationShard::TGCanCampaign { from }),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for RegionShard<'static> {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        let plain = plain_shards(|shard| {
+            matches!(
+                shard,
+                RegionShard::Census(_)
+                    | RegionShard::CensusRanks(_)
+                    | RegionShard::Messages(_)
+                    | RegionShard::Raw(_)
+            )
+        });
+        prop_oneof![
+            12 => select(plain),
+            2 => any::<CensusShard>().prop_map(RegionShard::Census),
+            1 => any::<CensusRanksShard>().prop_map(RegionShard::CensusRanks),
+            1 => any::<RmbShard>().prop_map(RegionShard::Messages),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for CensusShard<'static> {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (any::<CensusScales>(), any::<CensusModes>())
+            .prop_map(|(scale, modes)| CensusShard::new(scale, modes))
+            .boxed()
+    }
+}
+
+impl Arbitrary for CensusScales<'static> {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            Just(CensusScales::Today),
+            any::<u8>().prop_map(CensusScales::One),
+            vec(any::<u8>(), 1..10).prop_map(CensusScales::from),
+            Just(CensusScales::All),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for CensusModes {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            any::<CensusHistoryParams>().prop_map(CensusModes::History),
+            subsequence(CURRENT_MODES.to_vec(), 0..=CURRENT_MODES.len())
+                .prop_map(CensusModes::from),
+        ]
+        .boxed()
+    }
+}
+
+const CURRENT_MODES: [CensusCurrentMode; 5] = [
+    CensusCurrentMode::Score,
+    CensusCurrentMode::Rank,
+    CensusCurrentMode::RegionRank,
+    CensusCurrentMode::PercentRank,
+    CensusCurrentMode::PercentRegionRank,
+];
+
+impl Arbitrary for CensusCurrentMode {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        select(CURRENT_MODES.as_slice()).boxed()
+    }
+}
+
+/// Only valid windows are generated, which never end before they start.
+impl Arbitrary for CensusHistoryParams {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (any::<Option<NonZeroU64>>(), any::<Option<NonZeroU64>>())
+            .prop_map(|(a, b)| {
+                let mut params = CensusHistoryParams::default();
+                match (a, b) {
+                    (Some(a), Some(b)) => {
+                        params.after(a.min(b)).unwrap();
+                        params.before(a.max(b)).unwrap();
+                    }
+                    (Some(after), None) => {
+                        params.after(after).unwrap();
+                    }
+                    (None, Some(before)) => {
+                        params.before(before).unwrap();
+                    }
+                    (None, None) => {}
+                }
+                params
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for CensusRanksShard {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            option::of(0..u8::MAX),
+            option::of(any::<std::num::NonZeroU32>()),
+        )
+            .prop_map(|(scale, start)| {
+                let mut shard = CensusRanksShard::default();
+                if let Some(scale) = scale {
+                    shard.scale(scale);
+                }
+                if let Some(start) = start {
+                    shard.start(start);
+                }
+                shard
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for RmbShard {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (any::<u8>(), any::<u32>(), any::<u32>())
+            .prop_map(|(limit, offset, starting_post)| {
+                RmbShard::default()
+                    .limit(limit)
+                    .offset(offset)
+                    .starting_post(starting_post)
+                    .to_owned()
+            })
+            .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shards::{
+        nation::PublicNationRequest, region::RegionRequest, NSRequest, RequestBuildError,
+    };
+
+    proptest! {
+        #[test]
+        fn nation_requests_build(
+            name in nation_name(),
+            shards in vec(any::<PublicNationShard>(), 1..8),
+        ) {
+            match PublicNationRequest::new_with_shards(name.as_str(), shards).params() {
+                Ok(params) => prop_assert_eq!(params.get("nation"), Some(name.as_str())),
+                Err(e) => prop_assert!(matches!(e, RequestBuildError::ConflictingShards { .. }), "{}", e),
+            }
+        }
+
+        #[test]
+        fn region_requests_build(
+            name in region_name(),
+            shards in vec(any::<RegionShard>(), 1..8),
+        ) {
+            match RegionRequest::new_with_shards(name.as_str(), shards).params() {
+                Ok(params) => prop_assert_eq!(params.get("region"), Some(name.as_str())),
+                Err(e) => prop_assert!(matches!(e, RequestBuildError::ConflictingShards { .. }), "{}", e),
+            }
+        }
+
+        #[test]
+        fn names_are_well_formed(name in any::<NationName>()) {
+            prop_assert!((1..=40).contains(&name.as_str().len()));
+            prop_assert_eq!(name.as_str().trim(), name.as_str());
+        }
+    }
+}
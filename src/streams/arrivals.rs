@@ -0,0 +1,184 @@
+//! A stream of nations arriving in a set of regions, for welcome bots.
+
+use crate::{
+    client::{Client, ClientError},
+    models::{
+        name::{NationName, RegionName},
+        refounds::{Founding, FoundingKind},
+    },
+    parsers::happenings::Event,
+    regex,
+    shards::world::{HappeningsFilterType, HappeningsViewType},
+    streams::{happenings::HappeningsStream, impl_stream, DEFAULT_POLL_INTERVAL},
+};
+use futures::{stream::BoxStream, StreamExt};
+use std::{collections::HashSet, time::Duration};
+
+/// How a nation arrived in a region.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum ArrivalKind {
+    /// The nation moved from another region.
+    Moved,
+    /// The nation was founded in the region.
+    Founded,
+    /// The nation was revived after ceasing to exist.
+    Refounded,
+}
+
+/// A nation that arrived in a region.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct NewArrival {
+    /// The nation that arrived.
+    pub nation: NationName,
+    /// The region it arrived in.
+    pub region: RegionName,
+    /// The region it moved from, if it moved.
+    pub from_region: Option<RegionName>,
+    /// How it arrived.
+    pub kind: ArrivalKind,
+    /// The Unix timestamp of the arrival.
+    pub timestamp: u64,
+}
+
+impl NewArrival {
+    /// Reads an arrival from a happening, if it has the form
+    /// "NATION relocated from REGION1 to REGION2." or "NATION was (re)founded in REGION."
+    pub fn from_event(event: &Event) -> Option<Self> {
+        if let Some(captures) =
+            regex!(r"^@@([a-z0-9_-]+)@@ relocated from %%([a-z0-9_-]+)%% to %%([a-z0-9_-]+)%%")
+                .captures(&event.text)
+        {
+            return Some(Self {
+                nation: NationName::from(&captures[1]),
+                region: RegionName::from(&captures[3]),
+                from_region: Some(RegionName::from(&captures[2])),
+                kind: ArrivalKind::Moved,
+                timestamp: event.timestamp,
+            });
+        }
+        let founding = Founding::from_event(event)?;
+        Some(Self {
+            nation: founding.nation,
+            region: founding.region,
+            from_region: None,
+            kind: match founding.kind {
+                FoundingKind::Founded => ArrivalKind::Founded,
+                FoundingKind::Refounded => ArrivalKind::Refounded,
+            },
+            timestamp: founding.timestamp,
+        })
+    }
+}
+
+/// Watches the [`Move`](HappeningsFilterType::Move) and
+/// [`Founding`](HappeningsFilterType::Founding) happenings of a set of regions,
+/// and yields each [`NewArrival`] in them once, oldest first.
+///
+/// Nations moving out of the regions are skipped.
+/// If a poll fails, the error is yielded and polling continues.
+///
+/// ## Example
+/// ```rust
+/// # use crustacean_states::client::Client;
+/// # use crustacean_states::streams::arrivals::NewArrivalStream;
+/// # use futures::StreamExt;
+/// # async fn test() {
+/// # let client = Client::new("");
+/// let mut arrivals = NewArrivalStream::new(client, ["Testregionia"]);
+/// while let Some(Ok(arrival)) = arrivals.next().await {
+///     println!(
+///         "Welcome to {}, {}!",
+///         arrival.region.pretty_name(),
+///         arrival.nation.pretty_name(),
+///     );
+/// }
+/// # }
+/// ```
+pub struct NewArrivalStream {
+    inner: BoxStream<'static, Result<NewArrival, ClientError>>,
+}
+
+impl NewArrivalStream {
+    /// Creates a stream of arrivals in the `regions`, polling every [`DEFAULT_POLL_INTERVAL`].
+    pub fn new<I, R>(client: Client, regions: I) -> Self
+    where
+        I: IntoIterator<Item = R>,
+        R: ToString,
+    {
+        Self::with_poll_interval(client, regions, DEFAULT_POLL_INTERVAL)
+    }
+
+    /// Creates a stream of arrivals in the `regions`, polling every `interval`.
+    pub fn with_poll_interval<I, R>(client: Client, regions: I, interval: Duration) -> Self
+    where
+        I: IntoIterator<Item = R>,
+        R: ToString,
+    {
+        let regions = regions
+            .into_iter()
+            .map(|r| r.to_string())
+            .collect::<Vec<_>>();
+        let watched = regions
+            .iter()
+            .map(|r| RegionName::from(r.as_str()))
+            .collect::<HashSet<_>>();
+        Self {
+            inner: HappeningsStream::with_view(
+                client,
+                Some(HappeningsViewType::Region(regions)),
+                [HappeningsFilterType::Move, HappeningsFilterType::Founding],
+                interval,
+            )
+            .filter_map(move |event| {
+                let arrival = match event {
+                    Ok(event) => NewArrival::from_event(&event)
+                        .filter(|arrival| watched.contains(&arrival.region))
+                        .map(Ok),
+                    Err(e) => Some(Err(e)),
+                };
+                async move { arrival }
+            })
+            .boxed(),
+        }
+    }
+}
+
+impl_stream!(NewArrivalStream => NewArrival);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arrival_from_event() {
+        let event = |text| Event::from_text(100, text);
+        let arrival = NewArrival::from_event(&event(
+            "@@maxtopia@@ relocated from %%the_pacific%% to %%testregionia%%.",
+        ))
+        .unwrap();
+        assert_eq!(arrival.nation, NationName::from("maxtopia"));
+        assert_eq!(arrival.region, RegionName::from("testregionia"));
+        assert_eq!(arrival.from_region, Some(RegionName::from("the_pacific")));
+        assert_eq!(arrival.kind, ArrivalKind::Moved);
+
+        let arrival = NewArrival::from_event(&event(
+            "@@testlandia@@ was refounded in %%the_north_pacific%%.",
+        ))
+        .unwrap();
+        assert_eq!(arrival.kind, ArrivalKind::Refounded);
+        assert_eq!(arrival.from_region, None);
+        assert_eq!(
+            NewArrival::from_event(&event("@@aramos@@ was founded in %%lazarus%%."))
+                .unwrap()
+                .kind,
+            ArrivalKind::Founded
+        );
+        assert_eq!(
+            NewArrival::from_event(&event("@@aramos@@ ceased to exist in %%lazarus%%.")),
+            None
+        );
+    }
+}
@@ -0,0 +1,303 @@
+//! A stream of warnings about nations that are close to ceasing to exist.
+
+use crate::{
+    client::{Client, ClientError},
+    models::{name::NationName, timestamp::DAY},
+    parsers::nation::{CTE_DAYS, VACATION_CTE_DAYS},
+    shards::nation::{PublicNationRequest, PublicNationShard},
+    streams::{impl_stream, poll_stream},
+};
+use futures::{stream::BoxStream, FutureExt, StreamExt};
+use std::{
+    collections::HashMap,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// How long a [`CteWarningStream`] waits between polls by default.
+///
+/// Days left only change once a day, so there is no need to poll often.
+pub const CTE_POLL_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// A nation on a CTE watchlist.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WatchedNation {
+    /// The name of the nation.
+    pub name: NationName,
+    /// Whether the nation is in vacation mode,
+    /// which gives it [`VACATION_CTE_DAYS`] instead of [`CTE_DAYS`] without logging in.
+    pub vacation: bool,
+}
+
+impl WatchedNation {
+    /// A nation that is not in vacation mode.
+    pub fn new(name: impl Into<NationName>) -> Self {
+        Self {
+            name: name.into(),
+            vacation: false,
+        }
+    }
+
+    /// A nation that is in vacation mode.
+    pub fn on_vacation(name: impl Into<NationName>) -> Self {
+        Self {
+            name: name.into(),
+            vacation: true,
+        }
+    }
+
+    /// How many days the nation can go without logging in before it ceases to exist.
+    pub fn cte_days(&self) -> u64 {
+        if self.vacation {
+            VACATION_CTE_DAYS
+        } else {
+            CTE_DAYS
+        }
+    }
+}
+
+impl From<NationName> for WatchedNation {
+    fn from(value: NationName) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<&str> for WatchedNation {
+    fn from(value: &str) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<String> for WatchedNation {
+    fn from(value: String) -> Self {
+        Self::new(value)
+    }
+}
+
+/// A warning that a nation will soon cease to exist unless it logs in.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct CteWarning {
+    /// The nation.
+    pub nation: NationName,
+    /// The Unix timestamp of when the nation last logged in.
+    pub last_login: u64,
+    /// The number of whole days left before the nation ceases to exist.
+    pub days_left: u64,
+    /// Whether the nation was counted as being in vacation mode.
+    pub vacation: bool,
+}
+
+/// Repeatedly polls the [`LastLogin`](PublicNationShard::LastLogin) of a watchlist of nations,
+/// and yields a [`CteWarning`] for each nation that has `warn_days` or fewer days left
+/// before it ceases to exist.
+///
+/// A nation is warned about again each time its days left go down,
+/// so a nation that is never logged in to is warned about once a day.
+/// Nations are checked with [`Client::get_many`] to stay within the rate limit.
+/// If a check fails, for example because the nation has already ceased to exist,
+/// the error is yielded and polling continues.
+///
+/// ## Example
+/// ```rust
+/// # use crustacean_states::client::Client;
+/// # use crustacean_states::streams::cte::{CteWarningStream, WatchedNation};
+/// # use futures::StreamExt;
+/// # async fn test() {
+/// # let client = Client::new("");
+/// let watchlist = [WatchedNation::new("Testlandia"), WatchedNation::on_vacation("Maxtopia")];
+/// let mut warnings = CteWarningStream::new(client, watchlist, 7);
+/// while let Some(Ok(warning)) = warnings.next().await {
+///     println!(
+///         "{} will cease to exist in {} days!",
+///         warning.nation.pretty_name(),
+///         warning.days_left,
+///     );
+/// }
+/// # }
+/// ```
+pub struct CteWarningStream {
+    inner: BoxStream<'static, Result<CteWarning, ClientError>>,
+}
+
+struct State {
+    client: Client,
+    nations: Vec<WatchedNation>,
+    warn_days: u64,
+    /// The days left that each nation was last warned about.
+    warned: HashMap<NationName, u64>,
+}
+
+impl CteWarningStream {
+    /// Creates a stream of CTE warnings for a watchlist of nations,
+    /// polling every [`CTE_POLL_INTERVAL`].
+    pub fn new<I, W>(client: Client, nations: I, warn_days: u64) -> Self
+    where
+        I: IntoIterator<Item = W>,
+        W: Into<WatchedNation>,
+    {
+        Self::with_poll_interval(client, nations, warn_days, CTE_POLL_INTERVAL)
+    }
+
+    /// Creates a stream of CTE warnings for a watchlist of nations, polling every `interval`.
+    pub fn with_poll_interval<I, W>(
+        client: Client,
+        nations: I,
+        warn_days: u64,
+        interval: Duration,
+    ) -> Self
+    where
+        I: IntoIterator<Item = W>,
+        W: Into<WatchedNation>,
+    {
+        let state = State {
+            client,
+            nations: nations.into_iter().map(Into::into).collect(),
+            warn_days,
+            warned: HashMap::new(),
+        };
+        Self {
+            inner: poll_stream(state, interval, |state| state.poll().boxed()),
+        }
+    }
+}
+
+impl State {
+    /// Returns the warnings and errors from this poll,
+    /// or `None` if there are no nations to poll.
+    async fn poll(&mut self) -> Option<Vec<Result<CteWarning, ClientError>>> {
+        if self.nations.is_empty() {
+            return None;
+        }
+        let requests = self
+            .nations
+            .iter()
+            .map(|nation| {
+                PublicNationRequest::new_with_shards(
+                    nation.name.safe_name(),
+                    [PublicNationShard::LastLogin],
+                )
+            })
+            .collect::<Vec<PublicNationRequest<'static>>>();
+        let results = self
+            .client
+            .get_many(requests)
+            .boxed()
+            .collect::<Vec<_>>()
+            .await;
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        let mut items = Vec::new();
+        for (request, result) in results {
+            let name = NationName::from(request.nation_name().unwrap_or_default());
+            let last_login = match result {
+                Ok(nation) => nation.last_login,
+                Err(e) => {
+                    items.push(Err(e));
+                    continue;
+                }
+            };
+            let Some(watched) = self.nations.iter().find(|n| n.name == name) else {
+                continue;
+            };
+            let Some(last_login) = last_login else {
+                continue;
+            };
+            if let Some(warning) =
+                check_nation(watched, last_login, now, self.warn_days, &mut self.warned)
+            {
+                items.push(Ok(warning));
+            }
+        }
+        Some(items)
+    }
+}
+
+/// Decides whether to warn about a nation that last logged in at `last_login`,
+/// and updates `warned` with the days left that each nation was last warned about.
+///
+/// A nation is warned about once it has `warn_days` or fewer days left,
+/// and again each time its days left go down.
+/// Once it has more days left again, it is forgotten.
+fn check_nation(
+    watched: &WatchedNation,
+    last_login: u64,
+    now: u64,
+    warn_days: u64,
+    warned: &mut HashMap<NationName, u64>,
+) -> Option<CteWarning> {
+    let days_left = watched
+        .cte_days()
+        .saturating_sub(now.saturating_sub(last_login) / DAY);
+    if days_left > warn_days {
+        warned.remove(&watched.name);
+        return None;
+    }
+    if warned
+        .get(&watched.name)
+        .is_some_and(|&warned| warned <= days_left)
+    {
+        return None;
+    }
+    warned.insert(watched.name.clone(), days_left);
+    Some(CteWarning {
+        nation: watched.name.clone(),
+        last_login,
+        days_left,
+        vacation: watched.vacation,
+    })
+}
+
+impl_stream!(CteWarningStream => CteWarning);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn warnings() {
+        let nation = WatchedNation::new("Testlandia");
+        let mut warned = HashMap::new();
+        let mut check =
+            |now| check_nation(&nation, 0, now, 7, &mut warned).map(|warning| warning.days_left);
+
+        assert_eq!(check((CTE_DAYS - 8) * DAY), None);
+        assert_eq!(check((CTE_DAYS - 7) * DAY), Some(7));
+        // The days left have not gone down since the last warning.
+        assert_eq!(check((CTE_DAYS - 7) * DAY + 3600), None);
+        assert_eq!(check((CTE_DAYS - 6) * DAY), Some(6));
+        assert_eq!(check(CTE_DAYS * DAY), Some(0));
+        assert_eq!(check(CTE_DAYS * DAY + DAY), None);
+    }
+
+    #[test]
+    fn warnings_reset_after_login() {
+        let nation = WatchedNation::new("Testlandia");
+        let mut warned = HashMap::new();
+        let now = CTE_DAYS * DAY;
+
+        assert!(check_nation(&nation, 3 * DAY, now, 7, &mut warned).is_some());
+        // Logging in puts the nation out of range, so it is forgotten...
+        assert!(check_nation(&nation, now, now, 7, &mut warned).is_none());
+        assert!(warned.is_empty());
+        // ...and warned about again from the start the next time it gets close.
+        let warning = check_nation(&nation, now, now + (CTE_DAYS - 3) * DAY, 7, &mut warned);
+        assert_eq!(warning.map(|w| w.days_left), Some(3));
+    }
+
+    #[test]
+    fn vacation_warnings() {
+        let nation = WatchedNation::on_vacation("Maxtopia");
+        let mut warned = HashMap::new();
+        let now = (VACATION_CTE_DAYS - 5) * DAY;
+
+        // Without vacation mode, the nation would already be gone.
+        let warning = check_nation(&WatchedNation::new("Maxtopia"), 0, now, 7, &mut warned);
+        assert_eq!(warning.map(|w| w.days_left), Some(0));
+        warned.clear();
+        let warning = check_nation(&nation, 0, now, 7, &mut warned).unwrap();
+        assert_eq!((warning.days_left, warning.vacation), (5, true));
+    }
+}
@@ -0,0 +1,142 @@
+//! A stream of ejections and bans in a set of regions.
+
+use crate::{
+    client::{Client, ClientError},
+    models::name::{NationName, RegionName},
+    parsers::happenings::Event,
+    regex,
+    shards::world::{HappeningsFilterType, HappeningsViewType},
+    streams::{happenings::HappeningsStream, impl_stream, DEFAULT_POLL_INTERVAL},
+};
+use futures::{stream::BoxStream, StreamExt};
+use std::time::Duration;
+
+/// A nation that was ejected from a region.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct Ejection {
+    /// The nation that was ejected.
+    pub nation: NationName,
+    /// The region it was ejected from.
+    pub region: RegionName,
+    /// The nation that ejected it.
+    pub ejector: NationName,
+    /// Whether the nation was also banned from the region.
+    pub banned: bool,
+    /// The Unix timestamp of the ejection.
+    pub timestamp: u64,
+}
+
+impl Ejection {
+    /// Reads an ejection from a happening,
+    /// if it has the form "NATION was ejected (and banned) from REGION by OTHER_NATION."
+    pub fn from_event(event: &Event) -> Option<Self> {
+        let captures = regex!(
+            r"^@@([a-z0-9_-]+)@@ was ejected (and banned )?from %%([a-z0-9_-]+)%% by @@([a-z0-9_-]+)@@"
+        )
+        .captures(&event.text)?;
+        Some(Self {
+            nation: NationName::from(&captures[1]),
+            region: RegionName::from(&captures[3]),
+            ejector: NationName::from(&captures[4]),
+            banned: captures.get(2).is_some(),
+            timestamp: event.timestamp,
+        })
+    }
+}
+
+/// Watches the [`Eject`](HappeningsFilterType::Eject) and
+/// [`Admin`](HappeningsFilterType::Admin) happenings of a set of regions,
+/// and yields each [`Ejection`] once, oldest first.
+///
+/// Other administrative happenings are skipped.
+/// If a poll fails, the error is yielded and polling continues.
+///
+/// ## Example
+/// ```rust
+/// # use crustacean_states::client::Client;
+/// # use crustacean_states::streams::ejections::EjectionStream;
+/// # use futures::StreamExt;
+/// # async fn test() {
+/// # let client = Client::new("");
+/// let mut ejections = EjectionStream::new(client, ["Testregionia"]);
+/// while let Some(Ok(ejection)) = ejections.next().await {
+///     println!(
+///         "{} was ejected by {}",
+///         ejection.nation.pretty_name(),
+///         ejection.ejector.pretty_name(),
+///     );
+/// }
+/// # }
+/// ```
+pub struct EjectionStream {
+    inner: BoxStream<'static, Result<Ejection, ClientError>>,
+}
+
+impl EjectionStream {
+    /// Creates a stream of ejections from the `regions`, polling every [`DEFAULT_POLL_INTERVAL`].
+    pub fn new<I, R>(client: Client, regions: I) -> Self
+    where
+        I: IntoIterator<Item = R>,
+        R: ToString,
+    {
+        Self::with_poll_interval(client, regions, DEFAULT_POLL_INTERVAL)
+    }
+
+    /// Creates a stream of ejections from the `regions`, polling every `interval`.
+    pub fn with_poll_interval<I, R>(client: Client, regions: I, interval: Duration) -> Self
+    where
+        I: IntoIterator<Item = R>,
+        R: ToString,
+    {
+        let view = HappeningsViewType::Region(regions.into_iter().map(|r| r.to_string()).collect());
+        Self {
+            inner: HappeningsStream::with_view(
+                client,
+                Some(view),
+                [HappeningsFilterType::Eject, HappeningsFilterType::Admin],
+                interval,
+            )
+            .filter_map(|event| async move {
+                match event {
+                    Ok(event) => Ejection::from_event(&event).map(Ok),
+                    Err(e) => Some(Err(e)),
+                }
+            })
+            .boxed(),
+        }
+    }
+}
+
+impl_stream!(EjectionStream => Ejection);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ejection_from_event() {
+        let event = |text| Event::from_text(100, text);
+        let ejection = Ejection::from_event(&event(
+            "@@raider@@ was ejected and banned from %%testregionia%% by @@testlandia@@.",
+        ))
+        .unwrap();
+        assert_eq!(ejection.nation, NationName::from("raider"));
+        assert_eq!(ejection.region, RegionName::from("testregionia"));
+        assert_eq!(ejection.ejector, NationName::from("testlandia"));
+        assert!(ejection.banned);
+        assert!(
+            !Ejection::from_event(&event(
+                "@@raider@@ was ejected from %%testregionia%% by @@testlandia@@."
+            ))
+            .unwrap()
+            .banned
+        );
+        assert_eq!(
+            Ejection::from_event(&event(
+                "@@testlandia@@ appointed @@maxtopia@@ as Minister of Tests in %%testregionia%%."
+            )),
+            None
+        );
+    }
+}
@@ -0,0 +1,131 @@
+//! A stream of changes to a nation's endorsements.
+
+use crate::{
+    client::{Client, ClientError},
+    models::name::NationName,
+    parsers::nation::WAStatus,
+    shards::nation::PublicNationShard,
+    streams::{impl_stream, poll_stream, DEFAULT_POLL_INTERVAL},
+};
+use futures::{stream::BoxStream, FutureExt};
+use std::{collections::HashSet, time::Duration};
+
+/// A change to a nation's endorsements or World Assembly status.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct EndorsementChange {
+    /// The nations that started endorsing the nation, sorted by name.
+    pub gained: Vec<NationName>,
+    /// The nations that stopped endorsing the nation, sorted by name.
+    pub lost: Vec<NationName>,
+    /// The number of endorsements the nation has now.
+    pub count: usize,
+    /// The change in the number of endorsements since the last poll.
+    pub delta: i64,
+    /// The nation's World Assembly status now.
+    pub wa_status: WAStatus,
+    /// The nation's World Assembly status at the last poll, if it has changed.
+    pub previous_wa_status: Option<WAStatus>,
+}
+
+/// Repeatedly polls a nation's [`Endorsements`](PublicNationShard::Endorsements)
+/// and [`WA`](PublicNationShard::WA) status,
+/// and yields an [`EndorsementChange`] whenever either changes.
+///
+/// The first poll only records the current endorsements.
+/// If a poll fails, the error is yielded and polling continues.
+///
+/// ## Example
+/// ```rust
+/// # use crustacean_states::client::Client;
+/// # use crustacean_states::streams::endorsements::EndorsementStream;
+/// # use futures::StreamExt;
+/// # async fn test() {
+/// # let client = Client::new("");
+/// let mut changes = EndorsementStream::new(client, "Testlandia");
+/// while let Some(Ok(change)) = changes.next().await {
+///     for nation in change.lost {
+///         println!("{} unendorsed Testlandia", nation.pretty_name());
+///     }
+/// }
+/// # }
+/// ```
+pub struct EndorsementStream {
+    inner: BoxStream<'static, Result<EndorsementChange, ClientError>>,
+}
+
+struct State {
+    client: Client,
+    nation: NationName,
+    last: Option<(HashSet<NationName>, WAStatus)>,
+}
+
+impl EndorsementStream {
+    /// Creates a stream of changes to a nation's endorsements,
+    /// polling every [`DEFAULT_POLL_INTERVAL`].
+    pub fn new(client: Client, nation: impl Into<NationName>) -> Self {
+        Self::with_poll_interval(client, nation, DEFAULT_POLL_INTERVAL)
+    }
+
+    /// Creates a stream of changes to a nation's endorsements, polling every `interval`.
+    pub fn with_poll_interval(
+        client: Client,
+        nation: impl Into<NationName>,
+        interval: Duration,
+    ) -> Self {
+        let state = State {
+            client,
+            nation: nation.into(),
+            last: None,
+        };
+        Self {
+            inner: poll_stream(state, interval, |state| {
+                async move { Some(state.poll().await.transpose()) }.boxed()
+            }),
+        }
+    }
+}
+
+impl State {
+    async fn poll(&mut self) -> Result<Option<EndorsementChange>, ClientError> {
+        let nation = self
+            .client
+            .get_nation(
+                self.nation.as_str(),
+                [PublicNationShard::Endorsements, PublicNationShard::WA],
+            )
+            .await?;
+        let endorsements = nation
+            .endorsements
+            .unwrap_or_default()
+            .iter()
+            .map(NationName::from)
+            .collect::<HashSet<_>>();
+        let wa_status = nation
+            .wa_status
+            .unwrap_or_else(|| WAStatus::Unknown(String::new()));
+        let Some((last_endorsements, last_status)) =
+            self.last.replace((endorsements.clone(), wa_status.clone()))
+        else {
+            return Ok(None);
+        };
+        if last_endorsements == endorsements && last_status == wa_status {
+            return Ok(None);
+        }
+        let sorted = |names: Vec<&NationName>| {
+            let mut names = names.into_iter().cloned().collect::<Vec<_>>();
+            names.sort_by_key(|n| n.safe_name());
+            names
+        };
+        Ok(Some(EndorsementChange {
+            gained: sorted(endorsements.difference(&last_endorsements).collect()),
+            lost: sorted(last_endorsements.difference(&endorsements).collect()),
+            count: endorsements.len(),
+            delta: endorsements.len() as i64 - last_endorsements.len() as i64,
+            previous_wa_status: (last_status != wa_status).then_some(last_status),
+            wa_status,
+        }))
+    }
+}
+
+impl_stream!(EndorsementStream => EndorsementChange);
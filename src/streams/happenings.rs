@@ -0,0 +1,136 @@
+//! A stream of world happenings.
+
+use crate::{
+    client::{Client, ClientError},
+    parsers::happenings::Event,
+    shards::world::{HappeningsFilterType, HappeningsViewType, WorldRequest, WorldShard},
+    streams::{impl_stream, poll_stream, DEFAULT_POLL_INTERVAL},
+};
+use futures::{stream::BoxStream, FutureExt};
+use std::time::Duration;
+
+/// Repeatedly polls [`WorldShard::Happenings`] and yields each new [`Event`] once,
+/// oldest first.
+///
+/// The first poll yields the most recent events;
+/// every poll after that only asks for events after the last one seen.
+/// If a poll fails, the error is yielded and polling continues.
+///
+/// ## Example
+/// ```rust
+/// # use crustacean_states::client::Client;
+/// # use crustacean_states::shards::world::HappeningsFilterType;
+/// # use crustacean_states::streams::happenings::HappeningsStream;
+/// # use futures::StreamExt;
+/// # async fn test() {
+/// # let client = Client::new("");
+/// let mut happenings = HappeningsStream::new(client, [HappeningsFilterType::Founding]);
+/// while let Some(event) = happenings.next().await {
+///     match event {
+///         Ok(event) => println!("{}", event.text),
+///         Err(e) => eprintln!("{e}"),
+///     }
+/// }
+/// # }
+/// ```
+pub struct HappeningsStream {
+    inner: BoxStream<'static, Result<Event, ClientError>>,
+}
+
+struct State {
+    client: Client,
+    view: Option<HappeningsViewType>,
+    filter: Option<Vec<HappeningsFilterType>>,
+    last_id: Option<u32>,
+}
+
+impl HappeningsStream {
+    /// Creates a stream of happenings matching any of the `filters`,
+    /// polling every [`DEFAULT_POLL_INTERVAL`].
+    /// If `filters` is empty, every event is yielded.
+    pub fn new<I>(client: Client, filters: I) -> Self
+    where
+        I: IntoIterator<Item = HappeningsFilterType>,
+    {
+        Self::with_poll_interval(client, filters, DEFAULT_POLL_INTERVAL)
+    }
+
+    /// Creates a stream of happenings matching any of the `filters`,
+    /// polling every `interval`.
+    /// If `filters` is empty, every event is yielded.
+    pub fn with_poll_interval<I>(client: Client, filters: I, interval: Duration) -> Self
+    where
+        I: IntoIterator<Item = HappeningsFilterType>,
+    {
+        Self::with_view(client, None, filters, interval)
+    }
+
+    /// Creates a stream of happenings about the nations or regions in the `view`
+    /// that match any of the `filters`, polling every `interval`.
+    /// If `filters` is empty, every event is yielded.
+    pub fn with_view<I>(
+        client: Client,
+        view: Option<HappeningsViewType>,
+        filters: I,
+        interval: Duration,
+    ) -> Self
+    where
+        I: IntoIterator<Item = HappeningsFilterType>,
+    {
+        let filter = Some(filters.into_iter().collect::<Vec<_>>()).filter(|f| !f.is_empty());
+        let state = State {
+            client,
+            view,
+            filter,
+            last_id: None,
+        };
+        Self {
+            inner: poll_stream(state, interval, |state| {
+                async move {
+                    Some(match state.poll().await {
+                        Ok(events) => events.into_iter().map(Ok).collect(),
+                        Err(e) => vec![Err(e)],
+                    })
+                }
+                .boxed()
+            }),
+        }
+    }
+}
+
+impl State {
+    /// Returns the events since the last poll, oldest first.
+    async fn poll(&mut self) -> Result<Vec<Event>, ClientError> {
+        let mut request = WorldRequest::new_empty();
+        request.add_shard(WorldShard::Happenings {
+            view: self.view.clone(),
+            filter: self.filter.clone(),
+            limit: None,
+            since_id: self.last_id,
+            before_id: None,
+            since_time: None,
+            before_time: None,
+        });
+        self.client.wait_until_ready().await;
+        let events = self
+            .client
+            .get_parsed(request)
+            .await?
+            .happenings
+            .unwrap_or_default();
+
+        // Events are sent newest first.
+        let mut new = Vec::new();
+        for event in events.into_iter().rev() {
+            match (event.id, self.last_id) {
+                (Some(id), Some(last)) if id <= last => continue,
+                (Some(id), _) => self.last_id = Some(id),
+                (None, _) => {}
+            }
+            new.push(event);
+        }
+        Ok(new)
+    }
+}
+
+impl_stream!(HappeningsStream => Event);
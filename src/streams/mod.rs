@@ -0,0 +1,118 @@
+//! Long-running [`Stream`](futures::Stream)s built on top of the [`Client`](crate::client::Client).
+//!
+//! Each stream polls the API on its own schedule,
+//! waits for the client's rate limiter before every request,
+//! and yields parsed items as they come in.
+//!
+//! With the `sse` feature enabled, `sse::SseStream` instead subscribes
+//! to the live happenings feed, which does not count against the rate limit.
+
+use crate::client::ClientError;
+use futures::{future::BoxFuture, stream::BoxStream, StreamExt};
+use std::{collections::VecDeque, time::Duration};
+
+pub mod arrivals;
+pub mod cte;
+pub mod ejections;
+pub mod endorsements;
+pub mod happenings;
+pub mod new_nations;
+pub mod recruitment;
+pub mod region_updates;
+pub mod rmb;
+#[cfg(feature = "sse")]
+pub mod sse;
+pub mod tg_queue;
+pub mod triggers;
+pub mod vote_track;
+
+/// How long a polling stream waits between polls by default.
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Polls with `poll` every `interval`, yielding the items of each poll in order.
+///
+/// The first poll happens right away,
+/// and later polls wait until the items of the last one have been taken.
+/// `poll` returns the items it found, errors included,
+/// or `None` once there is nothing left to poll, which ends the stream.
+pub(crate) fn poll_stream<S, T, I, F>(
+    state: S,
+    interval: Duration,
+    poll: F,
+) -> BoxStream<'static, Result<T, ClientError>>
+where
+    S: Send + 'static,
+    T: Send + 'static,
+    I: IntoIterator<Item = Result<T, ClientError>>,
+    F: for<'s> FnMut(&'s mut S) -> BoxFuture<'s, Option<I>> + Send + 'static,
+{
+    let start = (state, poll, VecDeque::new(), false);
+    futures::stream::unfold(
+        start,
+        move |(mut state, mut poll, mut pending, mut polled)| async move {
+            loop {
+                if let Some(item) = pending.pop_front() {
+                    return Some((item, (state, poll, pending, polled)));
+                }
+                if polled {
+                    tokio::time::sleep(interval).await;
+                }
+                polled = true;
+                pending.extend(poll(&mut state).await?);
+            }
+        },
+    )
+    .boxed()
+}
+
+/// Implements [`Stream`](futures::Stream) for a wrapper around a `BoxStream` in its `inner` field.
+macro_rules! impl_stream {
+    ($stream:ty => $item:ty) => {
+        impl futures::Stream for $stream {
+            type Item = Result<$item, crate::client::ClientError>;
+
+            fn poll_next(
+                mut self: std::pin::Pin<&mut Self>,
+                cx: &mut std::task::Context<'_>,
+            ) -> std::task::Poll<Option<Self::Item>> {
+                futures::StreamExt::poll_next_unpin(&mut self.inner, cx)
+            }
+        }
+    };
+}
+pub(crate) use impl_stream;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::FutureExt;
+
+    #[tokio::test]
+    async fn polls_until_done() {
+        let stream = poll_stream(0u32, Duration::ZERO, |polls| {
+            async move {
+                *polls += 1;
+                match *polls {
+                    1 => Some(vec![Ok(1), Err(ClientError::RateLimitPolicyError)]),
+                    // A poll that finds nothing is followed by another.
+                    2 => Some(vec![]),
+                    3 => Some(vec![Ok(3)]),
+                    _ => None,
+                }
+            }
+            .boxed()
+        });
+        let items = stream
+            .map(|item| item.map_err(|e| e.to_string()))
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(
+            items,
+            [
+                Ok(1),
+                Err(ClientError::RateLimitPolicyError.to_string()),
+                Ok(3)
+            ]
+        );
+    }
+}
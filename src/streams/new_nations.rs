@@ -0,0 +1,139 @@
+//! A stream of newly founded nations.
+
+use crate::{
+    client::{Client, ClientError},
+    models::name::NationName,
+    shards::world::{HappeningsFilterType, WorldRequest, WorldShard},
+    streams::{impl_stream, poll_stream, DEFAULT_POLL_INTERVAL},
+};
+use futures::{stream::BoxStream, FutureExt};
+use std::{
+    collections::{HashMap, HashSet},
+    time::Duration,
+};
+
+/// A nation that was just founded.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct NewNation {
+    /// The name of the nation.
+    pub name: NationName,
+    /// The Unix timestamp of when the nation was founded.
+    /// This is `None` if the founding could not be found in the recent happenings.
+    pub founded: Option<u64>,
+}
+
+/// Repeatedly polls [`WorldShard::NewNations`] and yields each newly founded nation once,
+/// oldest first.
+///
+/// Founding timestamps are taken from the founding happenings,
+/// which are requested alongside the new nations so that each poll is a single request.
+/// If a poll fails, the error is yielded and polling continues.
+///
+/// ## Example
+/// ```rust
+/// # use crustacean_states::client::Client;
+/// # use crustacean_states::streams::new_nations::NewNationsStream;
+/// # use futures::StreamExt;
+/// # async fn test() {
+/// # let client = Client::new("");
+/// let mut new_nations = NewNationsStream::new(client);
+/// while let Some(Ok(nation)) = new_nations.next().await {
+///     println!("Welcome, {}!", nation.name.pretty_name());
+/// }
+/// # }
+/// ```
+pub struct NewNationsStream {
+    inner: BoxStream<'static, Result<NewNation, ClientError>>,
+}
+
+struct State {
+    client: Client,
+    /// The nations in the last poll.
+    /// The shard only lists the latest nations,
+    /// so any nation seen before that has dropped off the list and cannot come back.
+    seen: HashSet<NationName>,
+}
+
+impl NewNationsStream {
+    /// Creates a stream of new nations, polling every [`DEFAULT_POLL_INTERVAL`].
+    pub fn new(client: Client) -> Self {
+        Self::with_poll_interval(client, DEFAULT_POLL_INTERVAL)
+    }
+
+    /// Creates a stream of new nations, polling every `interval`.
+    pub fn with_poll_interval(client: Client, interval: Duration) -> Self {
+        let state = State {
+            client,
+            seen: HashSet::new(),
+        };
+        Self {
+            inner: poll_stream(state, interval, |state| {
+                async move {
+                    Some(match state.poll().await {
+                        Ok(nations) => nations.into_iter().map(Ok).collect(),
+                        Err(e) => vec![Err(e)],
+                    })
+                }
+                .boxed()
+            }),
+        }
+    }
+}
+
+impl State {
+    /// Returns the nations founded since the last poll, oldest first.
+    async fn poll(&mut self) -> Result<Vec<NewNation>, ClientError> {
+        let mut request = WorldRequest::new_empty();
+        request.add_shards([
+            WorldShard::NewNations,
+            WorldShard::Happenings {
+                view: None,
+                filter: Some(vec![HappeningsFilterType::Founding]),
+                limit: None,
+                since_id: None,
+                before_id: None,
+                since_time: None,
+                before_time: None,
+            },
+        ]);
+        self.client.wait_until_ready().await;
+        let world = self.client.get_parsed(request).await?;
+
+        let founded = world
+            .happenings
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|e| e.text.contains("was founded") || e.text.contains("was refounded"))
+            .filter_map(|e| {
+                let nation = e.nations.first()?.trim_matches('@').to_string();
+                Some((nation, e.timestamp))
+            })
+            .collect::<HashMap<_, _>>();
+
+        let names = world
+            .new_nations
+            .unwrap_or_default()
+            .into_iter()
+            .map(NationName::from)
+            .collect::<Vec<_>>();
+
+        // New nations are sent newest first.
+        let new = names
+            .iter()
+            .rev()
+            .filter(|name| !self.seen.contains(name))
+            .map(|name| NewNation {
+                name: name.clone(),
+                founded: founded.get(&name.safe_name()).copied(),
+            })
+            .collect();
+        // A response without the shard should not make the next poll repeat every nation.
+        if !names.is_empty() {
+            self.seen = names.into_iter().collect();
+        }
+        Ok(new)
+    }
+}
+
+impl_stream!(NewNationsStream => NewNation);
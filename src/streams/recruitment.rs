@@ -0,0 +1,184 @@
+//! A stream of new nations that can be sent a recruitment telegram.
+
+use crate::{
+    client::{Client, ClientError},
+    models::{name::NationName, telegram::TelegramKind},
+    shards::nation::PublicNationRequest,
+    streams::{
+        impl_stream,
+        new_nations::{NewNation, NewNationsStream},
+    },
+};
+use futures::{stream::BoxStream, FutureExt, StreamExt};
+use std::{collections::VecDeque, time::Duration};
+use tokio::time::Instant;
+
+/// How long the API makes a client wait between recruitment telegrams.
+pub const RECRUITMENT_TELEGRAM_INTERVAL: Duration = TelegramKind::Recruitment.interval();
+
+/// How many nations a [`RecruitmentTargets`] checks for each telegram by default.
+pub const DEFAULT_BATCH_SIZE: usize = 4;
+
+/// The most new nations kept waiting to be checked.
+/// Older nations are dropped first, since they are the least likely to still be unrecruited.
+const MAX_PENDING: usize = 100;
+
+/// Watches for new nations with a [`NewNationsStream`],
+/// and yields only those that can be sent a recruitment telegram,
+/// as reported by
+/// [`PublicNationShard::TGCanRecruit`](crate::shards::nation::PublicNationShard::TGCanRecruit).
+///
+/// The stream is paced to the telegram rate limit:
+/// once per telegram interval, it checks a batch of the newest nations,
+/// and yields those that can be contacted.
+/// If more than one can be contacted, the next batch waits for one interval per nation yielded,
+/// so no more nations are checked than can be sent a telegram.
+/// Checks are spread out under the API rate limit by [`Client::get_many`].
+/// If a poll or a check fails, the error is yielded and the stream continues.
+///
+/// ## Example
+/// ```rust
+/// # use crustacean_states::client::Client;
+/// # use crustacean_states::streams::recruitment::RecruitmentTargets;
+/// # use futures::StreamExt;
+/// # async fn test() {
+/// # let client = Client::new("");
+/// let mut targets = RecruitmentTargets::new(client, Some("Testregionia"));
+/// while let Some(Ok(nation)) = targets.next().await {
+///     println!("Sending a telegram to {}", nation.name.pretty_name());
+/// }
+/// # }
+/// ```
+pub struct RecruitmentTargets {
+    inner: BoxStream<'static, Result<NewNation, ClientError>>,
+}
+
+struct State {
+    client: Client,
+    new_nations: NewNationsStream,
+    from: Option<String>,
+    telegram_interval: Duration,
+    batch_size: usize,
+    /// New nations waiting to be checked, oldest first.
+    pending: VecDeque<NewNation>,
+    /// Nations that can be contacted, waiting to be yielded.
+    targets: VecDeque<NewNation>,
+    next_batch: Instant,
+}
+
+impl RecruitmentTargets {
+    /// Creates a stream of recruitment targets for telegrams from the region `from`,
+    /// checking [`DEFAULT_BATCH_SIZE`] nations every [`RECRUITMENT_TELEGRAM_INTERVAL`].
+    pub fn new(client: Client, from: Option<impl ToString>) -> Self {
+        Self::with_budget(
+            client,
+            from,
+            RECRUITMENT_TELEGRAM_INTERVAL,
+            DEFAULT_BATCH_SIZE,
+        )
+    }
+
+    /// Creates a stream of recruitment targets for telegrams from the region `from`,
+    /// checking up to `batch_size` nations for every telegram that can be sent,
+    /// and allowing one telegram every `telegram_interval`.
+    pub fn with_budget(
+        client: Client,
+        from: Option<impl ToString>,
+        telegram_interval: Duration,
+        batch_size: usize,
+    ) -> Self {
+        let state = State {
+            new_nations: NewNationsStream::new(client.clone()),
+            client,
+            from: from.map(|f| f.to_string()),
+            telegram_interval,
+            batch_size: batch_size.max(1),
+            pending: VecDeque::new(),
+            targets: VecDeque::new(),
+            next_batch: Instant::now(),
+        };
+        Self {
+            inner: futures::stream::unfold(state, |mut state| async move {
+                loop {
+                    if let Some(target) = state.targets.pop_front() {
+                        return Some((Ok(target), state));
+                    }
+                    tokio::time::sleep_until(state.next_batch).await;
+                    if let Err(e) = state.collect_new_nations().await {
+                        return Some((Err(e), state));
+                    }
+                    if let Err(e) = state.check_batch().await {
+                        return Some((Err(e), state));
+                    }
+                }
+            })
+            .boxed(),
+        }
+    }
+}
+
+impl State {
+    /// Takes every new nation that is ready,
+    /// waiting for one if there are none to check.
+    async fn collect_new_nations(&mut self) -> Result<(), ClientError> {
+        if self.pending.is_empty() {
+            if let Some(nation) = self.new_nations.next().await {
+                self.pending.push_back(nation?);
+            }
+        }
+        while let Some(Some(nation)) = self.new_nations.next().now_or_never() {
+            self.pending.push_back(nation?);
+        }
+        while self.pending.len() > MAX_PENDING {
+            self.pending.pop_front();
+        }
+        Ok(())
+    }
+
+    /// Checks the newest nations, and schedules the next batch.
+    async fn check_batch(&mut self) -> Result<(), ClientError> {
+        let take = self.batch_size.min(self.pending.len());
+        let batch = self
+            .pending
+            .drain(self.pending.len() - take..)
+            .collect::<Vec<_>>();
+        let requests = batch
+            .iter()
+            .map(|nation| {
+                PublicNationRequest::new_with_shards(
+                    nation.name.safe_name(),
+                    [TelegramKind::Recruitment.shard(self.from.clone())],
+                )
+            })
+            .collect::<Vec<PublicNationRequest<'static>>>();
+        let results = self
+            .client
+            .get_many(requests)
+            .boxed()
+            .collect::<Vec<_>>()
+            .await;
+
+        let mut error = None;
+        let mut contactable = Vec::new();
+        for (_, result) in results {
+            match result {
+                Ok(nation) if nation.tg_can_recruit == Some(true) => {
+                    contactable.push(NationName::from(nation.name))
+                }
+                Ok(_) => {}
+                Err(e) => error = Some(e),
+            }
+        }
+        // Results come back in any order, so keep the order the nations were founded in.
+        self.targets.extend(
+            batch
+                .into_iter()
+                .filter(|nation| contactable.contains(&nation.name)),
+        );
+        let telegrams = u32::try_from(self.targets.len().max(1)).unwrap_or(u32::MAX);
+        self.next_batch = Instant::now() + self.telegram_interval * telegrams;
+        error.map_or(Ok(()), Err)
+    }
+}
+
+impl_stream!(RecruitmentTargets => NewNation);
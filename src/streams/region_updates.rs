@@ -0,0 +1,149 @@
+//! A stream of region updates.
+
+use crate::{
+    client::{Client, ClientError},
+    models::name::RegionName,
+    shards::region::RegionShard,
+    streams::{impl_stream, poll_stream, DEFAULT_POLL_INTERVAL},
+};
+use futures::{stream::BoxStream, FutureExt};
+use std::{collections::HashMap, time::Duration};
+
+/// Which of the two daily updates a region was updated in.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum UpdateKind {
+    /// The major update, which happens once a day.
+    Major,
+    /// The minor update, which happens once a day, twelve hours after the major update.
+    Minor,
+    /// The region updated, but the API did not say which update it was.
+    Unknown,
+}
+
+/// A region that just updated.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct RegionUpdated {
+    /// The name of the region.
+    pub region: RegionName,
+    /// The Unix timestamp of the update.
+    pub timestamp: u64,
+    /// The Unix timestamp of the update before it.
+    pub previous: u64,
+    /// Which update it was.
+    pub kind: UpdateKind,
+}
+
+/// Repeatedly polls the update timestamps of a set of regions
+/// and yields a [`RegionUpdated`] whenever one of them changes.
+///
+/// Each round of polling sends one request per region,
+/// waiting for the rate limiter before each one,
+/// and then waits for the poll interval before the next round.
+/// The first round only records the current timestamps, so it yields nothing.
+/// If a request fails, the error is yielded and polling continues.
+///
+/// ## Example
+/// ```rust
+/// # use crustacean_states::client::Client;
+/// # use crustacean_states::streams::region_updates::RegionUpdateStream;
+/// # use futures::StreamExt;
+/// # async fn test() {
+/// # let client = Client::new("");
+/// let mut updates = RegionUpdateStream::new(client, ["Testregionia", "The Pacific"]);
+/// while let Some(Ok(update)) = updates.next().await {
+///     println!("{} updated at {}", update.region.pretty_name(), update.timestamp);
+/// }
+/// # }
+/// ```
+pub struct RegionUpdateStream {
+    inner: BoxStream<'static, Result<RegionUpdated, ClientError>>,
+}
+
+struct State {
+    client: Client,
+    regions: Vec<RegionName>,
+    last_updates: HashMap<RegionName, u64>,
+}
+
+impl RegionUpdateStream {
+    /// Creates a stream of updates to the `regions`, polling every [`DEFAULT_POLL_INTERVAL`].
+    pub fn new<I, R>(client: Client, regions: I) -> Self
+    where
+        I: IntoIterator<Item = R>,
+        R: Into<RegionName>,
+    {
+        Self::with_poll_interval(client, regions, DEFAULT_POLL_INTERVAL)
+    }
+
+    /// Creates a stream of updates to the `regions`, polling every `interval`.
+    pub fn with_poll_interval<I, R>(client: Client, regions: I, interval: Duration) -> Self
+    where
+        I: IntoIterator<Item = R>,
+        R: Into<RegionName>,
+    {
+        let state = State {
+            client,
+            regions: regions.into_iter().map(Into::into).collect(),
+            last_updates: HashMap::new(),
+        };
+        Self {
+            inner: poll_stream(state, interval, |state| state.poll().boxed()),
+        }
+    }
+}
+
+impl State {
+    /// Returns the updates and errors since the last poll,
+    /// or `None` if there are no regions to poll.
+    async fn poll(&mut self) -> Option<Vec<Result<RegionUpdated, ClientError>>> {
+        if self.regions.is_empty() {
+            return None;
+        }
+        let mut items = Vec::new();
+        for region in &self.regions {
+            let response = self
+                .client
+                .get_region(
+                    region.as_str(),
+                    [
+                        RegionShard::LastUpdate,
+                        RegionShard::LastMajorUpdate,
+                        RegionShard::LastMinorUpdate,
+                    ],
+                )
+                .await;
+            let parsed = match response {
+                Ok(parsed) => parsed,
+                Err(e) => {
+                    items.push(Err(e));
+                    continue;
+                }
+            };
+            let Some(timestamp) = parsed.last_update else {
+                continue;
+            };
+            let kind = if parsed.last_major_update == Some(timestamp) {
+                UpdateKind::Major
+            } else if parsed.last_minor_update == Some(timestamp) {
+                UpdateKind::Minor
+            } else {
+                UpdateKind::Unknown
+            };
+            match self.last_updates.insert(region.clone(), timestamp) {
+                Some(previous) if previous < timestamp => items.push(Ok(RegionUpdated {
+                    region: region.clone(),
+                    timestamp,
+                    previous,
+                    kind,
+                })),
+                _ => {}
+            }
+        }
+        Some(items)
+    }
+}
+
+impl_stream!(RegionUpdateStream => RegionUpdated);
@@ -0,0 +1,146 @@
+//! A stream of messages on a regional message board.
+
+use crate::{
+    client::{Client, ClientError},
+    parsers::region::Message,
+    shards::region::{RegionRequest, RegionShard, RmbShard},
+    streams::impl_stream,
+};
+use futures::{stream::BoxStream, StreamExt};
+use std::collections::VecDeque;
+
+/// The most messages that can be requested at once.
+const MAX_PAGE_SIZE: u8 = 100;
+
+/// Walks through a regional message board page by page, yielding each [`Message`] once.
+///
+/// If the [`RmbShard`] has a [starting post](RmbShard::starting_post),
+/// the stream walks forwards from that post, oldest message first.
+/// Otherwise, it walks backwards from the most recent message
+/// (skipping the [offset](RmbShard::offset), if there is one), newest message first.
+/// The [limit](RmbShard::limit) sets the size of each page; it defaults to 100.
+///
+/// The stream ends when there are no more messages,
+/// or after yielding an error if a page could not be fetched.
+///
+/// ## Example
+/// ```rust
+/// # use crustacean_states::client::Client;
+/// # use crustacean_states::shards::region::RmbShard;
+/// # use crustacean_states::streams::rmb::RmbStream;
+/// # use futures::StreamExt;
+/// # async fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// # let client = Client::new("");
+/// let mut messages = RmbStream::new(client, "Testregionia", RmbShard::default());
+/// while let Some(message) = messages.next().await {
+///     println!("{}", message?.text);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct RmbStream {
+    inner: BoxStream<'static, Result<Message, ClientError>>,
+}
+
+enum Direction {
+    /// The ID of the next post to ask for.
+    Forwards(u32),
+    /// How many of the most recent posts to skip,
+    /// and the ID of the oldest post seen so far.
+    Backwards(u32, Option<u32>),
+}
+
+struct State {
+    client: Client,
+    region: String,
+    page_size: u8,
+    direction: Direction,
+    pending: VecDeque<Message>,
+    done: bool,
+}
+
+impl RmbStream {
+    /// Creates a stream of the messages on a region's message board.
+    pub fn new(client: Client, region: impl ToString, rmb: RmbShard) -> Self {
+        let direction = match rmb.starting_post {
+            Some(post) => Direction::Forwards(post.get()),
+            None => Direction::Backwards(rmb.offset.map_or(0, |o| o.get()), None),
+        };
+        let state = State {
+            client,
+            region: region.to_string(),
+            page_size: rmb.limit.map_or(MAX_PAGE_SIZE, |l| l.get()),
+            direction,
+            pending: VecDeque::new(),
+            done: false,
+        };
+        Self {
+            inner: futures::stream::unfold(state, |mut state| async move {
+                loop {
+                    if let Some(message) = state.pending.pop_front() {
+                        return Some((Ok(message), state));
+                    }
+                    if state.done {
+                        return None;
+                    }
+                    if let Err(e) = state.next_page().await {
+                        state.done = true;
+                        return Some((Err(e), state));
+                    }
+                }
+            })
+            .boxed(),
+        }
+    }
+}
+
+impl State {
+    async fn next_page(&mut self) -> Result<(), ClientError> {
+        let mut rmb = RmbShard::default();
+        rmb.limit(self.page_size);
+        match self.direction {
+            Direction::Forwards(post) => rmb.starting_post(post),
+            Direction::Backwards(offset, _) => rmb.offset(offset),
+        };
+        self.client.wait_until_ready().await;
+        let mut messages = self
+            .client
+            .get_parsed(RegionRequest::new_with_shards(
+                &self.region,
+                [RegionShard::Messages(rmb)],
+            ))
+            .await?
+            .messages
+            .unwrap_or_default();
+        messages.sort_by_key(|m| m.id);
+
+        if messages.len() < self.page_size as usize {
+            self.done = true;
+        }
+        match &mut self.direction {
+            Direction::Forwards(post) => {
+                if let Some(last) = messages.last() {
+                    *post = last.id + 1;
+                }
+                self.pending.extend(messages);
+            }
+            Direction::Backwards(offset, oldest) => {
+                *offset += self.page_size as u32;
+                // New posts shift every page back, so some posts may be sent twice.
+                let before = *oldest;
+                if let Some(first) = messages.first() {
+                    *oldest = Some(before.map_or(first.id, |b| b.min(first.id)));
+                }
+                self.pending.extend(
+                    messages
+                        .into_iter()
+                        .rev()
+                        .filter(|m| before.is_none_or(|b| m.id < b)),
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+impl_stream!(RmbStream => Message);
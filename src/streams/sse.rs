@@ -0,0 +1,192 @@
+//! Live happenings from the NationStates server-sent events feed.
+//!
+//! Unlike the rest of the API, the feed does not count against the rate limit,
+//! so it is the best way to watch for events as they happen.
+
+use crate::{
+    client::Client, parsers::happenings::Event, safe_name, shards::world::HappeningsFilterType,
+};
+use eventsource_stream::{EventStreamError, Eventsource};
+use futures::{stream::BoxStream, Stream, StreamExt};
+use itertools::Itertools;
+use serde::Deserialize;
+use std::{
+    fmt::{Display, Formatter},
+    pin::Pin,
+    task::{Context, Poll},
+};
+use thiserror::Error;
+
+/// The base URL of the server-sent events feed.
+pub const SSE_BASE_URL: &str = "https://www.nationstates.net/api/";
+
+/// A group of events that can be subscribed to.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SseBucket {
+    /// Every event involving a nation.
+    Nation(String),
+    /// Every event involving a region.
+    Region(String),
+    /// Every event of a kind.
+    /// World Assembly events can be subscribed to with
+    /// [`HappeningsFilterType::Member`], [`HappeningsFilterType::Endo`],
+    /// [`HappeningsFilterType::Vote`], and [`HappeningsFilterType::Resolution`].
+    Filter(HappeningsFilterType),
+}
+
+impl Display for SseBucket {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SseBucket::Nation(nation) => write!(f, "nation:{}", safe_name(nation)),
+            SseBucket::Region(region) => write!(f, "region:{}", safe_name(region)),
+            SseBucket::Filter(filter) => write!(f, "{filter}"),
+        }
+    }
+}
+
+impl From<HappeningsFilterType> for SseBucket {
+    fn from(value: HappeningsFilterType) -> Self {
+        SseBucket::Filter(value)
+    }
+}
+
+/// Yields each [`Event`] sent by the server-sent events feed.
+///
+/// ## Example
+/// ```rust
+/// # use crustacean_states::client::Client;
+/// # use crustacean_states::streams::sse::{SseBucket, SseStream};
+/// # use futures::StreamExt;
+/// # async fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// # let client = Client::new("");
+/// let mut events = SseStream::connect(
+///     &client,
+///     [SseBucket::Region(String::from("The North Pacific"))],
+/// )
+/// .await?;
+/// while let Some(event) = events.next().await {
+///     println!("{}", event?.text);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct SseStream {
+    inner: BoxStream<'static, Result<Event, SseError>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawSseEvent {
+    id: RawSseId,
+    time: u64,
+    str: String,
+}
+
+/// The ID is sometimes sent as a string.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum RawSseId {
+    Number(u32),
+    Text(String),
+}
+
+impl From<RawSseEvent> for Event {
+    fn from(value: RawSseEvent) -> Self {
+        let id = match value.id {
+            RawSseId::Number(id) => Some(id),
+            RawSseId::Text(id) => id.parse().ok(),
+        };
+        Event::new(id, value.time, value.str)
+    }
+}
+
+impl SseStream {
+    /// Subscribes to the given buckets.
+    ///
+    /// The client's User-Agent is sent, but its rate limiter is not involved.
+    pub async fn connect<I>(client: &Client, buckets: I) -> Result<Self, SseError>
+    where
+        I: IntoIterator<Item = SseBucket>,
+    {
+        let url = format!("{SSE_BASE_URL}{}", buckets.into_iter().join("+"));
+        let response = client.http().get(url).send().await?.error_for_status()?;
+        Ok(Self {
+            inner: response
+                .bytes_stream()
+                .eventsource()
+                .filter_map(|event| async move {
+                    match event {
+                        // Keep-alive messages have no data.
+                        Ok(event) if event.data.is_empty() => None,
+                        Ok(event) => Some(
+                            serde_json::from_str::<RawSseEvent>(&event.data)
+                                .map(Event::from)
+                                .map_err(SseError::from),
+                        ),
+                        Err(e) => Some(Err(SseError::from(e))),
+                    }
+                })
+                .boxed(),
+        })
+    }
+}
+
+impl Stream for SseStream {
+    type Item = Result<Event, SseError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.poll_next_unpin(cx)
+    }
+}
+
+/// Describes the errors that may come about from using [`SseStream`].
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum SseError {
+    /// Could not connect to the feed.
+    #[error("could not connect to the feed")]
+    ConnectError {
+        /// The parent error.
+        #[from]
+        source: reqwest::Error,
+    },
+    /// The connection to the feed broke, or the feed sent something that was not an event.
+    #[error("event stream failed")]
+    StreamError {
+        /// The parent error.
+        #[from]
+        source: EventStreamError<reqwest::Error>,
+    },
+    /// An event could not be parsed.
+    #[error("could not parse event")]
+    ParseError {
+        /// The parent error.
+        #[from]
+        source: serde_json::Error,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bucket_display() {
+        assert_eq!(
+            SseBucket::Region(String::from("The North Pacific")).to_string(),
+            "region:the_north_pacific"
+        );
+        assert_eq!(
+            SseBucket::from(HappeningsFilterType::Founding).to_string(),
+            "founding"
+        );
+    }
+
+    #[test]
+    fn parse_event() {
+        let data = r#"{"str":"@@testlandia@@ was founded in %%the_pacific%%.","htmlStr":"","time":100,"id":"42"}"#;
+        let event = Event::from(serde_json::from_str::<RawSseEvent>(data).unwrap());
+        assert_eq!(event.id, Some(42));
+        assert_eq!(event.timestamp, 100);
+        assert_eq!(event.nations, vec!["@@testlandia@@"]);
+    }
+}
@@ -0,0 +1,261 @@
+//! A stream of telegram queue statistics, for timing mass telegrams.
+
+use crate::{
+    client::{Client, ClientError},
+    parsers::world::TGQueue,
+    shards::world::WorldShard,
+    streams::{impl_stream, poll_stream},
+};
+use futures::{stream::BoxStream, FutureExt};
+use std::{
+    collections::VecDeque,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// How long a [`TGQueueStream`] waits between polls by default.
+///
+/// The queue moves slowly enough that polling it as often as
+/// [`DEFAULT_POLL_INTERVAL`](crate::streams::DEFAULT_POLL_INTERVAL) adds little.
+pub const TG_QUEUE_POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How many samples a [`TGQueueHistory`] keeps by default.
+pub const DEFAULT_HISTORY_LEN: usize = 10;
+
+/// The telegram queue at one point in time.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct TGQueueSample {
+    /// The Unix timestamp the queue was polled at.
+    pub timestamp: u64,
+    /// The queue.
+    pub queue: TGQueue,
+}
+
+/// Statistics about the telegram queue over its recent history.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct TGQueueStats {
+    /// The latest sample.
+    pub current: TGQueueSample,
+    /// The change in the total number of queued telegrams since the previous sample.
+    pub delta: Option<i64>,
+    /// The average change in the total number of queued telegrams per second,
+    /// over the whole history.
+    /// A negative rate means the queue is draining.
+    pub rate: Option<f64>,
+    /// How long the telegrams queued now are expected to take to be delivered,
+    /// if the queue is draining.
+    pub estimated_delay: Option<Duration>,
+}
+
+impl TGQueueStats {
+    /// The total number of queued telegrams.
+    pub fn depth(&self) -> u64 {
+        self.current.queue.total()
+    }
+}
+
+/// The recent history of the telegram queue, oldest first.
+///
+/// ```rust
+/// # use crustacean_states::parsers::world::TGQueue;
+/// # use crustacean_states::streams::tg_queue::TGQueueHistory;
+/// # use std::time::Duration;
+/// let mut history = TGQueueHistory::new(10);
+/// history.record(0, TGQueue { manual: 0, mass: 600, api: 0 });
+/// let stats = history.record(60, TGQueue { manual: 0, mass: 540, api: 0 }).unwrap();
+/// assert_eq!(stats.delta, Some(-60));
+/// assert_eq!(stats.rate, Some(-1.0));
+/// assert_eq!(stats.estimated_delay, Some(Duration::from_secs(540)));
+/// ```
+#[derive(Clone, Debug)]
+pub struct TGQueueHistory {
+    samples: VecDeque<TGQueueSample>,
+    len: usize,
+}
+
+impl TGQueueHistory {
+    /// Creates a history that keeps the latest `len` samples.
+    pub fn new(len: usize) -> Self {
+        Self {
+            samples: VecDeque::with_capacity(len),
+            len: len.max(2),
+        }
+    }
+
+    /// Adds a sample of the queue polled at a Unix timestamp,
+    /// dropping the oldest sample if the history is full,
+    /// and returns the statistics with it included.
+    pub fn record(&mut self, timestamp: u64, queue: TGQueue) -> Option<TGQueueStats> {
+        while self.samples.len() >= self.len {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(TGQueueSample { timestamp, queue });
+        self.stats()
+    }
+
+    /// The samples in the history, oldest first.
+    pub fn samples(&self) -> impl Iterator<Item = &TGQueueSample> {
+        self.samples.iter()
+    }
+
+    /// The statistics of the history, if it has any samples.
+    pub fn stats(&self) -> Option<TGQueueStats> {
+        let current = *self.samples.back()?;
+        let change =
+            |from: &TGQueueSample| current.queue.total() as i64 - from.queue.total() as i64;
+        let previous = self.samples.len().checked_sub(2).map(|i| &self.samples[i]);
+        let oldest = self
+            .samples
+            .front()
+            .filter(|s| s.timestamp < current.timestamp);
+        let rate = oldest.map(|s| change(s) as f64 / (current.timestamp - s.timestamp) as f64);
+        Some(TGQueueStats {
+            current,
+            delta: previous.map(change),
+            rate,
+            estimated_delay: rate.filter(|rate| *rate < 0.0).and_then(|rate| {
+                Duration::try_from_secs_f64(current.queue.total() as f64 / -rate).ok()
+            }),
+        })
+    }
+}
+
+impl Default for TGQueueHistory {
+    /// A history that keeps the latest [`DEFAULT_HISTORY_LEN`] samples.
+    fn default() -> Self {
+        Self::new(DEFAULT_HISTORY_LEN)
+    }
+}
+
+/// Repeatedly polls [`WorldShard::TGQueue`]
+/// and yields the [`TGQueueStats`] of the queue after every poll.
+///
+/// If a poll fails, the error is yielded and polling continues.
+///
+/// ## Example
+/// ```rust
+/// # use crustacean_states::client::Client;
+/// # use crustacean_states::streams::tg_queue::TGQueueStream;
+/// # use futures::StreamExt;
+/// # async fn test() {
+/// # let client = Client::new("");
+/// let mut queue = TGQueueStream::new(client);
+/// while let Some(Ok(stats)) = queue.next().await {
+///     match stats.estimated_delay {
+///         Some(delay) => println!("{} telegrams queued, about {delay:?} to deliver", stats.depth()),
+///         None => println!("{} telegrams queued, and the queue is growing", stats.depth()),
+///     }
+/// }
+/// # }
+/// ```
+pub struct TGQueueStream {
+    inner: BoxStream<'static, Result<TGQueueStats, ClientError>>,
+}
+
+struct State {
+    client: Client,
+    history: TGQueueHistory,
+}
+
+impl TGQueueStream {
+    /// Creates a stream of telegram queue statistics over the last [`DEFAULT_HISTORY_LEN`] polls,
+    /// polling every [`TG_QUEUE_POLL_INTERVAL`].
+    pub fn new(client: Client) -> Self {
+        Self::with_poll_interval(client, TG_QUEUE_POLL_INTERVAL, DEFAULT_HISTORY_LEN)
+    }
+
+    /// Creates a stream of telegram queue statistics over the last `history_len` polls,
+    /// polling every `interval`.
+    pub fn with_poll_interval(client: Client, interval: Duration, history_len: usize) -> Self {
+        let state = State {
+            client,
+            history: TGQueueHistory::new(history_len),
+        };
+        Self {
+            inner: poll_stream(state, interval, |state| {
+                async move { Some(state.poll().await.transpose()) }.boxed()
+            }),
+        }
+    }
+}
+
+impl State {
+    async fn poll(&mut self) -> Result<Option<TGQueueStats>, ClientError> {
+        let world = self.client.get_world([WorldShard::TGQueue]).await?;
+        let Some(queue) = world.tg_queue else {
+            return Ok(None);
+        };
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        Ok(self.history.record(now, queue))
+    }
+}
+
+impl_stream!(TGQueueStream => TGQueueStats);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn queue_history() {
+        let queue = |mass| TGQueue {
+            manual: 0,
+            mass,
+            api: 10,
+        };
+        let mut history = TGQueueHistory::new(3);
+        let stats = history.record(100, queue(100)).unwrap();
+        assert_eq!((stats.depth(), stats.delta, stats.rate), (110, None, None));
+
+        history.record(110, queue(150));
+        let stats = history.record(120, queue(120)).unwrap();
+        assert_eq!(stats.delta, Some(-30));
+        assert_eq!(stats.rate, Some(1.0));
+        assert_eq!(stats.estimated_delay, None);
+
+        // The first sample has been dropped.
+        let stats = history.record(130, queue(50)).unwrap();
+        assert_eq!(history.samples().count(), 3);
+        assert_eq!(stats.rate, Some(-5.0));
+        assert_eq!(stats.estimated_delay, Some(Duration::from_secs(12)));
+    }
+
+    #[test]
+    fn default_history_is_bounded() {
+        let mut history = TGQueueHistory::default();
+        for timestamp in 0..2 * DEFAULT_HISTORY_LEN as u64 {
+            history.record(
+                timestamp,
+                TGQueue {
+                    manual: 0,
+                    mass: 0,
+                    api: 0,
+                },
+            );
+        }
+        assert_eq!(history.samples().count(), DEFAULT_HISTORY_LEN);
+    }
+
+    #[test]
+    fn extreme_timestamps() {
+        let queue = |api| TGQueue {
+            manual: 0,
+            mass: u32::MAX,
+            api,
+        };
+        let mut history = TGQueueHistory::new(2);
+        history.record(0, queue(u32::MAX));
+        // Draining one telegram over the whole range of timestamps would take too long
+        // for a `Duration`.
+        let stats = history.record(u64::MAX, queue(u32::MAX - 1)).unwrap();
+        assert!(stats.rate.is_some_and(|rate| rate < 0.0));
+        assert_eq!(stats.estimated_delay, None);
+    }
+}
@@ -0,0 +1,180 @@
+//! A stream of trigger region updates, for timing actions on a target region.
+
+use crate::{
+    client::{Client, ClientError},
+    models::name::RegionName,
+    shards::region::RegionShard,
+    streams::impl_stream,
+};
+use futures::{stream::BoxStream, StreamExt};
+use std::{collections::VecDeque, time::Duration};
+
+/// Whether an updated region was a trigger or the target.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum TriggerKind {
+    /// One of the trigger regions, which update shortly before the target.
+    Trigger,
+    /// The target region.
+    Target,
+}
+
+/// A trigger or target region that just updated.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct TriggerUpdate {
+    /// The name of the region.
+    pub region: RegionName,
+    /// Whether the region was a trigger or the target.
+    pub kind: TriggerKind,
+    /// The Unix timestamp of the update.
+    pub timestamp: u64,
+    /// The time between this update and the update of the previous region seen to update,
+    /// if another region has updated.
+    pub since_previous: Option<Duration>,
+    /// The average time between updates of the regions seen to update so far,
+    /// if more than one region has updated.
+    pub average_gap: Option<Duration>,
+}
+
+/// Watches a list of trigger regions and a target region,
+/// yielding a [`TriggerUpdate`] as each of them updates.
+///
+/// The regions that have not updated yet are polled one after another,
+/// as fast as the client's rate limiter allows, so no time is spent waiting between rounds.
+/// The first round only records the current update timestamps.
+/// Triggers are no longer polled once they have updated,
+/// and the stream ends after the target updates.
+/// If a request fails, the error is yielded and polling continues.
+///
+/// ## Example
+/// ```rust
+/// # use crustacean_states::client::Client;
+/// # use crustacean_states::streams::triggers::{TriggerKind, TriggerWatcher};
+/// # use futures::StreamExt;
+/// # async fn test() {
+/// # let client = Client::new("");
+/// let mut watcher = TriggerWatcher::new(client, ["Trigger One", "Trigger Two"], "Target");
+/// while let Some(Ok(update)) = watcher.next().await {
+///     match update.kind {
+///         TriggerKind::Trigger => println!("{} updated", update.region.pretty_name()),
+///         TriggerKind::Target => println!("Too late!"),
+///     }
+/// }
+/// # }
+/// ```
+pub struct TriggerWatcher {
+    inner: BoxStream<'static, Result<TriggerUpdate, ClientError>>,
+}
+
+struct Watched {
+    region: RegionName,
+    kind: TriggerKind,
+    last_update: Option<u64>,
+}
+
+struct State {
+    client: Client,
+    /// The regions that have not updated yet.
+    watched: Vec<Watched>,
+    /// The timestamps of the updates seen so far.
+    updates: Vec<u64>,
+    pending: VecDeque<Result<TriggerUpdate, ClientError>>,
+    done: bool,
+}
+
+impl TriggerWatcher {
+    /// Creates a stream of updates to the `triggers` and the `target`.
+    pub fn new<I, R>(client: Client, triggers: I, target: impl Into<RegionName>) -> Self
+    where
+        I: IntoIterator<Item = R>,
+        R: Into<RegionName>,
+    {
+        let watched = triggers
+            .into_iter()
+            .map(|r| (r.into(), TriggerKind::Trigger))
+            .chain([(target.into(), TriggerKind::Target)])
+            .map(|(region, kind)| Watched {
+                region,
+                kind,
+                last_update: None,
+            })
+            .collect();
+        let state = State {
+            client,
+            watched,
+            updates: Vec::new(),
+            pending: VecDeque::new(),
+            done: false,
+        };
+        Self {
+            inner: futures::stream::unfold(state, |mut state| async move {
+                loop {
+                    if let Some(item) = state.pending.pop_front() {
+                        return Some((item, state));
+                    }
+                    if state.done {
+                        return None;
+                    }
+                    state.poll().await;
+                }
+            })
+            .boxed(),
+        }
+    }
+}
+
+impl State {
+    async fn poll(&mut self) {
+        let mut i = 0;
+        while i < self.watched.len() {
+            let watched = &mut self.watched[i];
+            let timestamp = match self
+                .client
+                .get_region(watched.region.as_str(), [RegionShard::LastUpdate])
+                .await
+            {
+                Ok(region) => region.last_update,
+                Err(e) => {
+                    self.pending.push_back(Err(e));
+                    i += 1;
+                    continue;
+                }
+            };
+            let previous = std::mem::replace(&mut watched.last_update, timestamp);
+            match (previous, timestamp) {
+                (Some(previous), Some(timestamp)) if previous < timestamp => {
+                    let watched = self.watched.remove(i);
+                    self.record(watched.region, watched.kind, timestamp);
+                    if watched.kind == TriggerKind::Target {
+                        self.done = true;
+                        return;
+                    }
+                }
+                _ => i += 1,
+            }
+        }
+    }
+
+    fn record(&mut self, region: RegionName, kind: TriggerKind, timestamp: u64) {
+        let since_previous = self
+            .updates
+            .last()
+            .map(|&last| Duration::from_secs(timestamp.saturating_sub(last)));
+        self.updates.push(timestamp);
+        let average_gap = match (self.updates.first(), self.updates.len()) {
+            (Some(&first), len) if len > 1 => Some(Duration::from_secs(
+                timestamp.saturating_sub(first) / (len as u64 - 1),
+            )),
+            _ => None,
+        };
+        self.pending.push_back(Ok(TriggerUpdate {
+            region,
+            kind,
+            timestamp,
+            since_previous,
+            average_gap,
+        }));
+    }
+}
+
+impl_stream!(TriggerWatcher => TriggerUpdate);
@@ -0,0 +1,127 @@
+//! A stream of vote totals on the resolution at vote.
+
+use crate::{
+    client::{Client, ClientError},
+    parsers::wa::VoteTrackPoint,
+    shards::wa::{ResolutionRequest, ResolutionShard, WACouncil, WARequest},
+    streams::{impl_stream, poll_stream},
+};
+use futures::{stream::BoxStream, FutureExt};
+use std::time::Duration;
+
+/// How long a [`VoteTrackStream`] waits between polls by default.
+///
+/// The vote track only grows once an hour, so there is no need to poll it as often as
+/// [`DEFAULT_POLL_INTERVAL`](crate::streams::DEFAULT_POLL_INTERVAL).
+pub const VOTE_TRACK_POLL_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// The vote totals on a resolution so far.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct VoteTrack {
+    /// The name of the resolution.
+    pub resolution: String,
+    /// The vote totals at the end of every hour since the resolution was brought to vote,
+    /// oldest first.
+    pub points: Vec<VoteTrackPoint>,
+}
+
+/// Repeatedly polls [`ResolutionShard::VoteTrack`] for a council
+/// and yields the whole [`VoteTrack`] every time it grows.
+///
+/// The stream ends once the resolution that was at vote on the first poll
+/// is no longer at vote, or if there was no resolution at vote.
+/// If a poll fails, the error is yielded and polling continues.
+///
+/// ## Example
+/// ```rust
+/// # use crustacean_states::client::Client;
+/// # use crustacean_states::shards::wa::WACouncil;
+/// # use crustacean_states::streams::vote_track::VoteTrackStream;
+/// # use futures::StreamExt;
+/// # async fn test() {
+/// # let client = Client::new("");
+/// let mut track = VoteTrackStream::new(client, WACouncil::GeneralAssembly);
+/// while let Some(Ok(track)) = track.next().await {
+///     if let Some(latest) = track.points.last() {
+///         println!("{}: {} for, {} against", track.resolution, latest.votes_for, latest.votes_against);
+///     }
+/// }
+/// # }
+/// ```
+pub struct VoteTrackStream {
+    inner: BoxStream<'static, Result<VoteTrack, ClientError>>,
+}
+
+struct State {
+    client: Client,
+    council: WACouncil,
+    /// The name and creation time of the resolution being tracked.
+    tracking: Option<(String, u64)>,
+    hours: usize,
+}
+
+impl VoteTrackStream {
+    /// Creates a stream of the vote totals on a council's resolution at vote,
+    /// polling every [`VOTE_TRACK_POLL_INTERVAL`].
+    pub fn new(client: Client, council: WACouncil) -> Self {
+        Self::with_poll_interval(client, council, VOTE_TRACK_POLL_INTERVAL)
+    }
+
+    /// Creates a stream of the vote totals on a council's resolution at vote,
+    /// polling every `interval`.
+    pub fn with_poll_interval(client: Client, council: WACouncil, interval: Duration) -> Self {
+        let state = State {
+            client,
+            council,
+            tracking: None,
+            hours: 0,
+        };
+        Self {
+            inner: poll_stream(state, interval, |state| {
+                async move {
+                    match state.poll().await {
+                        Ok(track) => track.map(|track| track.map(Ok)),
+                        Err(e) => Some(Some(Err(e))),
+                    }
+                }
+                .boxed()
+            }),
+        }
+    }
+}
+
+impl State {
+    /// Returns `None` once the tracked resolution is no longer at vote,
+    /// and `Some(None)` if the vote track has not grown.
+    async fn poll(&mut self) -> Result<Option<Option<VoteTrack>>, ClientError> {
+        self.client.wait_until_ready().await;
+        let wa = self
+            .client
+            .get_parsed(WARequest::AtVoteResolution(ResolutionRequest::new(
+                self.council.clone(),
+                [ResolutionShard::VoteTrack].as_slice(),
+            )))
+            .await?;
+        let Some(Some(resolution)) = wa.resolution else {
+            return Ok(None);
+        };
+        let id = (resolution.name.clone(), resolution.created);
+        match &self.tracking {
+            Some(tracking) if *tracking != id => return Ok(None),
+            Some(_) => {}
+            None => self.tracking = Some(id),
+        }
+        let points = resolution.vote_track().unwrap_or_default();
+        if points.is_empty() || points.len() == self.hours {
+            return Ok(Some(None));
+        }
+        self.hours = points.len();
+        Ok(Some(Some(VoteTrack {
+            resolution: resolution.name,
+            points,
+        })))
+    }
+}
+
+impl_stream!(VoteTrackStream => VoteTrack);